@@ -1,12 +1,159 @@
 use crate::utils::parse_value;
 use graphql_parser::query::Value;
-use syn::{Attribute, AttributeArgs, Error, Meta, MetaList, NestedMeta, Result, Type};
+use syn::{Attribute, AttributeArgs, Error, Expr, Meta, MetaList, NestedMeta, Result, Type};
+
+#[derive(Debug)]
+pub enum DefaultValue {
+    Literal(Value),
+    Default,
+    Expr(Box<Expr>),
+}
+
+fn parse_default_with(lit: &syn::Lit) -> Result<DefaultValue> {
+    if let syn::Lit::Str(lit) = lit {
+        match syn::parse_str::<Expr>(&lit.value()) {
+            Ok(expr) => Ok(DefaultValue::Expr(Box::new(expr))),
+            Err(err) => Err(Error::new_spanned(
+                lit,
+                format!("Invalid expression: {}", err),
+            )),
+        }
+    } else {
+        Err(Error::new_spanned(
+            lit,
+            "Attribute 'default_with' should be a string.",
+        ))
+    }
+}
+
+#[derive(Debug)]
+pub struct CacheControl {
+    pub public: bool,
+    pub max_age: usize,
+}
+
+impl Default for CacheControl {
+    fn default() -> Self {
+        Self {
+            public: true,
+            max_age: 0,
+        }
+    }
+}
+
+impl CacheControl {
+    pub fn parse(ls: &MetaList) -> Result<Self> {
+        let mut cache_control = Self::default();
+
+        for meta in &ls.nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("max_age") => {
+                    if let syn::Lit::Int(lit) = &nv.lit {
+                        cache_control.max_age = lit.base10_parse()?;
+                    } else {
+                        return Err(Error::new_spanned(
+                            &nv.lit,
+                            "Attribute 'max_age' should be an integer.",
+                        ));
+                    }
+                }
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("public") => {
+                    cache_control.public = true;
+                }
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("private") => {
+                    cache_control.public = false;
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        meta,
+                        "Expected 'max_age = <seconds>', 'public' or 'private'.",
+                    ))
+                }
+            }
+        }
+
+        Ok(cache_control)
+    }
+}
+
+#[derive(Debug)]
+pub struct ConcreteType {
+    pub name: String,
+    pub params: Vec<Type>,
+}
+
+impl ConcreteType {
+    pub fn parse(ls: &MetaList) -> Result<Self> {
+        let mut name = None;
+        let mut params = None;
+
+        for meta in &ls.nested {
+            match meta {
+                NestedMeta::Meta(Meta::NameValue(nv)) if nv.path.is_ident("name") => {
+                    if let syn::Lit::Str(lit) = &nv.lit {
+                        name = Some(lit.value());
+                    } else {
+                        return Err(Error::new_spanned(
+                            &nv.lit,
+                            "Attribute 'name' should be a string.",
+                        ));
+                    }
+                }
+                NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("params") => {
+                    let mut types = Vec::new();
+                    for param in &ls.nested {
+                        match param {
+                            NestedMeta::Meta(Meta::Path(p)) => {
+                                types.push(syn::parse_quote!(#p));
+                            }
+                            NestedMeta::Lit(syn::Lit::Str(lit)) => {
+                                match syn::parse_str(&lit.value()) {
+                                    Ok(ty) => types.push(ty),
+                                    Err(err) => {
+                                        return Err(Error::new_spanned(
+                                            lit,
+                                            format!("Invalid type: {}", err),
+                                        ))
+                                    }
+                                }
+                            }
+                            _ => {
+                                return Err(Error::new_spanned(
+                                    param,
+                                    "Expected a type path or a string.",
+                                ))
+                            }
+                        }
+                    }
+                    params = Some(types);
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        meta,
+                        "Expected 'name = \"...\"' or 'params(...)'.",
+                    ))
+                }
+            }
+        }
+
+        match (name, params) {
+            (Some(name), Some(params)) => Ok(Self { name, params }),
+            _ => Err(Error::new_spanned(
+                ls,
+                "Both 'name' and 'params' are required.",
+            )),
+        }
+    }
+}
 
 #[derive(Debug)]
 pub struct Object {
     pub internal: bool,
     pub name: Option<String>,
     pub desc: Option<String>,
+    pub extends: bool,
+    pub cache_control: Option<CacheControl>,
+    pub concretes: Vec<ConcreteType>,
 }
 
 impl Object {
@@ -14,12 +161,24 @@ impl Object {
         let mut internal = false;
         let mut name = None;
         let mut desc = None;
+        let mut extends = false;
+        let mut cache_control = None;
+        let mut concretes = Vec::new();
 
         for arg in args {
             match arg {
                 NestedMeta::Meta(Meta::Path(p)) if p.is_ident("internal") => {
                     internal = true;
                 }
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("extends") => {
+                    extends = true;
+                }
+                NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("cache_control") => {
+                    cache_control = Some(CacheControl::parse(&ls)?);
+                }
+                NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("concretes") => {
+                    concretes.push(ConcreteType::parse(&ls)?);
+                }
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = nv.lit {
@@ -49,6 +208,9 @@ impl Object {
             internal,
             name,
             desc,
+            extends,
+            cache_control,
+            concretes,
         })
     }
 }
@@ -57,7 +219,8 @@ impl Object {
 pub struct Argument {
     pub name: Option<String>,
     pub desc: Option<String>,
-    pub default: Option<Value>,
+    pub default: Option<DefaultValue>,
+    pub validator: Option<MetaList>,
 }
 
 impl Argument {
@@ -65,12 +228,19 @@ impl Argument {
         let mut name = None;
         let mut desc = None;
         let mut default = None;
+        let mut validator = None;
 
         for attr in attrs {
             match attr.parse_meta() {
                 Ok(Meta::List(ls)) if ls.path.is_ident("arg") => {
                     for meta in &ls.nested {
                         match meta {
+                            NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("validator") => {
+                                validator = Some(ls.clone());
+                            }
+                            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("default") => {
+                                default = Some(DefaultValue::Default);
+                            }
                             NestedMeta::Meta(Meta::NameValue(nv)) => {
                                 if nv.path.is_ident("name") {
                                     if let syn::Lit::Str(lit) = &nv.lit {
@@ -90,6 +260,8 @@ impl Argument {
                                             "Attribute 'desc' should be a string.",
                                         ));
                                     }
+                                } else if nv.path.is_ident("default_with") {
+                                    default = Some(parse_default_with(&nv.lit)?);
                                 } else if nv.path.is_ident("default") {
                                     if let syn::Lit::Str(lit) = &nv.lit {
                                         match parse_value(&lit.value()) {
@@ -99,7 +271,9 @@ impl Argument {
                                                     "The default cannot be a variable",
                                                 ))
                                             }
-                                            Ok(value) => default = Some(value),
+                                            Ok(value) => {
+                                                default = Some(DefaultValue::Literal(value))
+                                            }
                                             Err(err) => {
                                                 return Err(Error::new_spanned(
                                                     &nv.lit,
@@ -127,6 +301,7 @@ impl Argument {
             name,
             desc,
             default,
+            validator,
         })
     }
 }
@@ -136,6 +311,11 @@ pub struct Field {
     pub name: Option<String>,
     pub desc: Option<String>,
     pub deprecation: Option<String>,
+    pub guard: Option<MetaList>,
+    pub external: bool,
+    pub requires: Option<String>,
+    pub provides: Option<String>,
+    pub cache_control: Option<CacheControl>,
 }
 
 impl Field {
@@ -144,6 +324,11 @@ impl Field {
         let mut name = None;
         let mut desc = None;
         let mut deprecation = None;
+        let mut guard = None;
+        let mut external = false;
+        let mut requires = None;
+        let mut provides = None;
+        let mut cache_control = None;
 
         for attr in attrs {
             match attr.parse_meta() {
@@ -182,8 +367,37 @@ impl Field {
                                             "Attribute 'deprecation' should be a string.",
                                         ));
                                     }
+                                } else if nv.path.is_ident("requires") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        requires = Some(lit.value());
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'requires' should be a string.",
+                                        ));
+                                    }
+                                } else if nv.path.is_ident("provides") {
+                                    if let syn::Lit::Str(lit) = &nv.lit {
+                                        provides = Some(lit.value());
+                                    } else {
+                                        return Err(Error::new_spanned(
+                                            &nv.lit,
+                                            "Attribute 'provides' should be a string.",
+                                        ));
+                                    }
                                 }
                             }
+                            NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("guard") => {
+                                guard = Some(ls.clone());
+                            }
+                            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("external") => {
+                                external = true;
+                            }
+                            NestedMeta::Meta(Meta::List(ls))
+                                if ls.path.is_ident("cache_control") =>
+                            {
+                                cache_control = Some(CacheControl::parse(ls)?);
+                            }
                             _ => {}
                         }
                     }
@@ -197,6 +411,11 @@ impl Field {
                 name,
                 desc,
                 deprecation,
+                guard,
+                external,
+                requires,
+                provides,
+                cache_control,
             }))
         } else {
             Ok(None)
@@ -323,7 +542,9 @@ pub struct InputField {
     pub internal: bool,
     pub name: Option<String>,
     pub desc: Option<String>,
-    pub default: Option<Value>,
+    pub default: Option<DefaultValue>,
+    pub guard: Option<MetaList>,
+    pub validator: Option<MetaList>,
 }
 
 impl InputField {
@@ -332,6 +553,8 @@ impl InputField {
         let mut name = None;
         let mut desc = None;
         let mut default = None;
+        let mut guard = None;
+        let mut validator = None;
 
         for attr in attrs {
             if attr.path.is_ident("field") {
@@ -341,6 +564,15 @@ impl InputField {
                             NestedMeta::Meta(Meta::Path(p)) if p.is_ident("internal") => {
                                 internal = true;
                             }
+                            NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("guard") => {
+                                guard = Some(ls);
+                            }
+                            NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("validator") => {
+                                validator = Some(ls);
+                            }
+                            NestedMeta::Meta(Meta::Path(p)) if p.is_ident("default") => {
+                                default = Some(DefaultValue::Default);
+                            }
                             NestedMeta::Meta(Meta::NameValue(nv)) => {
                                 if nv.path.is_ident("name") {
                                     if let syn::Lit::Str(lit) = nv.lit {
@@ -360,6 +592,8 @@ impl InputField {
                                             "Attribute 'desc' should be a string.",
                                         ));
                                     }
+                                } else if nv.path.is_ident("default_with") {
+                                    default = Some(parse_default_with(&nv.lit)?);
                                 } else if nv.path.is_ident("default") {
                                     if let syn::Lit::Str(lit) = nv.lit {
                                         match parse_value(&lit.value()) {
@@ -369,7 +603,9 @@ impl InputField {
                                                     "The default cannot be a variable",
                                                 ))
                                             }
-                                            Ok(value) => default = Some(value),
+                                            Ok(value) => {
+                                                default = Some(DefaultValue::Literal(value))
+                                            }
                                             Err(err) => {
                                                 return Err(Error::new_spanned(
                                                     &lit,
@@ -397,6 +633,8 @@ impl InputField {
             name,
             desc,
             default,
+            guard,
+            validator,
         })
     }
 }
@@ -457,7 +695,7 @@ pub struct InterfaceFieldArgument {
     pub name: String,
     pub desc: Option<String>,
     pub ty: Type,
-    pub default: Option<Value>,
+    pub default: Option<DefaultValue>,
 }
 
 impl InterfaceFieldArgument {
@@ -469,6 +707,9 @@ impl InterfaceFieldArgument {
 
         for meta in &ls.nested {
             match meta {
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("default") => {
+                    default = Some(DefaultValue::Default);
+                }
                 NestedMeta::Meta(Meta::NameValue(nv)) => {
                     if nv.path.is_ident("name") {
                         if let syn::Lit::Str(lit) = &nv.lit {
@@ -501,6 +742,8 @@ impl InterfaceFieldArgument {
                                 "Attribute 'type' should be a string.",
                             ));
                         }
+                    } else if nv.path.is_ident("default_with") {
+                        default = Some(parse_default_with(&nv.lit)?);
                     } else if nv.path.is_ident("default") {
                         if let syn::Lit::Str(lit) = &nv.lit {
                             match parse_value(&lit.value()) {
@@ -510,7 +753,7 @@ impl InterfaceFieldArgument {
                                         "The default cannot be a variable",
                                     ))
                                 }
-                                Ok(value) => default = Some(value),
+                                Ok(value) => default = Some(DefaultValue::Literal(value)),
                                 Err(err) => {
                                     return Err(Error::new_spanned(
                                         &nv.lit,
@@ -699,3 +942,66 @@ impl Interface {
         })
     }
 }
+
+#[derive(Debug)]
+pub struct Scalar {
+    pub internal: bool,
+    pub name: Option<String>,
+    pub desc: Option<String>,
+    pub specified_by_url: Option<String>,
+}
+
+impl Scalar {
+    pub fn parse(args: AttributeArgs) -> Result<Self> {
+        let mut internal = false;
+        let mut name = None;
+        let mut desc = None;
+        let mut specified_by_url = None;
+
+        for arg in args {
+            match arg {
+                NestedMeta::Meta(Meta::Path(p)) if p.is_ident("internal") => {
+                    internal = true;
+                }
+                NestedMeta::Meta(Meta::NameValue(nv)) => {
+                    if nv.path.is_ident("name") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            name = Some(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'name' should be a string.",
+                            ));
+                        }
+                    } else if nv.path.is_ident("desc") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            desc = Some(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'desc' should be a string.",
+                            ));
+                        }
+                    } else if nv.path.is_ident("specified_by_url") {
+                        if let syn::Lit::Str(lit) = nv.lit {
+                            specified_by_url = Some(lit.value());
+                        } else {
+                            return Err(Error::new_spanned(
+                                &nv.lit,
+                                "Attribute 'specified_by_url' should be a string.",
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            internal,
+            name,
+            desc,
+            specified_by_url,
+        })
+    }
+}
@@ -61,10 +61,10 @@ pub fn generate(enum_args: &args::Enum, input: &DeriveInput) -> Result<TokenStre
             }
         });
         schema_enum_items.push(quote! {
-            enum_items.insert(#gql_item_name, #crate_name::registry::EnumValue {
-                name: #gql_item_name,
-                description: #item_desc,
-                deprecation: #item_deprecation,
+            enum_items.insert(#gql_item_name.into(), #crate_name::registry::EnumValue {
+                name: std::borrow::Cow::Borrowed(#gql_item_name),
+                description: #item_desc.map(std::borrow::Cow::Borrowed),
+                deprecation: #item_deprecation.map(std::borrow::Cow::Borrowed),
             });
         });
     }
@@ -90,8 +90,8 @@ pub fn generate(enum_args: &args::Enum, input: &DeriveInput) -> Result<TokenStre
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
                 registry.create_type::<Self, _>(|registry| {
                     #crate_name::registry::Type::Enum {
-                        name: #gql_typename,
-                        description: #desc,
+                        name: std::borrow::Cow::Borrowed(#gql_typename),
+                        description: #desc.map(std::borrow::Cow::Borrowed),
                         enum_values: {
                             let mut enum_items = std::collections::HashMap::new();
                             #(#schema_enum_items)*
@@ -103,9 +103,13 @@ pub fn generate(enum_args: &args::Enum, input: &DeriveInput) -> Result<TokenStre
         }
 
         impl #crate_name::GQLInputValue for #ident {
-            fn parse(value: &#crate_name::Value) -> Option<Self> {
+            fn parse(value: &#crate_name::Value) -> #crate_name::InputValueResult<Self> {
                 #crate_name::GQLEnum::parse_enum(value)
             }
+
+            fn to_value(&self) -> #crate_name::Value {
+                #crate_name::GQLEnum::to_enum_value(self)
+            }
         }
 
         #[#crate_name::async_trait::async_trait]
@@ -1,5 +1,5 @@
 use crate::args;
-use crate::utils::{build_value_repr, get_crate_name};
+use crate::utils::{generate_default, generate_guard, generate_validator, get_crate_name};
 use inflector::Inflector;
 use proc_macro::TokenStream;
 use quote::quote;
@@ -44,6 +44,8 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
     let mut get_fields = Vec::new();
     let mut fields = Vec::new();
     let mut schema_fields = Vec::new();
+    let mut check_guards = Vec::new();
+    let mut to_values = Vec::new();
 
     for field in &s.fields {
         let field_args = args::InputField::parse(&field.attrs)?;
@@ -57,41 +59,62 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
             .as_ref()
             .map(|s| quote! {Some(#s)})
             .unwrap_or_else(|| quote! {None});
-        let default = field_args
+        let default_repr = field_args
             .default
             .as_ref()
-            .map(|v| {
-                let s = v.to_string();
-                quote! {Some(#s)}
-            })
+            .map(|default| generate_default(&crate_name, default, ty));
+        let default = default_repr
+            .as_ref()
+            .map(|default| quote! {Some(#default)})
             .unwrap_or_else(|| quote! {None});
 
-        if let Some(default) = &field_args.default {
-            let default_repr = build_value_repr(&crate_name, default);
+        if let Some(default_repr) = &default_repr {
             get_fields.push(quote! {
                 let #ident:#ty = {
                     match obj.get(#name) {
-                        Some(value) => #crate_name::GQLInputValue::parse(value)?,
+                        Some(value) => #crate_name::GQLInputValue::parse(value),
                         None => {
                             let default = #default_repr;
-                            #crate_name::GQLInputValue::parse(&default)?
+                            #crate_name::GQLInputValue::parse(&default)
                         }
                     }
+                    .map_err(|err| err.with_field(#name))?
                 };
             });
         } else {
             get_fields.push(quote! {
-                let #ident:#ty = #crate_name::GQLInputValue::parse(obj.get(#name).unwrap_or(&#crate_name::Value::Null))?;
+                let #ident:#ty = #crate_name::GQLInputValue::parse_optional(obj.get(#name))
+                    .map_err(|err| err.with_field(#name))?;
             });
         }
 
+        let guard = match &field_args.guard {
+            Some(guard) => {
+                let guard = generate_guard(&crate_name, guard)?;
+                quote! { #guard?; }
+            }
+            None => quote! {},
+        };
+        check_guards.push(quote! {
+            if let Some(value) = obj.get(#name) {
+                #guard
+                <#ty as #crate_name::GQLInputValue>::check_guards(ctx, value).await?;
+            }
+        });
+
+        let validator = generate_validator(&crate_name, field_args.validator.as_ref())?;
+
+        to_values.push(quote! {
+            map.insert(#name.to_string(), #crate_name::GQLInputValue::to_value(&self.#ident));
+        });
         fields.push(ident);
         schema_fields.push(quote! {
             #crate_name::registry::InputValue {
-                name: #name,
-                description: #desc,
+                name: std::borrow::Cow::Borrowed(#name),
+                description: #desc.map(std::borrow::Cow::Borrowed),
                 ty: <#ty as #crate_name::GQLType>::create_type_info(registry),
                 default_value: #default,
+                validator: #validator,
             }
         })
     }
@@ -106,24 +129,42 @@ pub fn generate(object_args: &args::InputObject, input: &DeriveInput) -> Result<
 
             fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
                 registry.create_type::<Self, _>(|registry| #crate_name::registry::Type::InputObject {
-                    name: #gql_typename,
-                    description: #desc,
+                    name: std::borrow::Cow::Borrowed(#gql_typename),
+                    description: #desc.map(std::borrow::Cow::Borrowed),
                     input_fields: vec![#(#schema_fields),*]
                 })
             }
         }
 
         impl #crate_name::GQLInputValue for #ident {
-            fn parse(value: &#crate_name::Value) -> Option<Self> {
+            fn parse(value: &#crate_name::Value) -> #crate_name::InputValueResult<Self> {
                 use #crate_name::GQLType;
 
                 if let #crate_name::Value::Object(obj) = value {
                     #(#get_fields)*
-                    Some(Self { #(#fields),* })
+                    Ok(Self { #(#fields),* })
                 } else {
-                    None
+                    Err(#crate_name::InputValueError::expected_type(#gql_typename, value))
                 }
             }
+
+            fn to_value(&self) -> #crate_name::Value {
+                let mut map = std::collections::BTreeMap::new();
+                #(#to_values)*
+                #crate_name::Value::Object(map)
+            }
+
+            fn check_guards<'a>(
+                ctx: &'a #crate_name::Context<'a>,
+                value: &'a #crate_name::Value,
+            ) -> std::pin::Pin<Box<dyn std::future::Future<Output = #crate_name::Result<()>> + Send + 'a>> {
+                Box::pin(async move {
+                    if let #crate_name::Value::Object(obj) = value {
+                        #(#check_guards)*
+                    }
+                    Ok(())
+                })
+            }
         }
 
         impl #crate_name::GQLInputObject for #ident {}
@@ -1,7 +1,7 @@
 use crate::args;
 use crate::args::{InterfaceField, InterfaceFieldArgument};
 use crate::output_type::OutputType;
-use crate::utils::{build_value_repr, get_crate_name};
+use crate::utils::{generate_default, get_crate_name};
 use inflector::Inflector;
 use proc_macro::TokenStream;
 use proc_macro2::{Ident, Span};
@@ -88,7 +88,6 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         let mut calls = Vec::new();
         let mut use_params = Vec::new();
         let mut decl_params = Vec::new();
-        let mut get_params = Vec::new();
         let mut schema_args = Vec::new();
 
         if *context {
@@ -108,34 +107,24 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
             decl_params.push(quote! { #ident: #ty });
             use_params.push(quote! { #ident });
 
-            let param_default = match &default {
-                Some(default) => {
-                    let repr = build_value_repr(&crate_name, &default);
-                    quote! {|| #repr }
-                }
-                None => quote! { || #crate_name::Value::Null },
-            };
-            get_params.push(quote! {
-                let #ident: #ty = ctx.param_value(#name, #param_default)?;
-            });
-
             let desc = desc
                 .as_ref()
                 .map(|s| quote! {Some(#s)})
                 .unwrap_or_else(|| quote! {None});
             let schema_default = default
                 .as_ref()
-                .map(|v| {
-                    let s = v.to_string();
-                    quote! {Some(#s)}
+                .map(|default| {
+                    let default = generate_default(&crate_name, default, ty);
+                    quote! {Some(#default)}
                 })
                 .unwrap_or_else(|| quote! {None});
             schema_args.push(quote! {
-                args.insert(#name, #crate_name::registry::InputValue {
-                    name: #name,
-                    description: #desc,
+                args.insert(#name.into(), #crate_name::registry::InputValue {
+                    name: std::borrow::Cow::Borrowed(#name),
+                    description: #desc.map(std::borrow::Cow::Borrowed),
                     ty: <#ty as #crate_name::GQLType>::create_type_info(registry),
                     default_value: #schema_default,
+                    validator: None,
                 });
             });
         }
@@ -173,37 +162,29 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
         let schema_ty = ty.value_type();
 
         schema_fields.push(quote! {
-            fields.insert(#name, #crate_name::registry::Field {
-                name: #name,
-                description: #desc,
+            fields.insert(#name.into(), #crate_name::registry::Field {
+                name: std::borrow::Cow::Borrowed(#name),
+                description: #desc.map(std::borrow::Cow::Borrowed),
                 args: {
                     let mut args = std::collections::HashMap::new();
                     #(#schema_args)*
                     args
                 },
                 ty: <#schema_ty as #crate_name::GQLType>::create_type_info(registry),
-                deprecation: #deprecation,
+                deprecation: #deprecation.map(std::borrow::Cow::Borrowed),
+                external: false,
+                requires: None,
+                provides: None,
+                cache_control: Default::default(),
             });
         });
 
-        let resolve_obj = match &ty {
-            OutputType::Value(_) => quote! {
-                self.#method_name(#(#use_params),*).await
-            },
-            OutputType::Result(_, _) => {
-                quote! {
-                    self.#method_name(#(#use_params),*).await.
-                        map_err(|err| err.with_position(field.position))?
-                }
-            }
-        };
-
+        // Forward to the implementing object, so that its guards are also checked.
         resolvers.push(quote! {
             if field.name.as_str() == #name {
-                #(#get_params)*
-                let ctx_obj = ctx.with_item(&field.selection_set);
-                return #crate_name::GQLOutputValue::resolve(&#resolve_obj, &ctx_obj).await.
-                    map_err(|err| err.with_position(field.position).into());
+                return match self {
+                    #(#ident::#enum_names(obj) => #crate_name::GQLObject::resolve_field(obj, ctx, field).await,)*
+                };
             }
         });
     }
@@ -228,8 +209,8 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
                     #(#registry_types)*
 
                     #crate_name::registry::Type::Interface {
-                        name: #gql_typename,
-                        description: #desc,
+                        name: std::borrow::Cow::Borrowed(#gql_typename),
+                        description: #desc.map(std::borrow::Cow::Borrowed),
                         fields: {
                             let mut fields = std::collections::HashMap::new();
                             #(#schema_fields)*
@@ -4,8 +4,10 @@ mod args;
 mod r#enum;
 mod input_object;
 mod interface;
+mod merged_object;
 mod object;
 mod output_type;
+mod scalar;
 mod union;
 mod utils;
 
@@ -27,6 +29,20 @@ pub fn Object(args: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_attribute]
+#[allow(non_snake_case)]
+pub fn MergedObject(args: TokenStream, input: TokenStream) -> TokenStream {
+    let object_args = match args::Object::parse(parse_macro_input!(args as AttributeArgs)) {
+        Ok(object_args) => object_args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let input = parse_macro_input!(input as DeriveInput);
+    match merged_object::generate(&object_args, &input) {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
 #[proc_macro_attribute]
 #[allow(non_snake_case)]
 pub fn Enum(args: TokenStream, input: TokenStream) -> TokenStream {
@@ -82,3 +98,23 @@ pub fn Union(args: TokenStream, input: TokenStream) -> TokenStream {
         Err(err) => err.to_compile_error().into(),
     }
 }
+
+#[proc_macro_attribute]
+#[allow(non_snake_case)]
+pub fn Scalar(args: TokenStream, input: TokenStream) -> TokenStream {
+    let scalar_args = match args::Scalar::parse(parse_macro_input!(args as AttributeArgs)) {
+        Ok(scalar_args) => scalar_args,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let res = match syn::parse::<ItemImpl>(input.clone()) {
+        Ok(mut item_impl) => scalar::generate(&scalar_args, &mut item_impl),
+        Err(_) => {
+            let input = parse_macro_input!(input as DeriveInput);
+            scalar::generate_newtype(&scalar_args, &input)
+        }
+    };
+    match res {
+        Ok(expanded) => expanded,
+        Err(err) => err.to_compile_error().into(),
+    }
+}
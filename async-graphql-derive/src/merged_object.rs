@@ -0,0 +1,127 @@
+use crate::args;
+use crate::utils::{generate_cache_control, get_crate_name};
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Index, Member, Result};
+
+pub fn generate(object_args: &args::Object, input: &DeriveInput) -> Result<TokenStream> {
+    let crate_name = get_crate_name(object_args.internal);
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let s = match &input.data {
+        Data::Struct(s) => s,
+        _ => return Err(Error::new_spanned(input, "It should be a struct.")),
+    };
+    if s.fields.is_empty() {
+        return Err(Error::new_spanned(
+            input,
+            "A merged object should have at least one field.",
+        ));
+    }
+    if !object_args.concretes.is_empty() {
+        return Err(Error::new_spanned(
+            input,
+            "The concrete types are not supported by a merged object.",
+        ));
+    }
+
+    let gql_typename = object_args
+        .name
+        .clone()
+        .unwrap_or_else(|| ident.to_string());
+    let desc = object_args
+        .desc
+        .as_ref()
+        .map(|s| quote! {Some(#s)})
+        .unwrap_or_else(|| quote! {None});
+    let extends = object_args.extends;
+    let cache_control = generate_cache_control(&crate_name, &object_args.cache_control);
+
+    let mut create_types = Vec::new();
+    let mut type_names = Vec::new();
+    let mut resolvers = Vec::new();
+    let mut find_entities = Vec::new();
+
+    for (idx, field) in s.fields.iter().enumerate() {
+        let ty = &field.ty;
+        let member = match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(idx)),
+        };
+
+        create_types.push(quote! {
+            <#ty as #crate_name::GQLType>::create_type_info(registry);
+        });
+        type_names.push(quote! {
+            &<#ty as #crate_name::GQLType>::type_name()
+        });
+        resolvers.push(quote! {
+            if ctx
+                .registry()
+                .types
+                .get(<#ty as #crate_name::GQLType>::type_name().as_ref())
+                .and_then(|ty| ty.field_by_name(&field.name))
+                .is_some()
+            {
+                return #crate_name::GQLObject::resolve_field(&self.#member, ctx, field).await;
+            }
+        });
+        find_entities.push(quote! {
+            if let Some(value) = #crate_name::GQLObject::find_entity(&self.#member, ctx, params).await? {
+                return Ok(Some(value));
+            }
+        });
+    }
+
+    let expanded = quote! {
+        #input
+
+        impl #impl_generics #crate_name::GQLType for #ident #ty_generics #where_clause {
+            fn type_name() -> std::borrow::Cow<'static, str> {
+                std::borrow::Cow::Borrowed(#gql_typename)
+            }
+
+            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                registry.create_type::<Self, _>(|registry| {
+                    #(#create_types)*
+                    #crate_name::registry::Type::Object {
+                        name: #gql_typename.to_string(),
+                        description: #desc.map(std::borrow::Cow::Borrowed),
+                        fields: registry.merge_object_fields(#gql_typename, &[#(#type_names),*]),
+                        extends: #extends,
+                        keys: None,
+                        cache_control: #cache_control,
+                    }
+                })
+            }
+        }
+
+        #[#crate_name::async_trait::async_trait]
+        impl #impl_generics #crate_name::GQLObject for #ident #ty_generics #where_clause {
+            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>, field: &#crate_name::graphql_parser::query::Field) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                use #crate_name::ErrorWithPosition;
+
+                #(#resolvers)*
+
+                #crate_name::anyhow::bail!(#crate_name::QueryError::FieldNotFound {
+                    field_name: field.name.clone(),
+                    object: #gql_typename.to_string(),
+                }
+                .with_position(field.position));
+            }
+
+            async fn resolve_inline_fragment(&self, name: &str, ctx: &#crate_name::ContextSelectionSet<'_>, result: &mut #crate_name::serde_json::Map<String, serde_json::Value>) -> #crate_name::Result<()> {
+                if name == #gql_typename {
+                    #crate_name::do_resolve(ctx, self, result).await?;
+                }
+                Ok(())
+            }
+
+            async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<Option<#crate_name::serde_json::Value>> {
+                #(#find_entities)*
+                Ok(None)
+            }
+        }
+    };
+    Ok(expanded.into())
+}
@@ -1,6 +1,8 @@
 use crate::args;
 use crate::output_type::OutputType;
-use crate::utils::{build_value_repr, get_crate_name};
+use crate::utils::{
+    generate_cache_control, generate_default, generate_guard, generate_validator, get_crate_name,
+};
 use inflector::Inflector;
 use proc_macro::TokenStream;
 use quote::quote;
@@ -19,7 +21,6 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         ),
         _ => return Err(Error::new_spanned(&item_impl.self_ty, "Invalid type")),
     };
-    let generics = &item_impl.generics;
 
     let gql_typename = object_args
         .name
@@ -31,12 +32,127 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         .map(|s| quote! {Some(#s)})
         .unwrap_or_else(|| quote! {None});
 
+    let extends = object_args.extends;
+    let cache_control = generate_cache_control(&crate_name, &object_args.cache_control);
+
     let mut resolvers = Vec::new();
     let mut schema_fields = Vec::new();
+    let mut entity_types = Vec::new();
+    let mut find_entities = Vec::new();
 
     for item in &mut item_impl.items {
         if let ImplItem::Method(method) = item {
-            if let Some(field) = args::Field::parse(&method.attrs)? {
+            if method.attrs.iter().any(|attr| attr.path.is_ident("entity")) {
+                let ty = match &method.sig.output {
+                    ReturnType::Type(_, ty) => OutputType::parse(ty)?,
+                    ReturnType::Default => {
+                        return Err(Error::new_spanned(&method.sig.output, "Missing type"))
+                    }
+                };
+
+                let mut arg_ctx = false;
+                let mut keys = Vec::new();
+                let mut get_keys = Vec::new();
+                let mut use_keys = Vec::new();
+
+                for (idx, arg) in method.sig.inputs.iter_mut().enumerate() {
+                    if let FnArg::Receiver(receiver) = arg {
+                        if idx != 0 {
+                            return Err(Error::new_spanned(
+                                receiver,
+                                "The self receiver must be the first parameter.",
+                            ));
+                        }
+                    } else if let FnArg::Typed(pat) = arg {
+                        if idx == 0 {
+                            return Err(Error::new_spanned(
+                                pat,
+                                "The self receiver must be the first parameter.",
+                            ));
+                        }
+
+                        match (&*pat.pat, &*pat.ty) {
+                            (Pat::Ident(arg_ident), Type::Path(arg_ty)) => {
+                                let name = args::Argument::parse(&pat.attrs)?
+                                    .name
+                                    .unwrap_or_else(|| arg_ident.ident.to_string().to_camel_case());
+                                let ident = &arg_ident.ident;
+                                get_keys.push(quote! {
+                                    let #ident: #arg_ty = #crate_name::GQLInputValue::parse(&params[#name])
+                                        .map_err(|err| err.into_argument_error(#name).with_position(ctx.position))?;
+                                });
+                                use_keys.push(quote! { #ident });
+                                keys.push(name);
+                                pat.attrs.clear();
+                            }
+                            (_, Type::Reference(TypeReference { elem, .. })) => {
+                                if let Type::Path(path) = elem.as_ref() {
+                                    if idx != 1
+                                        || path.path.segments.last().unwrap().ident != "Context"
+                                    {
+                                        return Err(Error::new_spanned(
+                                            arg,
+                                            "The Context must be the second argument.",
+                                        ));
+                                    }
+                                    arg_ctx = true;
+                                }
+                            }
+                            _ => return Err(Error::new_spanned(arg, "Invalid argument type.")),
+                        }
+                    }
+                }
+
+                if keys.is_empty() {
+                    return Err(Error::new_spanned(
+                        &method.sig,
+                        "An entity resolver must have at least one key argument.",
+                    ));
+                }
+
+                let entity_ty = ty.value_type();
+                let key = keys.join(" ");
+                entity_types.push(quote! {
+                    <#entity_ty as #crate_name::GQLType>::create_type_info(registry);
+                    if let Some(#crate_name::registry::Type::Object { keys, .. }) = registry
+                        .types
+                        .get_mut(<#entity_ty as #crate_name::GQLType>::type_name().as_ref())
+                    {
+                        let keys = keys.get_or_insert_with(Vec::new);
+                        if !keys.iter().any(|k| k == #key) {
+                            keys.push(#key.to_string());
+                        }
+                    }
+                });
+
+                let ctx_param = match arg_ctx {
+                    true => quote! { &ctx, },
+                    false => quote! {},
+                };
+                let entity_ident = &method.sig.ident;
+                let resolve_obj = match &ty {
+                    OutputType::Value(_) => quote! {
+                        self.#entity_ident(#ctx_param #(#use_keys),*).await
+                    },
+                    OutputType::Result(_, _) => quote! {
+                        self.#entity_ident(#ctx_param #(#use_keys),*).await
+                            .map_err(|err| err.with_position(ctx.position))?
+                    },
+                };
+                find_entities.push(quote! {
+                    if typename.as_str() == <#entity_ty as #crate_name::GQLType>::type_name()
+                        && [#(#keys),*].iter().all(|key| params.contains_key(*key))
+                    {
+                        #(#get_keys)*
+                        let ctx_obj = ctx.with_item(&ctx.selection_set);
+                        return #crate_name::GQLOutputValue::resolve(&#resolve_obj, &ctx_obj)
+                            .await
+                            .map(Some);
+                    }
+                });
+
+                method.attrs.clear();
+            } else if let Some(field) = args::Field::parse(&method.attrs)? {
                 let field_name = field
                     .name
                     .clone()
@@ -51,6 +167,18 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     .as_ref()
                     .map(|s| quote! {Some(#s)})
                     .unwrap_or_else(|| quote! {None});
+                let external = field.external;
+                let field_cache_control = generate_cache_control(&crate_name, &field.cache_control);
+                let requires = field
+                    .requires
+                    .as_ref()
+                    .map(|s| quote! {Some(#s)})
+                    .unwrap_or_else(|| quote! {None});
+                let provides = field
+                    .provides
+                    .as_ref()
+                    .map(|s| quote! {Some(#s)})
+                    .unwrap_or_else(|| quote! {None});
                 let ty = match &method.sig.output {
                     ReturnType::Type(_, ty) => OutputType::parse(ty)?,
                     ReturnType::Default => {
@@ -113,6 +241,7 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         name,
                         desc,
                         default,
+                        validator,
                     },
                 ) in args
                 {
@@ -123,49 +252,52 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                         .as_ref()
                         .map(|s| quote! {Some(#s)})
                         .unwrap_or_else(|| quote! {None});
+                    let default = default
+                        .as_ref()
+                        .map(|default| generate_default(&crate_name, default, ty));
                     let schema_default = default
                         .as_ref()
-                        .map(|v| {
-                            let s = v.to_string();
-                            quote! {Some(#s)}
-                        })
+                        .map(|default| quote! {Some(#default)})
                         .unwrap_or_else(|| quote! {None});
+                    let validator = generate_validator(&crate_name, validator.as_ref())?;
 
                     schema_args.push(quote! {
-                        args.insert(#name, #crate_name::registry::InputValue {
-                            name: #name,
-                            description: #desc,
+                        args.insert(#name.into(), #crate_name::registry::InputValue {
+                            name: std::borrow::Cow::Borrowed(#name),
+                            description: #desc.map(std::borrow::Cow::Borrowed),
                             ty: <#ty as #crate_name::GQLType>::create_type_info(registry),
                             default_value: #schema_default,
+                            validator: #validator,
                         });
                     });
 
                     use_params.push(quote! { #ident });
 
                     let default = match &default {
-                        Some(default) => {
-                            let repr = build_value_repr(&crate_name, &default);
-                            quote! {|| #repr }
-                        }
-                        None => quote! { || #crate_name::Value::Null },
+                        Some(default) => quote! { Some(|| #default) },
+                        None => quote! { None::<fn() -> #crate_name::Value> },
                     };
                     get_params.push(quote! {
-                        let #ident: #ty = ctx.param_value(#name, #default)?;
+                        let #ident: #ty = ctx.param_value(#name, #default).await?;
                     });
                 }
 
                 let schema_ty = ty.value_type();
                 schema_fields.push(quote! {
-                    fields.insert(#field_name, #crate_name::registry::Field {
-                        name: #field_name,
-                        description: #field_desc,
+                    fields.insert(#field_name.into(), #crate_name::registry::Field {
+                        name: std::borrow::Cow::Borrowed(#field_name),
+                        description: #field_desc.map(std::borrow::Cow::Borrowed),
                         args: {
                             let mut args = std::collections::HashMap::new();
                             #(#schema_args)*
                             args
                         },
                         ty: <#schema_ty as #crate_name::GQLType>::create_type_info(registry),
-                        deprecation: #field_deprecation,
+                        deprecation: #field_deprecation.map(std::borrow::Cow::Borrowed),
+                        external: #external,
+                        requires: #requires,
+                        provides: #provides,
+                        cache_control: #field_cache_control,
                     });
                 });
 
@@ -187,8 +319,19 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
                     }
                 };
 
+                let guard = match &field.guard {
+                    Some(guard) => {
+                        let guard = generate_guard(&crate_name, guard)?;
+                        quote! {
+                            #guard.map_err(|err| err.with_position(field.position))?;
+                        }
+                    }
+                    None => quote! {},
+                };
+
                 resolvers.push(quote! {
                     if field.name.as_str() == #field_name {
+                        #guard
                         #(#get_params)*
                         let ctx_obj = ctx.with_item(&field.selection_set);
                         return #crate_name::GQLOutputValue::resolve(&#resolve_obj, &ctx_obj).await.
@@ -201,47 +344,231 @@ pub fn generate(object_args: &args::Object, item_impl: &mut ItemImpl) -> Result<
         }
     }
 
-    let expanded = quote! {
-        #item_impl
+    let find_entity_body = if find_entities.is_empty() {
+        None
+    } else {
+        Some(quote! {
+            use #crate_name::ErrorWithPosition;
+
+            let params = match params {
+                #crate_name::Value::Object(params) => params,
+                _ => return Ok(None),
+            };
+            let typename = match params.get("__typename") {
+                Some(#crate_name::Value::String(typename)) => typename,
+                _ => #crate_name::anyhow::bail!(#crate_name::QueryError::TypeNameNotExists.with_position(ctx.position)),
+            };
+            #(#find_entities)*
+            Ok(None)
+        })
+    };
 
-        impl #generics #crate_name::GQLType for #self_ty {
-            fn type_name() -> std::borrow::Cow<'static, str> {
-                std::borrow::Cow::Borrowed(#gql_typename)
+    let create_type_info = quote! {
+        registry.create_type::<Self, _>(|registry| {
+            #(#entity_types)*
+            #crate_name::registry::Type::Object {
+                name: <Self as #crate_name::GQLType>::type_name().to_string(),
+                description: #desc.map(std::borrow::Cow::Borrowed),
+                fields: {
+                    let mut fields = std::collections::HashMap::new();
+                    #(#schema_fields)*
+                    fields
+                },
+                extends: #extends,
+                keys: None,
+                cache_control: #cache_control,
             }
+        })
+    };
 
-            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
-                registry.create_type::<Self, _>(|registry| #crate_name::registry::Type::Object {
-                    name: #gql_typename,
-                    description: #desc,
-                    fields: {
-                        let mut fields = std::collections::HashMap::new();
-                        #(#schema_fields)*
-                        fields
-                    },
-                })
+    let resolve_field = quote! {
+        use #crate_name::ErrorWithPosition;
+
+        #(#resolvers)*
+
+        #crate_name::anyhow::bail!(#crate_name::QueryError::FieldNotFound {
+            field_name: field.name.clone(),
+            object: <Self as #crate_name::GQLType>::type_name().to_string(),
+        }
+        .with_position(field.position));
+    };
+
+    let resolve_inline_fragment = quote! {
+        let type_name = <Self as #crate_name::GQLType>::type_name();
+        if name == type_name
+            || ctx
+                .registry()
+                .implements
+                .get(type_name.as_ref())
+                .map(|interfaces| interfaces.contains(name))
+                .unwrap_or_default()
+        {
+            #crate_name::do_resolve(ctx, self, result).await?;
+        }
+        Ok(())
+    };
+
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+
+    let expanded = if object_args.concretes.is_empty() {
+        let type_params: Vec<_> = item_impl
+            .generics
+            .type_params()
+            .map(|param| &param.ident)
+            .collect();
+        if object_args.name.is_some() && !type_params.is_empty() {
+            return Err(Error::new_spanned(
+                &item_impl.generics,
+                "A generic object cannot have a name, every concrete type would be registered with it. Use 'concretes' to name the concrete types.",
+            ));
+        }
+        let type_name = if !type_params.is_empty() {
+            // The name of a generic type is prefixed by the names of its type parameters, such as `UserPage`.
+            quote! {
+                let mut name = String::new();
+                #(name.push_str(&#crate_name::registry::TypeName::get_generic_param_name(
+                    &<#type_params as #crate_name::GQLType>::qualified_type_name(),
+                ));)*
+                name.push_str(#gql_typename);
+                std::borrow::Cow::Owned(name)
+            }
+        } else {
+            quote! { std::borrow::Cow::Borrowed(#gql_typename) }
+        };
+        let find_entity = find_entity_body.map(|body| {
+            quote! {
+                async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<Option<#crate_name::serde_json::Value>> {
+                    #body
+                }
+            }
+        });
+
+        quote! {
+            #item_impl
+
+            impl #impl_generics #crate_name::GQLType for #self_ty #where_clause {
+                fn type_name() -> std::borrow::Cow<'static, str> {
+                    #type_name
+                }
+
+                fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                    #create_type_info
+                }
+            }
+
+            #[#crate_name::async_trait::async_trait]
+            impl #impl_generics #crate_name::GQLObject for #self_ty #where_clause {
+                async fn resolve_field(&self, ctx: &#crate_name::Context<'_>, field: &#crate_name::graphql_parser::query::Field) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                    #resolve_field
+                }
+
+                async fn resolve_inline_fragment(&self, name: &str, ctx: &#crate_name::ContextSelectionSet<'_>, result: &mut #crate_name::serde_json::Map<String, serde_json::Value>) -> #crate_name::Result<()> {
+                    #resolve_inline_fragment
+                }
+
+                #find_entity
             }
         }
+    } else {
+        // The bodies are generated once in generic helper methods, and the trait implementations of every concrete
+        // type delegate to them.
+        let type_param_count = item_impl.generics.type_params().count();
+        let mut concrete_impls = Vec::new();
 
-        #[#crate_name::async_trait::async_trait]
-        impl#generics #crate_name::GQLObject for #self_ty {
-            async fn resolve_field(&self, ctx: &#crate_name::Context<'_>, field: &#crate_name::graphql_parser::query::Field) -> #crate_name::Result<#crate_name::serde_json::Value> {
-                use #crate_name::ErrorWithPosition;
+        for concrete in &object_args.concretes {
+            if concrete.params.len() != type_param_count {
+                return Err(Error::new_spanned(
+                    &item_impl.self_ty,
+                    format!(
+                        "The concrete type \"{}\" should have {} type parameters.",
+                        concrete.name, type_param_count
+                    ),
+                ));
+            }
+
+            let mut concrete_ty = self_ty.clone();
+            let params = &concrete.params;
+            concrete_ty.path.segments.last_mut().unwrap().arguments =
+                syn::PathArguments::AngleBracketed(syn::parse_quote!(<#(#params),*>));
+            let name = &concrete.name;
+            let find_entity = find_entity_body.as_ref().map(|_| {
+                quote! {
+                    async fn find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<Option<#crate_name::serde_json::Value>> {
+                        self.__find_entity(ctx, params).await
+                    }
+                }
+            });
 
-                #(#resolvers)*
+            concrete_impls.push(quote! {
+                impl #crate_name::GQLType for #concrete_ty {
+                    fn type_name() -> std::borrow::Cow<'static, str> {
+                        std::borrow::Cow::Borrowed(#name)
+                    }
+
+                    fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                        Self::__create_type_info(registry)
+                    }
+                }
+
+                #[#crate_name::async_trait::async_trait]
+                impl #crate_name::GQLObject for #concrete_ty {
+                    async fn resolve_field(&self, ctx: &#crate_name::Context<'_>, field: &#crate_name::graphql_parser::query::Field) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                        self.__resolve_field(ctx, field).await
+                    }
+
+                    async fn resolve_inline_fragment(&self, name: &str, ctx: &#crate_name::ContextSelectionSet<'_>, result: &mut #crate_name::serde_json::Map<String, serde_json::Value>) -> #crate_name::Result<()> {
+                        self.__resolve_inline_fragment(name, ctx, result).await
+                    }
 
-                #crate_name::anyhow::bail!(#crate_name::QueryError::FieldNotFound {
-                    field_name: field.name.clone(),
-                    object: #gql_typename.to_string(),
+                    #find_entity
+                }
+            });
+        }
+
+        let find_entity = find_entity_body.map(|body| {
+            quote! {
+                #[doc(hidden)]
+                async fn __find_entity(&self, ctx: &#crate_name::Context<'_>, params: &#crate_name::Value) -> #crate_name::Result<Option<#crate_name::serde_json::Value>>
+                where
+                    Self: #crate_name::GQLObject + Send + Sync,
+                {
+                    #body
                 }
-                .with_position(field.position));
             }
+        });
 
-            async fn resolve_inline_fragment(&self, name: &str, ctx: &#crate_name::ContextSelectionSet<'_>, result: &mut #crate_name::serde_json::Map<String, serde_json::Value>) -> #crate_name::Result<()> {
-                #crate_name::anyhow::bail!(#crate_name::QueryError::UnrecognizedInlineFragment {
-                    object: #gql_typename.to_string(),
-                    name: name.to_string(),
-                });
+        quote! {
+            #item_impl
+
+            impl #impl_generics #self_ty #where_clause {
+                #[doc(hidden)]
+                fn __create_type_info(registry: &mut #crate_name::registry::Registry) -> String
+                where
+                    Self: #crate_name::GQLType,
+                {
+                    #create_type_info
+                }
+
+                #[doc(hidden)]
+                async fn __resolve_field(&self, ctx: &#crate_name::Context<'_>, field: &#crate_name::graphql_parser::query::Field) -> #crate_name::Result<#crate_name::serde_json::Value>
+                where
+                    Self: #crate_name::GQLObject + Send + Sync,
+                {
+                    #resolve_field
+                }
+
+                #[doc(hidden)]
+                async fn __resolve_inline_fragment(&self, name: &str, ctx: &#crate_name::ContextSelectionSet<'_>, result: &mut #crate_name::serde_json::Map<String, serde_json::Value>) -> #crate_name::Result<()>
+                where
+                    Self: #crate_name::GQLObject + Send + Sync,
+                {
+                    #resolve_inline_fragment
+                }
+
+                #find_entity
             }
+
+            #(#concrete_impls)*
         }
     };
     Ok(expanded.into())
@@ -0,0 +1,206 @@
+use crate::args;
+use crate::utils::get_crate_name;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Fields, ImplItem, ItemImpl, Result, Type};
+
+pub fn generate(scalar_args: &args::Scalar, item_impl: &mut ItemImpl) -> Result<TokenStream> {
+    let crate_name = get_crate_name(scalar_args.internal);
+    if item_impl.trait_.is_none() {
+        return Err(Error::new_spanned(
+            &item_impl,
+            "The Scalar attribute must be used on the implementation of `GQLScalar`.",
+        ));
+    }
+
+    let has_method = |name: &str| {
+        item_impl.items.iter().any(|item| match item {
+            ImplItem::Method(method) => method.sig.ident == name,
+            _ => false,
+        })
+    };
+    let has_type_name = has_method("type_name");
+    let has_description = has_method("description");
+    let has_specified_by_url = has_method("specified_by_url");
+
+    let mut methods: Vec<ImplItem> = Vec::new();
+    match &scalar_args.name {
+        Some(_) if has_type_name => {
+            return Err(Error::new_spanned(
+                &item_impl.self_ty,
+                "The scalar name is specified by both the 'name' attribute and `type_name`.",
+            ))
+        }
+        Some(name) => methods.push(syn::parse_quote! {
+            fn type_name() -> &'static str {
+                #name
+            }
+        }),
+        None if !has_type_name => {
+            let name = match item_impl.self_ty.as_ref() {
+                Type::Path(path) => path
+                    .path
+                    .segments
+                    .last()
+                    .map(|s| s.ident.to_string())
+                    .unwrap(),
+                _ => return Err(Error::new_spanned(&item_impl.self_ty, "Invalid type")),
+            };
+            methods.push(syn::parse_quote! {
+                fn type_name() -> &'static str {
+                    #name
+                }
+            });
+        }
+        None => {}
+    }
+    if let Some(desc) = &scalar_args.desc {
+        if has_description {
+            return Err(Error::new_spanned(
+                &item_impl.self_ty,
+                "The scalar description is specified by both the 'desc' attribute and `description`.",
+            ));
+        }
+        methods.push(syn::parse_quote! {
+            fn description() -> Option<&'static str> {
+                Some(#desc)
+            }
+        });
+    }
+    if let Some(url) = &scalar_args.specified_by_url {
+        if has_specified_by_url {
+            return Err(Error::new_spanned(
+                &item_impl.self_ty,
+                "The specification url is specified by both the 'specified_by_url' attribute and `specified_by_url`.",
+            ));
+        }
+        methods.push(syn::parse_quote! {
+            fn specified_by_url() -> Option<&'static str> {
+                Some(#url)
+            }
+        });
+    }
+    item_impl.items.extend(methods);
+
+    let self_ty = &item_impl.self_ty;
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+
+    // The futures returned by `resolve` borrow the value, so it must be `Sync` to be sent.
+    let mut output_generics = item_impl.generics.clone();
+    output_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(#self_ty: Sync));
+    let output_where_clause = &output_generics.where_clause;
+
+    let expanded = quote! {
+        #item_impl
+
+        impl #impl_generics #crate_name::GQLType for #self_ty #where_clause {
+            fn type_name() -> std::borrow::Cow<'static, str> {
+                std::borrow::Cow::Borrowed(<#self_ty as #crate_name::GQLScalar>::type_name())
+            }
+
+            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                registry.create_type::<#self_ty, _>(|_| #crate_name::registry::Type::Scalar {
+                    name: <#self_ty as #crate_name::GQLScalar>::type_name().to_string(),
+                    description: <#self_ty as #crate_name::GQLScalar>::description().map(std::borrow::Cow::Borrowed),
+                    is_valid: |value| <#self_ty as #crate_name::GQLScalar>::is_valid(value),
+                    parse: |value| <#self_ty as #crate_name::GQLScalar>::parse(value).map(|_| ()),
+                    specified_by_url: <#self_ty as #crate_name::GQLScalar>::specified_by_url(),
+                })
+            }
+        }
+
+        impl #impl_generics #crate_name::GQLType for &#self_ty #where_clause {
+            fn type_name() -> std::borrow::Cow<'static, str> {
+                <#self_ty as #crate_name::GQLType>::type_name()
+            }
+
+            fn create_type_info(registry: &mut #crate_name::registry::Registry) -> String {
+                <#self_ty as #crate_name::GQLType>::create_type_info(registry)
+            }
+        }
+
+        impl #impl_generics #crate_name::GQLInputValue for #self_ty #where_clause {
+            fn parse(value: &#crate_name::Value) -> #crate_name::InputValueResult<Self> {
+                <#self_ty as #crate_name::GQLScalar>::parse(value)
+            }
+
+            fn to_value(&self) -> #crate_name::Value {
+                <#self_ty as #crate_name::GQLScalar>::to_value(self)
+            }
+        }
+
+        #[#crate_name::async_trait::async_trait]
+        impl #impl_generics #crate_name::GQLOutputValue for #self_ty #output_where_clause {
+            async fn resolve(
+                value: &Self,
+                _: &#crate_name::ContextSelectionSet<'_>,
+            ) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                <#self_ty as #crate_name::GQLScalar>::to_json(value)
+            }
+        }
+
+        #[#crate_name::async_trait::async_trait]
+        impl #impl_generics #crate_name::GQLOutputValue for &#self_ty #output_where_clause {
+            async fn resolve(
+                value: &Self,
+                _: &#crate_name::ContextSelectionSet<'_>,
+            ) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                <#self_ty as #crate_name::GQLScalar>::to_json(*value)
+            }
+        }
+    };
+    Ok(expanded.into())
+}
+
+pub fn generate_newtype(scalar_args: &args::Scalar, input: &DeriveInput) -> Result<TokenStream> {
+    let crate_name = get_crate_name(scalar_args.internal);
+    let ident = &input.ident;
+    let inner_ty = match &input.data {
+        Data::Struct(s) => match &s.fields {
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => &fields.unnamed[0].ty,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "Scalar can only be derived for a newtype struct with one unnamed field.",
+                ))
+            }
+        },
+        _ => return Err(Error::new_spanned(
+            &input.ident,
+            "Scalar can only be applied to an implementation of `GQLScalar` or a newtype struct.",
+        )),
+    };
+
+    let mut generics = input.generics.clone();
+    generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(#inner_ty: #crate_name::GQLScalar));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let mut item_impl: ItemImpl = syn::parse_quote! {
+        impl #impl_generics #crate_name::GQLScalar for #ident #ty_generics #where_clause {
+            fn parse(value: &#crate_name::Value) -> #crate_name::InputValueResult<Self> {
+                <#inner_ty as #crate_name::GQLScalar>::parse(value).map(#ident)
+            }
+
+            fn is_valid(value: &#crate_name::Value) -> bool {
+                <#inner_ty as #crate_name::GQLScalar>::is_valid(value)
+            }
+
+            fn to_json(&self) -> #crate_name::Result<#crate_name::serde_json::Value> {
+                <#inner_ty as #crate_name::GQLScalar>::to_json(&self.0)
+            }
+        }
+    };
+    let glue = proc_macro2::TokenStream::from(generate(scalar_args, &mut item_impl)?);
+
+    let expanded = quote! {
+        #input
+        #glue
+    };
+    Ok(expanded.into())
+}
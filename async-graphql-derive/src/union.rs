@@ -81,8 +81,8 @@ pub fn generate(interface_args: &args::Interface, input: &DeriveInput) -> Result
                     #(#registry_types)*
 
                     #crate_name::registry::Type::Union {
-                        name: #gql_typename,
-                        description: #desc,
+                        name: std::borrow::Cow::Borrowed(#gql_typename),
+                        description: #desc.map(std::borrow::Cow::Borrowed),
                         possible_types: {
                             let mut possible_types = std::collections::HashSet::new();
                             #(#possible_types)*
@@ -1,8 +1,9 @@
+use crate::args::{CacheControl, DefaultValue};
 use graphql_parser::parse_query;
 use graphql_parser::query::{Definition, OperationDefinition, ParseError, Query, Value};
 use proc_macro2::{Span, TokenStream};
-use quote::quote;
-use syn::Ident;
+use quote::{quote, ToTokens};
+use syn::{Error, Ident, Meta, MetaList, NestedMeta, Result};
 
 pub fn get_crate_name(internal: bool) -> TokenStream {
     match internal {
@@ -14,7 +15,7 @@ pub fn get_crate_name(internal: bool) -> TokenStream {
     }
 }
 
-pub fn parse_value(s: &str) -> Result<Value, ParseError> {
+pub fn parse_value(s: &str) -> std::result::Result<Value, ParseError> {
     let mut doc = parse_query(&format!("query ($a:Int!={}) {{ dummy }}", s))?;
     let definition = doc.definitions.remove(0);
     if let Definition::Operation(OperationDefinition::Query(Query {
@@ -72,9 +73,137 @@ pub fn build_value_repr(crate_name: &TokenStream, value: &Value) -> TokenStream
                 {
                     let mut obj = std::collections::BTreeMap::new();
                     #(#members)*
-                    obj
+                    #crate_name::Value::Object(obj)
                 }
             }
         }
     }
 }
+
+fn parse_combinator(meta: &NestedMeta, ext: &TokenStream, kind: &str) -> Result<TokenStream> {
+    match meta {
+        NestedMeta::Meta(Meta::Path(path)) => Ok(quote! { #path }),
+        NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("and") => {
+            parse_combinators(ls, ext, kind, "and")
+        }
+        NestedMeta::Meta(Meta::List(ls)) if ls.path.is_ident("or") => {
+            parse_combinators(ls, ext, kind, "or")
+        }
+        NestedMeta::Meta(Meta::List(ls))
+            if ls
+                .nested
+                .iter()
+                .all(|item| matches!(item, NestedMeta::Lit(_))) =>
+        {
+            // `Type("a", 1)` calls `Type::new("a", 1)`.
+            let ty = &ls.path;
+            let params = ls.nested.iter();
+            Ok(quote! { #ty::new(#(#params),*) })
+        }
+        NestedMeta::Meta(Meta::List(ls)) => {
+            let ty = &ls.path;
+            let mut params = Vec::new();
+            for item in &ls.nested {
+                if let NestedMeta::Meta(Meta::NameValue(nv)) = item {
+                    let name = &nv.path;
+                    if let syn::Lit::Str(value) = &nv.lit {
+                        let value_expr = value.parse::<syn::Expr>()?;
+                        params.push(quote! { #name: #value_expr });
+                    } else {
+                        let value = &nv.lit;
+                        params.push(quote! { #name: #value });
+                    }
+                } else {
+                    return Err(Error::new_spanned(
+                        item,
+                        format!("Invalid {} parameter", kind),
+                    ));
+                }
+            }
+            Ok(quote! { #ty { #(#params),* } })
+        }
+        _ => Err(Error::new_spanned(meta, format!("Invalid {}", kind))),
+    }
+}
+
+fn parse_combinators(
+    ls: &MetaList,
+    ext: &TokenStream,
+    kind: &str,
+    op: &str,
+) -> Result<TokenStream> {
+    let mut items: Option<TokenStream> = None;
+    for item in &ls.nested {
+        let item = parse_combinator(item, ext, kind)?;
+        items = Some(match items {
+            Some(prev) if op == "or" => quote! { #ext::or(#prev, #item) },
+            Some(prev) => quote! { #ext::and(#prev, #item) },
+            None => item,
+        });
+    }
+    items.ok_or_else(|| Error::new_spanned(ls, format!("Missing {}s", kind)))
+}
+
+/// Generate the registry value of `cache_control(...)`, `None` if there is no hint.
+pub fn generate_cache_control(
+    crate_name: &TokenStream,
+    cache_control: &Option<CacheControl>,
+) -> TokenStream {
+    match cache_control {
+        Some(cache_control) => {
+            let public = cache_control.public;
+            let max_age = cache_control.max_age;
+            quote! {
+                Some(#crate_name::CacheControl {
+                    public: #public,
+                    max_age: #max_age,
+                })
+            }
+        }
+        None => quote! { None },
+    }
+}
+
+/// Generate the code that checks the guards of `#[field(guard(...))]`, multiple guards must all pass.
+pub fn generate_guard(crate_name: &TokenStream, guard: &MetaList) -> Result<TokenStream> {
+    let ext = quote! { #crate_name::guard::GuardExt };
+    let guards = parse_combinators(guard, &ext, "guard", "and")?;
+    Ok(quote! {
+        #crate_name::guard::Guard::check(&#guards, ctx).await
+    })
+}
+
+/// Generate the validator of `#[arg(validator(...))]` and `#[field(validator(...))]` for the registry,
+/// multiple validators must all pass.
+pub fn generate_validator(
+    crate_name: &TokenStream,
+    validator: Option<&MetaList>,
+) -> Result<TokenStream> {
+    match validator {
+        Some(validator) => {
+            let ext = quote! { #crate_name::validators::ValidatorExt };
+            let validators = parse_combinators(validator, &ext, "validator", "and")?;
+            Ok(quote! {
+                Some(std::sync::Arc::new(#validators))
+            })
+        }
+        None => Ok(quote! { None }),
+    }
+}
+
+pub fn generate_default<T: ToTokens>(
+    crate_name: &TokenStream,
+    default: &DefaultValue,
+    ty: &T,
+) -> TokenStream {
+    match default {
+        DefaultValue::Literal(value) => build_value_repr(crate_name, value),
+        DefaultValue::Default => quote! {
+            #crate_name::GQLInputValue::to_value(&<#ty as std::default::Default>::default())
+        },
+        DefaultValue::Expr(expr) => quote! {{
+            let value: #ty = #expr;
+            #crate_name::GQLInputValue::to_value(&value)
+        }},
+    }
+}
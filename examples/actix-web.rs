@@ -6,8 +6,13 @@ use async_graphql::{GQLEmptyMutation, Schema};
 
 type StarWarsSchema = Schema<starwars::QueryRoot, GQLEmptyMutation>;
 
-async fn index(s: web::Data<StarWarsSchema>, req: web::Json<GQLRequest>) -> web::Json<GQLResponse> {
-    web::Json(req.into_inner().execute(&s).await)
+async fn index(s: web::Data<StarWarsSchema>, req: web::Json<GQLRequest>) -> HttpResponse {
+    let resp: GQLResponse = req.into_inner().execute(&s).await;
+    let mut builder = HttpResponse::Ok();
+    if let Some(cache_control) = resp.cache_control_header() {
+        builder.header("Cache-Control", cache_control);
+    }
+    builder.json(resp)
 }
 
 async fn gql_playgound() -> HttpResponse {
@@ -1,6 +1,8 @@
-use crate::{registry, Context, ContextSelectionSet, Result};
+use crate::{registry, Context, ContextSelectionSet, InputValueError, InputValueResult, Result};
 use graphql_parser::query::{Field, Value};
 use std::borrow::Cow;
+use std::future::Future;
+use std::pin::Pin;
 
 /// Represents a GraphQL type
 pub trait GQLType {
@@ -18,7 +20,34 @@ pub trait GQLType {
 
 /// Represents a GraphQL input value
 pub trait GQLInputValue: GQLType + Sized {
-    fn parse(value: &Value) -> Option<Self>;
+    /// Coerce the input value, the error describes which part of the value is invalid and why.
+    fn parse(value: &Value) -> InputValueResult<Self>;
+
+    /// Convert the value back to an input value, used to build the default values of the schema.
+    fn to_value(&self) -> Value;
+
+    /// Parse the value of an argument or of an input object field, the `value` is `None` if it is not provided.
+    #[doc(hidden)]
+    fn parse_optional(value: Option<&Value>) -> InputValueResult<Self> {
+        match value {
+            Some(value) => Self::parse(value),
+            None => Self::parse(&Value::Null).map_err(|_| {
+                InputValueError::custom(format!(
+                    "expected type \"{}\", but it is not provided",
+                    Self::qualified_type_name()
+                ))
+            }),
+        }
+    }
+
+    /// Check the guards of the input object fields that are present in the `value`.
+    #[doc(hidden)]
+    fn check_guards<'a>(
+        _ctx: &'a Context<'a>,
+        _value: &'a Value,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(async { Ok(()) })
+    }
 }
 
 /// Represents a GraphQL output value
@@ -46,6 +75,19 @@ pub trait GQLObject: GQLOutputValue {
         ctx: &ContextSelectionSet<'_>,
         result: &mut serde_json::Map<String, serde_json::Value>,
     ) -> Result<()>;
+
+    /// Find the entity of the federation that matches the representation `params`, it is generated from the
+    /// entity resolvers of the object.
+    ///
+    /// Returns `None` if no entity resolver matches the representation.
+    #[doc(hidden)]
+    async fn find_entity(
+        &self,
+        _ctx: &Context<'_>,
+        _params: &Value,
+    ) -> Result<Option<serde_json::Value>> {
+        Ok(None)
+    }
 }
 
 /// Represents a GraphQL input object
@@ -53,7 +95,7 @@ pub trait GQLInputObject: GQLInputValue {}
 
 /// Represents a GraphQL scalar
 ///
-/// You can implement the trait to create a custom scalar.
+/// You can implement the trait and apply the `Scalar` attribute to the implementation to create a custom scalar.
 ///
 /// # Examples
 ///
@@ -62,16 +104,17 @@ pub trait GQLInputObject: GQLInputValue {}
 ///
 /// struct MyInt(i32);
 ///
+/// #[Scalar]
 /// impl GQLScalar for MyInt {
 ///     fn type_name() -> &'static str {
 ///         "MyInt"
 ///     }
 ///
-///     fn parse(value: &Value) -> Option<Self> {
+///     fn parse(value: &Value) -> InputValueResult<Self> {
 ///         if let Value::Int(n) = value {
-///             Some(MyInt(n.as_i64().unwrap() as i32))
+///             Ok(MyInt(n.as_i64().unwrap() as i32))
 ///         } else {
-///             None
+///             Err(InputValueError::expected_type("MyInt", value))
 ///         }
 ///     }
 ///
@@ -79,8 +122,6 @@ pub trait GQLInputObject: GQLInputValue {}
 ///         Ok(self.0.into())
 ///     }
 /// }
-///
-/// impl_scalar!(MyInt); // // Don't forget this one
 /// ```
 pub trait GQLScalar: Sized + Send {
     /// The type name of a scalar.
@@ -91,134 +132,95 @@ pub trait GQLScalar: Sized + Send {
         None
     }
 
-    /// Parse a scalar value, return `Some(Self)` if successful, otherwise return `None`.
-    fn parse(value: &Value) -> Option<Self>;
+    /// The URL of the specification of a scalar, exposed as `specifiedByURL` in the introspection.
+    fn specified_by_url() -> Option<&'static str> {
+        None
+    }
+
+    /// Parse a scalar value, return an `InputValueError` with the reason if the value is invalid.
+    fn parse(value: &Value) -> InputValueResult<Self>;
 
     /// Checks for a valid scalar value.
     ///
     /// The default implementation is to try to parse it, and in some cases you can implement this on your own to improve performance.
     fn is_valid(value: &Value) -> bool {
-        Self::parse(value).is_some()
+        Self::parse(value).is_ok()
     }
 
     /// Convert the scalar value to json value.
     fn to_json(&self) -> Result<serde_json::Value>;
-}
-
-#[macro_export]
-#[doc(hidden)]
-macro_rules! impl_scalar_internal {
-    ($ty:ty) => {
-        impl crate::GQLType for $ty {
-            fn type_name() -> std::borrow::Cow<'static, str> {
-                std::borrow::Cow::Borrowed(<$ty as crate::GQLScalar>::type_name())
-            }
-
-            fn create_type_info(registry: &mut crate::registry::Registry) -> String {
-                registry.create_type::<$ty, _>(|_| crate::registry::Type::Scalar {
-                    name: <$ty as crate::GQLScalar>::type_name().to_string(),
-                    description: <$ty>::description(),
-                    is_valid: |value| <$ty as crate::GQLScalar>::is_valid(value),
-                })
-            }
-        }
 
-        impl crate::GQLType for &$ty {
-            fn type_name() -> std::borrow::Cow<'static, str> {
-                std::borrow::Cow::Borrowed(<$ty as crate::GQLScalar>::type_name())
-            }
-
-            fn create_type_info(registry: &mut crate::registry::Registry) -> String {
-                registry.create_type::<$ty, _>(|_| crate::registry::Type::Scalar {
-                    name: <$ty as crate::GQLScalar>::type_name().to_string(),
-                    description: <$ty>::description(),
-                    is_valid: |value| <$ty as crate::GQLScalar>::is_valid(value),
-                })
-            }
-        }
-
-        impl crate::GQLInputValue for $ty {
-            fn parse(value: &crate::Value) -> Option<Self> {
-                <$ty as crate::GQLScalar>::parse(value)
-            }
-        }
-
-        #[async_trait::async_trait]
-        impl crate::GQLOutputValue for $ty {
-            async fn resolve(
-                value: &Self,
-                _: &crate::ContextSelectionSet<'_>,
-            ) -> crate::Result<serde_json::Value> {
-                value.to_json()
-            }
-        }
-
-        #[async_trait::async_trait]
-        impl crate::GQLOutputValue for &$ty {
-            async fn resolve(
-                value: &Self,
-                _: &crate::ContextSelectionSet<'_>,
-            ) -> crate::Result<serde_json::Value> {
-                value.to_json()
-            }
-        }
-    };
+    /// Convert the scalar value to an input value.
+    ///
+    /// The default implementation converts the result of `to_json`, `null` is returned if it fails.
+    fn to_value(&self) -> Value {
+        self.to_json()
+            .map(crate::context::json_value_to_gql_value)
+            .unwrap_or(Value::Null)
+    }
 }
 
+/// Generate the glue of a scalar from its implementation of `GQLScalar`, like the `Scalar` attribute.
+///
+/// It is kept for the scalars written before the attribute existed, apply `#[Scalar]` to the implementation instead.
 #[macro_export]
+#[deprecated(note = "apply the `#[Scalar]` attribute to the implementation of `GQLScalar` instead")]
 macro_rules! impl_scalar {
     ($ty:ty) => {
-        impl async_graphql::GQLType for $ty {
+        impl $crate::GQLType for $ty {
             fn type_name() -> std::borrow::Cow<'static, str> {
-                std::borrow::Cow::Borrowed(<$ty as async_graphql::GQLScalar>::type_name())
+                std::borrow::Cow::Borrowed(<$ty as $crate::GQLScalar>::type_name())
             }
 
-            fn create_type_info(registry: &mut async_graphql::registry::Registry) -> String {
-                registry.create_type::<$ty, _>(|_| async_graphql::registry::Type::Scalar {
-                    name: <$ty as async_graphql::GQLScalar>::type_name().to_string(),
-                    description: <$ty>::description(),
-                    is_valid: |value| <$ty as async_graphql::GQLScalar>::is_valid(value),
+            fn create_type_info(registry: &mut $crate::registry::Registry) -> String {
+                registry.create_type::<$ty, _>(|_| $crate::registry::Type::Scalar {
+                    name: <$ty as $crate::GQLScalar>::type_name().to_string(),
+                    description: <$ty as $crate::GQLScalar>::description()
+                        .map(std::borrow::Cow::Borrowed),
+                    is_valid: |value| <$ty as $crate::GQLScalar>::is_valid(value),
+                    parse: |value| <$ty as $crate::GQLScalar>::parse(value).map(|_| ()),
+                    specified_by_url: <$ty as $crate::GQLScalar>::specified_by_url(),
                 })
             }
         }
 
-        impl async_graphql::GQLType for &$ty {
+        impl $crate::GQLType for &$ty {
             fn type_name() -> std::borrow::Cow<'static, str> {
-                std::borrow::Cow::Borrowed(<$ty as async_graphql::GQLScalar>::type_name())
+                <$ty as $crate::GQLType>::type_name()
             }
 
-            fn create_type_info(registry: &mut async_graphql::registry::Registry) -> String {
-                registry.create_type::<$ty, _>(|_| async_graphql::registry::Type::Scalar {
-                    name: <$ty as async_graphql::GQLScalar>::type_name().to_string(),
-                    description: <$ty>::description(),
-                    is_valid: |value| <$ty as async_graphql::GQLScalar>::is_valid(value),
-                })
+            fn create_type_info(registry: &mut $crate::registry::Registry) -> String {
+                <$ty as $crate::GQLType>::create_type_info(registry)
             }
         }
 
-        impl async_graphql::GQLInputValue for $ty {
-            fn parse(value: &async_graphql::Value) -> Option<Self> {
-                <$ty as async_graphql::GQLScalar>::parse(value)
+        impl $crate::GQLInputValue for $ty {
+            fn parse(value: &$crate::Value) -> $crate::InputValueResult<Self> {
+                <$ty as $crate::GQLScalar>::parse(value)
+            }
+
+            fn to_value(&self) -> $crate::Value {
+                <$ty as $crate::GQLScalar>::to_value(self)
             }
         }
 
-        #[async_graphql::async_trait::async_trait]
-        impl async_graphql::GQLOutputValue for $ty {
+        #[$crate::async_trait::async_trait]
+        impl $crate::GQLOutputValue for $ty {
             async fn resolve(
                 value: &Self,
-                _: &async_graphql::ContextSelectionSet<'_>,
-            ) -> async_graphql::Result<serde_json::Value> {
-                value.to_json()
+                _: &$crate::ContextSelectionSet<'_>,
+            ) -> $crate::Result<$crate::serde_json::Value> {
+                <$ty as $crate::GQLScalar>::to_json(value)
             }
         }
 
-        #[async_graphql::async_trait::async_trait]
-        impl async_graphql::GQLOutputValue for &$ty {
+        #[$crate::async_trait::async_trait]
+        impl $crate::GQLOutputValue for &$ty {
             async fn resolve(
                 value: &Self,
-                _: &async_graphql::ContextSelectionSet<'_>,
-            ) -> async_graphql::Result<serde_json::Value> {
-                value.to_json()
+                _: &$crate::ContextSelectionSet<'_>,
+            ) -> $crate::Result<$crate::serde_json::Value> {
+                <$ty as $crate::GQLScalar>::to_json(*value)
             }
         }
     };
@@ -0,0 +1,46 @@
+/// Cache control hints of a type or a field, and the aggregated hints of a response.
+///
+/// The hints are declared with `#[Object(cache_control(max_age = 60, public))]` and
+/// `#[field(cache_control(max_age = 30, private))]`. A response is cacheable for the minimum `max_age` of the
+/// resolved fields, and is private if any of them is private. The hint of a field is combined with the hint of its
+/// object type, a resolved field without either of them makes the response uncacheable.
+///
+/// A `max_age` of 0 means that the response must not be cached.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheControl {
+    /// Whether the response can be stored in a shared cache.
+    pub public: bool,
+
+    /// The maximum age of the response in seconds.
+    pub max_age: usize,
+}
+
+impl Default for CacheControl {
+    fn default() -> Self {
+        Self {
+            public: true,
+            max_age: 0,
+        }
+    }
+}
+
+impl CacheControl {
+    /// Get the value of the `Cache-Control` HTTP header, `None` if there is no `max_age`.
+    pub fn value(&self) -> Option<String> {
+        if self.max_age > 0 {
+            if self.public {
+                Some(format!("max-age={}", self.max_age))
+            } else {
+                Some(format!("max-age={}, private", self.max_age))
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Combine with other hints, keeping the minimum `max_age` and the most restrictive scope.
+    pub fn merge(&mut self, other: &CacheControl) {
+        self.public = self.public && other.public;
+        self.max_age = self.max_age.min(other.max_age);
+    }
+}
@@ -1,13 +1,19 @@
-use crate::registry::Registry;
-use crate::{ErrorWithPosition, GQLInputValue, GQLType, QueryError, Result};
+use crate::incremental::{IncrementalPatches, InitialValue};
+use crate::registry::{self, Registry};
+use crate::{CacheControl, ErrorWithPosition, GQLInputValue, Lookahead, QueryError, Result};
 use fnv::FnvHasher;
+use graphql_parser::parse_query;
 use graphql_parser::query::{
-    Directive, Field, FragmentDefinition, SelectionSet, Value, VariableDefinition,
+    Definition, Directive, Field, FragmentDefinition, Number, OperationDefinition, Selection,
+    SelectionSet, Value, VariableDefinition,
 };
 use std::any::{Any, TypeId};
 use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
 use std::hash::BuildHasherDefault;
 use std::ops::{Deref, DerefMut};
+use std::sync::Mutex;
 
 /// Variables of query
 #[derive(Default)]
@@ -38,12 +44,38 @@ impl Variables {
     }
 }
 
-fn json_value_to_gql_value(value: serde_json::Value) -> Value {
+/// Create a `Number` from an `i64`.
+///
+/// `graphql_parser::query::Number` only implements `From<i32>`, so a wider value is read from an integer literal
+/// by the parser, which is the only way to create it.
+fn number_from_i64(n: i64) -> Option<Number> {
+    if let Ok(n) = i32::try_from(n) {
+        return Some(n.into());
+    }
+
+    let doc = parse_query(&format!("{{a(n:{})}}", n)).ok()?;
+    match doc.definitions.into_iter().next()? {
+        Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
+            match selection_set.items.into_iter().next()? {
+                Selection::Field(field) => match field.arguments.into_iter().next()?.1 {
+                    Value::Int(number) => Some(number),
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+pub(crate) fn json_value_to_gql_value(value: serde_json::Value) -> Value {
     match value {
         serde_json::Value::Null => Value::Null,
         serde_json::Value::Bool(n) => Value::Boolean(n),
-        serde_json::Value::Number(n) if n.is_f64() => Value::Float(n.as_f64().unwrap()),
-        serde_json::Value::Number(n) => Value::Int((n.as_i64().unwrap() as i32).into()),
+        serde_json::Value::Number(n) => match n.as_i64().and_then(number_from_i64) {
+            Some(number) => Value::Int(number),
+            None => Value::Float(n.as_f64().unwrap()),
+        },
         serde_json::Value::String(s) => Value::String(s),
         serde_json::Value::Array(ls) => Value::List(
             ls.into_iter()
@@ -58,6 +90,7 @@ fn json_value_to_gql_value(value: serde_json::Value) -> Value {
     }
 }
 
+/// Data of the schema or of a single request, indexed by type.
 #[derive(Default)]
 pub struct Data(HashMap<TypeId, Box<dyn Any + Sync + Send>, BuildHasherDefault<FnvHasher>>);
 
@@ -65,6 +98,68 @@ impl Data {
     pub fn insert<D: Any + Send + Sync>(&mut self, data: D) {
         self.0.insert(TypeId::of::<D>(), Box::new(data));
     }
+
+    /// Gets the data of type `D`, return `None` if it does not exist.
+    pub fn get<D: Any + Send + Sync>(&self) -> Option<&D> {
+        self.0
+            .get(&TypeId::of::<D>())
+            .and_then(|d| d.downcast_ref::<D>())
+    }
+}
+
+/// A segment of the response path.
+#[derive(Debug, Copy, Clone)]
+pub enum QueryPathSegment<'a> {
+    /// The index of a list item.
+    Index(usize),
+
+    /// The response name (alias or field name) of an object field.
+    Name(&'a str),
+}
+
+/// A node in the response path, linked to its parent.
+#[derive(Debug, Copy, Clone)]
+pub struct QueryPathNode<'a> {
+    pub parent: Option<&'a QueryPathNode<'a>>,
+    pub segment: QueryPathSegment<'a>,
+}
+
+impl<'a> QueryPathNode<'a> {
+    /// Call `f` with each segment of the path, starting from the root.
+    pub fn for_each<F: FnMut(&QueryPathSegment<'a>)>(&self, mut f: F) {
+        self.for_each_ref(&mut f);
+    }
+
+    /// Convert the path to a json array, e.g. `["hero", "friends", 2, "name"]`.
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut path = Vec::<serde_json::Value>::new();
+        self.for_each(|segment| {
+            path.push(match segment {
+                QueryPathSegment::Index(idx) => (*idx).into(),
+                QueryPathSegment::Name(name) => (*name).into(),
+            })
+        });
+        path.into()
+    }
+
+    fn for_each_ref<F: FnMut(&QueryPathSegment<'a>)>(&self, f: &mut F) {
+        if let Some(parent) = &self.parent {
+            parent.for_each_ref(f);
+        }
+        f(&self.segment);
+    }
+}
+
+impl<'a> Display for QueryPathNode<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(parent) = self.parent {
+            write!(f, "{}.", parent)?;
+        }
+        match &self.segment {
+            QueryPathSegment::Index(idx) => write!(f, "{}", idx),
+            QueryPathSegment::Name(name) => write!(f, "{}", name),
+        }
+    }
 }
 
 pub type ContextSelectionSet<'a> = ContextBase<'a, &'a SelectionSet>;
@@ -73,12 +168,25 @@ pub type ContextSelectionSet<'a> = ContextBase<'a, &'a SelectionSet>;
 pub type Context<'a> = ContextBase<'a, &'a Field>;
 
 pub struct ContextBase<'a, T> {
+    pub(crate) path_node: Option<QueryPathNode<'a>>,
     pub(crate) item: T,
     pub(crate) variables: Option<&'a Variables>,
     pub(crate) variable_definitions: Option<&'a [VariableDefinition]>,
     pub(crate) registry: &'a Registry,
     pub(crate) data: &'a Data,
+    pub(crate) ctx_data: Option<&'a Data>,
     pub(crate) fragments: &'a HashMap<String, &'a FragmentDefinition>,
+    pub(crate) incremental: Option<&'a IncrementalPatches>,
+    pub(crate) initial: Option<&'a InitialValue>,
+    pub(crate) stream: Option<IncrementalDirective>,
+    pub(crate) cache_control: &'a Mutex<Option<CacheControl>>,
+}
+
+/// The arguments of an enabled `@defer` or `@stream` directive.
+#[derive(Clone)]
+pub(crate) struct IncrementalDirective {
+    pub label: Option<String>,
+    pub initial_count: usize,
 }
 
 impl<'a, T> Deref for ContextBase<'a, T> {
@@ -93,59 +201,134 @@ impl<'a, T> ContextBase<'a, T> {
     #[doc(hidden)]
     pub fn with_item<R>(&self, item: R) -> ContextBase<'a, R> {
         ContextBase {
+            path_node: self.path_node,
             item,
             variables: self.variables,
             variable_definitions: self.variable_definitions,
             registry: self.registry.clone(),
             data: self.data,
+            ctx_data: self.ctx_data,
             fragments: self.fragments,
+            incremental: self.incremental,
+            initial: self.initial,
+            stream: self.stream.clone(),
+            cache_control: self.cache_control,
         }
     }
 
-    /// Gets the global data defined in the `Schema`.
+    #[doc(hidden)]
+    pub fn with_index(&'a self, idx: usize) -> ContextBase<'a, T>
+    where
+        T: Copy,
+    {
+        ContextBase {
+            path_node: Some(QueryPathNode {
+                parent: self.path_node.as_ref(),
+                segment: QueryPathSegment::Index(idx),
+            }),
+            item: self.item,
+            variables: self.variables,
+            variable_definitions: self.variable_definitions,
+            registry: self.registry,
+            data: self.data,
+            ctx_data: self.ctx_data,
+            fragments: self.fragments,
+            incremental: self.incremental,
+            initial: None,
+            stream: None,
+            cache_control: self.cache_control,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn registry(&self) -> &'a Registry {
+        self.registry
+    }
+
+    /// Gets the path of the current value in the response.
+    pub fn path_node(&self) -> Option<&QueryPathNode<'a>> {
+        self.path_node.as_ref()
+    }
+
+    /// Gets the data of the current request defined in the `QueryBuilder`, or the global data defined in the `Schema`.
     pub fn data<D: Any + Send + Sync>(&self) -> &D {
-        self.data
-            .0
-            .get(&TypeId::of::<D>())
-            .and_then(|d| d.downcast_ref::<D>())
+        self.data_opt::<D>()
             .expect("The specified data type does not exist.")
     }
 
-    fn var_value(&self, name: &str) -> Result<Value> {
+    /// Gets the data of the current request or of the `Schema`, return `None` if it does not exist.
+    pub fn data_opt<D: Any + Send + Sync>(&self) -> Option<&D> {
+        self.ctx_data
+            .and_then(|ctx_data| ctx_data.get::<D>())
+            .or_else(|| self.data.get::<D>())
+    }
+
+    /// Returns the value of the variable, or `None` if the variable is defined but it is not provided and has no
+    /// default value.
+    fn var_value_opt(&self, name: &str) -> Result<Option<Value>> {
         let def = self
             .variable_definitions
             .and_then(|defs| defs.iter().find(|def| def.name == name));
         if let Some(def) = def {
             if let Some(var_value) = self.variables.map(|vars| vars.get(&def.name)).flatten() {
-                return Ok(var_value.clone());
-            } else if let Some(default) = &def.default_value {
-                return Ok(default.clone());
+                return Ok(Some(var_value.clone()));
+            } else {
+                return Ok(def.default_value.clone());
             }
         }
-        return Err(QueryError::VarNotDefined {
+        Err(QueryError::VarNotDefined {
             var_name: name.to_string(),
         }
-        .into());
+        .into())
+    }
+
+    fn var_value(&self, name: &str) -> Result<Value> {
+        self.var_value_opt(name)?.ok_or_else(|| {
+            QueryError::VarNotDefined {
+                var_name: name.to_string(),
+            }
+            .into()
+        })
+    }
+
+    fn is_var_provided(&self, name: &str) -> bool {
+        matches!(self.var_value_opt(name), Ok(Some(_)))
     }
 
-    fn resolve_input_value(&self, mut value: Value) -> Result<Value> {
+    /// Replace the variables in the value recursively.
+    ///
+    /// The missing optional variables are replaced with `null` in the lists, and the input object fields that use
+    /// them are omitted.
+    pub(crate) fn resolve_input_value(&self, value: Value) -> Result<Value> {
         match value {
             Value::Variable(var_name) => self.var_value(&var_name),
-            Value::List(ref mut ls) => {
+            Value::List(ls) => {
+                let mut res = Vec::with_capacity(ls.len());
                 for value in ls {
-                    if let Value::Variable(var_name) = value {
-                        *value = self.var_value(&var_name)?;
-                    }
+                    res.push(match value {
+                        Value::Variable(var_name) => {
+                            self.var_value_opt(&var_name)?.unwrap_or(Value::Null)
+                        }
+                        value => self.resolve_input_value(value)?,
+                    });
                 }
-                Ok(value)
+                Ok(Value::List(res))
             }
-            Value::Object(ref mut obj) => {
-                for (_, value) in obj {
-                    if let Value::Variable(var_name) = value {
-                        *value = self.var_value(&var_name)?;
+            Value::Object(obj) => {
+                let mut res = BTreeMap::new();
+                for (name, value) in obj {
+                    match value {
+                        Value::Variable(var_name) => {
+                            if let Some(value) = self.var_value_opt(&var_name)? {
+                                res.insert(name, value);
+                            }
+                        }
+                        value => {
+                            res.insert(name, self.resolve_input_value(value)?);
+                        }
                     }
                 }
-                Ok(value)
+                Ok(Value::Object(res))
             }
             _ => Ok(value),
         }
@@ -162,12 +345,9 @@ impl<'a, T> ContextBase<'a, T> {
                     .map(|(_, value)| value)
                 {
                     let value = self.resolve_input_value(value.clone())?;
-                    let res: bool = GQLInputValue::parse(&value).ok_or_else(|| {
-                        QueryError::ExpectedType {
-                            expect: bool::qualified_type_name(),
-                            actual: value,
-                        }
-                        .with_position(directive.position)
+                    let res: bool = GQLInputValue::parse(&value).map_err(|err| {
+                        err.into_argument_error("if")
+                            .with_position(directive.position)
                     })?;
                     if res {
                         return Ok(true);
@@ -189,12 +369,9 @@ impl<'a, T> ContextBase<'a, T> {
                     .map(|(_, value)| value)
                 {
                     let value = self.resolve_input_value(value.clone())?;
-                    let res: bool = GQLInputValue::parse(&value).ok_or_else(|| {
-                        QueryError::ExpectedType {
-                            expect: bool::qualified_type_name(),
-                            actual: value,
-                        }
-                        .with_position(directive.position)
+                    let res: bool = GQLInputValue::parse(&value).map_err(|err| {
+                        err.into_argument_error("if")
+                            .with_position(directive.position)
                     })?;
                     if !res {
                         return Ok(true);
@@ -208,7 +385,7 @@ impl<'a, T> ContextBase<'a, T> {
                     .with_position(directive.position)
                     .into());
                 }
-            } else {
+            } else if directive.name != "defer" && directive.name != "stream" {
                 return Err(QueryError::UnknownDirective {
                     name: directive.name.clone(),
                 }
@@ -219,47 +396,162 @@ impl<'a, T> ContextBase<'a, T> {
 
         Ok(false)
     }
+
+    /// Returns the arguments of the `@defer` or `@stream` directive, `None` if its `if` argument is false.
+    pub(crate) fn incremental_directive(
+        &self,
+        directive: &Directive,
+    ) -> Result<Option<IncrementalDirective>> {
+        let mut res = IncrementalDirective {
+            label: None,
+            initial_count: 0,
+        };
+        for (name, value) in &directive.arguments {
+            let value = self.resolve_input_value(value.clone())?;
+            match name.as_str() {
+                "if" => {
+                    let enabled: bool = GQLInputValue::parse(&value).map_err(|err| {
+                        err.into_argument_error("if")
+                            .with_position(directive.position)
+                    })?;
+                    if !enabled {
+                        return Ok(None);
+                    }
+                }
+                "label" => {
+                    res.label = GQLInputValue::parse(&value).map_err(|err| {
+                        err.into_argument_error("label")
+                            .with_position(directive.position)
+                    })?;
+                }
+                "initialCount" => {
+                    let initial_count: i32 = GQLInputValue::parse(&value).map_err(|err| {
+                        err.into_argument_error("initialCount")
+                            .with_position(directive.position)
+                    })?;
+                    res.initial_count = initial_count.max(0) as usize;
+                }
+                _ => {}
+            }
+        }
+        Ok(Some(res))
+    }
+
+    /// Returns the enabled `@defer` or `@stream` directive with the `name`.
+    pub(crate) fn find_incremental_directive(
+        &self,
+        directives: &[Directive],
+        name: &str,
+    ) -> Result<Option<IncrementalDirective>> {
+        match directives.iter().find(|directive| directive.name == name) {
+            Some(directive) => self.incremental_directive(directive),
+            None => Ok(None),
+        }
+    }
+}
+
+impl<'a> ContextBase<'a, &'a SelectionSet> {
+    #[doc(hidden)]
+    pub fn with_field(&'a self, field: &'a Field) -> ContextBase<'a, &'a Field> {
+        ContextBase {
+            path_node: Some(QueryPathNode {
+                parent: self.path_node.as_ref(),
+                segment: QueryPathSegment::Name(
+                    field.alias.as_deref().unwrap_or(field.name.as_str()),
+                ),
+            }),
+            item: field,
+            variables: self.variables,
+            variable_definitions: self.variable_definitions,
+            registry: self.registry,
+            data: self.data,
+            ctx_data: self.ctx_data,
+            fragments: self.fragments,
+            incremental: self.incremental,
+            initial: None,
+            stream: None,
+            cache_control: self.cache_control,
+        }
+    }
+
+    /// Merge the cache control hints of the object type `type_name` and of its field `field_name` into the hints of
+    /// the response, a field without hints makes the response uncacheable.
+    pub(crate) fn merge_cache_control(&self, type_name: &str, field_name: &str) {
+        let (type_hint, field_hint) = match self.registry.types.get(type_name) {
+            Some(registry::Type::Object {
+                cache_control,
+                fields,
+                ..
+            }) => (
+                *cache_control,
+                fields.get(field_name).and_then(|field| field.cache_control),
+            ),
+            _ => (None, None),
+        };
+        let hint = match (type_hint, field_hint) {
+            (Some(mut type_hint), Some(field_hint)) => {
+                type_hint.merge(&field_hint);
+                type_hint
+            }
+            (type_hint, field_hint) => type_hint.or(field_hint).unwrap_or_default(),
+        };
+
+        let mut response_cache_control = self.cache_control.lock().unwrap();
+        match &mut *response_cache_control {
+            Some(response_cache_control) => response_cache_control.merge(&hint),
+            None => *response_cache_control = Some(hint),
+        }
+    }
 }
 
 impl<'a> ContextBase<'a, &'a Field> {
     #[doc(hidden)]
-    pub fn param_value<T: GQLInputValue, F: FnOnce() -> Value>(
+    pub async fn param_value<T: GQLInputValue, F: FnOnce() -> Value>(
         &self,
         name: &str,
-        default: F,
+        default: Option<F>,
     ) -> Result<T> {
         match self
             .arguments
             .iter()
             .find(|(n, _)| n == name)
             .map(|(_, v)| v)
+            .filter(|value| match value {
+                Value::Variable(var_name) => self.is_var_provided(var_name),
+                _ => true,
+            })
             .cloned()
         {
             Some(value) => {
                 let value = self.resolve_input_value(value)?;
-                let res = GQLInputValue::parse(&value).ok_or_else(|| {
-                    QueryError::ExpectedType {
-                        expect: T::qualified_type_name(),
-                        actual: value,
-                    }
-                    .with_position(self.item.position)
+                T::check_guards(self, &value)
+                    .await
+                    .map_err(|err| err.with_position(self.item.position))?;
+                let res = GQLInputValue::parse(&value).map_err(|err| {
+                    err.into_argument_error(name)
+                        .with_position(self.item.position)
                 })?;
                 Ok(res)
             }
             None => {
-                let value = default();
-                let res = GQLInputValue::parse(&value).ok_or_else(|| {
-                    QueryError::ExpectedType {
-                        expect: T::qualified_type_name(),
-                        actual: value.clone(),
-                    }
-                    .with_position(self.item.position)
+                let value = default.map(|default| default());
+                let res = GQLInputValue::parse_optional(value.as_ref()).map_err(|err| {
+                    err.into_argument_error(name)
+                        .with_position(self.item.position)
                 })?;
                 Ok(res)
             }
         }
     }
 
+    /// Look ahead into the selection set of the current field.
+    ///
+    /// It can be used to find out which fields are requested before resolving them, to fetch only the required
+    /// data.
+    pub fn look_ahead(&self) -> Lookahead<'_> {
+        Lookahead::new(self, vec![self.item])
+    }
+
     #[doc(hidden)]
     pub fn result_name(&self) -> String {
         self.item.alias.clone().unwrap_or_else(|| self.name.clone())
@@ -0,0 +1,838 @@
+//! Compare two versions of a schema and classify the changes.
+//!
+//! The schemas are compared as `Registry` snapshots with `diff_registry`, or as SDL documents with `diff_sdl`. Every
+//! change has a level:
+//!
+//! - `Breaking`: the existing queries may fail, such as a removed field, a field type that becomes nullable, or a new
+//!   required argument.
+//! - `Dangerous`: the existing queries are valid, but the clients may not handle the new responses, such as an added
+//!   enum value or a changed default value.
+//! - `Safe`: the existing clients are not affected, such as an added type or field.
+//!
+//! The `SchemaDiff` can be serialized to JSON to be checked by CI.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::diff::{diff_sdl, ChangeKind};
+//!
+//! let old = "type Query { user: User } type User { name: String! email: String }";
+//! let new = "type Query { user: User } type User { name: String }";
+//! let diff = diff_sdl(old, new).unwrap();
+//! assert!(diff.is_breaking());
+//! assert_eq!(diff.changes[0].kind, ChangeKind::FieldRemoved);
+//! assert_eq!(diff.changes[0].path, "User.email");
+//! ```
+
+use crate::registry::{self, Registry, TypeName};
+use crate::{Result, SchemaParseError, Value};
+use graphql_parser::parse_schema;
+use graphql_parser::schema::{Definition, Directive, Document, TypeDefinition, TypeExtension};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::{self, Display, Formatter};
+
+/// The level of a change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeLevel {
+    /// The existing clients are not affected.
+    Safe,
+
+    /// The existing queries are valid, but the responses may not be handled by the clients.
+    Dangerous,
+
+    /// The existing queries may fail.
+    Breaking,
+}
+
+/// The kind of a change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ChangeKind {
+    RootTypeChanged,
+    TypeAdded,
+    TypeRemoved,
+    TypeKindChanged,
+    FieldAdded,
+    FieldRemoved,
+    FieldTypeChanged,
+    FieldDeprecated,
+    ArgumentAdded,
+    ArgumentRemoved,
+    ArgumentTypeChanged,
+    ArgumentDefaultValueChanged,
+    InputFieldAdded,
+    InputFieldRemoved,
+    InputFieldTypeChanged,
+    InputFieldDefaultValueChanged,
+    EnumValueAdded,
+    EnumValueRemoved,
+    EnumValueDeprecated,
+    UnionMemberAdded,
+    UnionMemberRemoved,
+    InterfaceAdded,
+    InterfaceRemoved,
+}
+
+/// A change between two schemas.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Change {
+    /// The level of the change.
+    pub level: ChangeLevel,
+
+    /// The kind of the change.
+    pub kind: ChangeKind,
+
+    /// The path of the changed item, such as `User`, `User.name` or `Query.user.id`.
+    pub path: String,
+
+    /// The description of the change.
+    pub message: String,
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "[{:?}] {}", self.level, self.message)
+    }
+}
+
+/// The changes between two schemas, ordered by the path.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct SchemaDiff {
+    /// The changes.
+    pub changes: Vec<Change>,
+}
+
+impl SchemaDiff {
+    /// Returns true if the schemas are the same.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns true if there are breaking changes.
+    pub fn is_breaking(&self) -> bool {
+        self.level() == Some(ChangeLevel::Breaking)
+    }
+
+    /// Returns the highest level of the changes, `None` if there are no changes.
+    pub fn level(&self) -> Option<ChangeLevel> {
+        self.changes.iter().map(|change| change.level).max()
+    }
+
+    /// Returns the changes of the `level`.
+    pub fn changes_of_level(&self, level: ChangeLevel) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(move |change| change.level == level)
+    }
+}
+
+/// Compare two registries.
+pub fn diff_registry(old: &Registry, new: &Registry) -> SchemaDiff {
+    diff_models(&Model::from_registry(old), &Model::from_registry(new))
+}
+
+/// Compare two SDL documents.
+pub fn diff_sdl(old: &str, new: &str) -> Result<SchemaDiff> {
+    Ok(diff_models(&Model::from_sdl(old)?, &Model::from_sdl(new)?))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Scalar,
+    Object,
+    Interface,
+    Union,
+    Enum,
+    InputObject,
+}
+
+struct InputValueModel {
+    ty: String,
+    default_value: Option<String>,
+}
+
+struct FieldModel {
+    ty: String,
+    args: BTreeMap<String, InputValueModel>,
+    deprecation: Option<String>,
+}
+
+struct TypeModel {
+    kind: Kind,
+    fields: BTreeMap<String, FieldModel>,
+    interfaces: BTreeSet<String>,
+    possible_types: BTreeSet<String>,
+    enum_values: BTreeMap<String, Option<String>>,
+    input_fields: BTreeMap<String, InputValueModel>,
+}
+
+impl TypeModel {
+    fn new(kind: Kind) -> Self {
+        Self {
+            kind,
+            fields: Default::default(),
+            interfaces: Default::default(),
+            possible_types: Default::default(),
+            enum_values: Default::default(),
+            input_fields: Default::default(),
+        }
+    }
+}
+
+struct Model {
+    query_type: String,
+    mutation_type: Option<String>,
+    types: BTreeMap<String, TypeModel>,
+}
+
+fn is_builtin_type(name: &str) -> bool {
+    name.starts_with("__") || ["Int", "Float", "String", "Boolean", "ID"].contains(&name)
+}
+
+impl Model {
+    fn from_registry(registry: &Registry) -> Self {
+        let input_value = |value: &registry::InputValue| InputValueModel {
+            ty: value.ty.clone(),
+            default_value: value.default_value.as_ref().map(Value::to_string),
+        };
+        let fields = |fields: &HashMap<Cow<'static, str>, registry::Field>| -> BTreeMap<_, _> {
+            fields
+                .values()
+                .filter(|field| !field.name.starts_with("__"))
+                .map(|field| {
+                    (
+                        field.name.to_string(),
+                        FieldModel {
+                            ty: field.ty.clone(),
+                            args: field
+                                .args
+                                .values()
+                                .map(|arg| (arg.name.to_string(), input_value(arg)))
+                                .collect(),
+                            deprecation: field.deprecation.as_deref().map(ToString::to_string),
+                        },
+                    )
+                })
+                .collect()
+        };
+
+        let mut types = BTreeMap::new();
+        for ty in registry.types.values() {
+            if is_builtin_type(ty.name()) {
+                continue;
+            }
+            let model = match ty {
+                registry::Type::Scalar { .. } => TypeModel::new(Kind::Scalar),
+                registry::Type::Object {
+                    name, fields: f, ..
+                } => TypeModel {
+                    fields: fields(f),
+                    interfaces: registry
+                        .implements
+                        .get(name)
+                        .map(|interfaces| interfaces.iter().cloned().collect())
+                        .unwrap_or_default(),
+                    ..TypeModel::new(Kind::Object)
+                },
+                registry::Type::Interface { fields: f, .. } => TypeModel {
+                    fields: fields(f),
+                    ..TypeModel::new(Kind::Interface)
+                },
+                registry::Type::Union { possible_types, .. } => TypeModel {
+                    possible_types: possible_types.iter().cloned().collect(),
+                    ..TypeModel::new(Kind::Union)
+                },
+                registry::Type::Enum { enum_values, .. } => TypeModel {
+                    enum_values: enum_values
+                        .values()
+                        .map(|value| {
+                            (
+                                value.name.to_string(),
+                                value.deprecation.as_deref().map(ToString::to_string),
+                            )
+                        })
+                        .collect(),
+                    ..TypeModel::new(Kind::Enum)
+                },
+                registry::Type::InputObject { input_fields, .. } => TypeModel {
+                    input_fields: input_fields
+                        .iter()
+                        .map(|field| (field.name.to_string(), input_value(field)))
+                        .collect(),
+                    ..TypeModel::new(Kind::InputObject)
+                },
+            };
+            types.insert(ty.name().to_string(), model);
+        }
+
+        Model {
+            query_type: registry.query_type.clone(),
+            mutation_type: registry.mutation_type.clone(),
+            types,
+        }
+    }
+
+    fn from_sdl(sdl: &str) -> Result<Self> {
+        let document: Document =
+            parse_schema(sdl).map_err(|err| SchemaParseError(err.to_string()))?;
+        let input_value = |value: &graphql_parser::schema::InputValue| {
+            (
+                value.name.clone(),
+                InputValueModel {
+                    ty: value.value_type.to_string(),
+                    default_value: value.default_value.as_ref().map(Value::to_string),
+                },
+            )
+        };
+        let fields = |fields: &[graphql_parser::schema::Field]| {
+            fields
+                .iter()
+                .map(|field| {
+                    (
+                        field.name.clone(),
+                        FieldModel {
+                            ty: field.field_type.to_string(),
+                            args: field.arguments.iter().map(input_value).collect(),
+                            deprecation: deprecation(&field.directives),
+                        },
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut schema_definition = None;
+        let mut types: BTreeMap<String, TypeModel> = BTreeMap::new();
+        let mut extensions = Vec::new();
+
+        for definition in &document.definitions {
+            match definition {
+                Definition::SchemaDefinition(schema) => schema_definition = Some(schema),
+                Definition::TypeDefinition(ty) => {
+                    let (name, model) = match ty {
+                        TypeDefinition::Scalar(ty) => (&ty.name, TypeModel::new(Kind::Scalar)),
+                        TypeDefinition::Object(ty) => (
+                            &ty.name,
+                            TypeModel {
+                                fields: fields(&ty.fields).into_iter().collect(),
+                                interfaces: ty.implements_interfaces.iter().cloned().collect(),
+                                ..TypeModel::new(Kind::Object)
+                            },
+                        ),
+                        TypeDefinition::Interface(ty) => (
+                            &ty.name,
+                            TypeModel {
+                                fields: fields(&ty.fields).into_iter().collect(),
+                                ..TypeModel::new(Kind::Interface)
+                            },
+                        ),
+                        TypeDefinition::Union(ty) => (
+                            &ty.name,
+                            TypeModel {
+                                possible_types: ty.types.iter().cloned().collect(),
+                                ..TypeModel::new(Kind::Union)
+                            },
+                        ),
+                        TypeDefinition::Enum(ty) => (
+                            &ty.name,
+                            TypeModel {
+                                enum_values: ty
+                                    .values
+                                    .iter()
+                                    .map(|value| {
+                                        (value.name.clone(), deprecation(&value.directives))
+                                    })
+                                    .collect(),
+                                ..TypeModel::new(Kind::Enum)
+                            },
+                        ),
+                        TypeDefinition::InputObject(ty) => (
+                            &ty.name,
+                            TypeModel {
+                                input_fields: ty.fields.iter().map(input_value).collect(),
+                                ..TypeModel::new(Kind::InputObject)
+                            },
+                        ),
+                    };
+                    if !is_builtin_type(name) {
+                        types.insert(name.clone(), model);
+                    }
+                }
+                Definition::TypeExtension(extension) => extensions.push(extension),
+                Definition::DirectiveDefinition(_) => {}
+            }
+        }
+
+        // The extensions are applied after all the types are defined, they may precede the types they extend.
+        for extension in extensions {
+            let name = match extension {
+                TypeExtension::Scalar(ty) => &ty.name,
+                TypeExtension::Object(ty) => &ty.name,
+                TypeExtension::Interface(ty) => &ty.name,
+                TypeExtension::Union(ty) => &ty.name,
+                TypeExtension::Enum(ty) => &ty.name,
+                TypeExtension::InputObject(ty) => &ty.name,
+            };
+            let model = match types.get_mut(name) {
+                Some(model) => model,
+                None => {
+                    let kind = match extension {
+                        TypeExtension::Scalar(_) => Kind::Scalar,
+                        TypeExtension::Object(_) => Kind::Object,
+                        TypeExtension::Interface(_) => Kind::Interface,
+                        TypeExtension::Union(_) => Kind::Union,
+                        TypeExtension::Enum(_) => Kind::Enum,
+                        TypeExtension::InputObject(_) => Kind::InputObject,
+                    };
+                    types
+                        .entry(name.clone())
+                        .or_insert_with(|| TypeModel::new(kind))
+                }
+            };
+            match extension {
+                TypeExtension::Scalar(_) => {}
+                TypeExtension::Object(ty) => {
+                    model.fields.extend(fields(&ty.fields));
+                    model
+                        .interfaces
+                        .extend(ty.implements_interfaces.iter().cloned());
+                }
+                TypeExtension::Interface(ty) => model.fields.extend(fields(&ty.fields)),
+                TypeExtension::Union(ty) => model.possible_types.extend(ty.types.iter().cloned()),
+                TypeExtension::Enum(ty) => model.enum_values.extend(
+                    ty.values
+                        .iter()
+                        .map(|value| (value.name.clone(), deprecation(&value.directives))),
+                ),
+                TypeExtension::InputObject(ty) => {
+                    model.input_fields.extend(ty.fields.iter().map(input_value))
+                }
+            }
+        }
+
+        // Without a schema definition, the root types are the types with the default names.
+        let (query_type, mutation_type) = match schema_definition {
+            Some(schema) => (
+                schema.query.clone().unwrap_or_else(|| "Query".to_string()),
+                schema.mutation.clone(),
+            ),
+            None => (
+                "Query".to_string(),
+                Some("Mutation".to_string()).filter(|name| types.contains_key(name)),
+            ),
+        };
+        Ok(Model {
+            query_type,
+            mutation_type,
+            types,
+        })
+    }
+}
+
+fn deprecation(directives: &[Directive]) -> Option<String> {
+    directives
+        .iter()
+        .find(|directive| directive.name == "deprecated")
+        .map(|directive| {
+            directive
+                .arguments
+                .iter()
+                .find_map(|(name, value)| match value {
+                    Value::String(reason) if name == "reason" => Some(reason.clone()),
+                    _ => None,
+                })
+                .unwrap_or_else(|| "No longer supported".to_string())
+        })
+}
+
+/// Returns true if the values of the type `new` are valid values of the output type `old`, such as `String!` for
+/// `String`.
+fn is_safe_output_type_change(old: &str, new: &str) -> bool {
+    match (TypeName::create(old), TypeName::create(new)) {
+        (TypeName::NonNull(old), TypeName::NonNull(new)) => is_safe_output_type_change(old, new),
+        (TypeName::NonNull(_), _) => false,
+        (_, TypeName::NonNull(new)) => is_safe_output_type_change(old, new),
+        (TypeName::List(old), TypeName::List(new)) => is_safe_output_type_change(old, new),
+        (TypeName::Named(old), TypeName::Named(new)) => old == new,
+        _ => false,
+    }
+}
+
+/// Returns true if the values of the input type `old` are valid values of the type `new`, such as `String` for
+/// `String!`.
+fn is_safe_input_type_change(old: &str, new: &str) -> bool {
+    match (TypeName::create(old), TypeName::create(new)) {
+        (TypeName::NonNull(old), TypeName::NonNull(new)) => is_safe_input_type_change(old, new),
+        (TypeName::NonNull(old), _) => is_safe_input_type_change(old, new),
+        (_, TypeName::NonNull(_)) => false,
+        (TypeName::List(old), TypeName::List(new)) => is_safe_input_type_change(old, new),
+        (TypeName::Named(old), TypeName::Named(new)) => old == new,
+        _ => false,
+    }
+}
+
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn add(&mut self, level: ChangeLevel, kind: ChangeKind, path: String, message: String) {
+        self.changes.push(Change {
+            level,
+            kind,
+            path,
+            message,
+        });
+    }
+
+    fn diff_fields(
+        &mut self,
+        type_name: &str,
+        old: &BTreeMap<String, FieldModel>,
+        new: &BTreeMap<String, FieldModel>,
+    ) {
+        for (name, old_field) in old {
+            let path = format!("{}.{}", type_name, name);
+            let new_field = match new.get(name) {
+                Some(new_field) => new_field,
+                None => {
+                    self.add(
+                        ChangeLevel::Breaking,
+                        ChangeKind::FieldRemoved,
+                        path.clone(),
+                        format!("Field \"{}\" was removed.", path),
+                    );
+                    continue;
+                }
+            };
+
+            if old_field.ty != new_field.ty {
+                let level = if is_safe_output_type_change(&old_field.ty, &new_field.ty) {
+                    ChangeLevel::Safe
+                } else {
+                    ChangeLevel::Breaking
+                };
+                self.add(
+                    level,
+                    ChangeKind::FieldTypeChanged,
+                    path.clone(),
+                    format!(
+                        "Field \"{}\" changed type from \"{}\" to \"{}\".",
+                        path, old_field.ty, new_field.ty
+                    ),
+                );
+            }
+
+            if old_field.deprecation.is_none() && new_field.deprecation.is_some() {
+                self.add(
+                    ChangeLevel::Safe,
+                    ChangeKind::FieldDeprecated,
+                    path.clone(),
+                    format!("Field \"{}\" was deprecated.", path),
+                );
+            }
+
+            for (arg_name, old_arg) in &old_field.args {
+                let arg_path = format!("{}.{}", path, arg_name);
+                match new_field.args.get(arg_name) {
+                    Some(new_arg) => self.diff_input_value(
+                        &arg_path,
+                        "Argument",
+                        ChangeKind::ArgumentTypeChanged,
+                        ChangeKind::ArgumentDefaultValueChanged,
+                        old_arg,
+                        new_arg,
+                    ),
+                    None => self.add(
+                        ChangeLevel::Breaking,
+                        ChangeKind::ArgumentRemoved,
+                        arg_path.clone(),
+                        format!("Argument \"{}\" was removed.", arg_path),
+                    ),
+                }
+            }
+            for (arg_name, new_arg) in &new_field.args {
+                if !old_field.args.contains_key(arg_name) {
+                    self.added_input_value(
+                        &format!("{}.{}", path, arg_name),
+                        "argument",
+                        ChangeKind::ArgumentAdded,
+                        new_arg,
+                    );
+                }
+            }
+        }
+
+        for name in new.keys() {
+            if !old.contains_key(name) {
+                let path = format!("{}.{}", type_name, name);
+                self.add(
+                    ChangeLevel::Safe,
+                    ChangeKind::FieldAdded,
+                    path.clone(),
+                    format!("Field \"{}\" was added.", path),
+                );
+            }
+        }
+    }
+
+    fn diff_input_value(
+        &mut self,
+        path: &str,
+        noun: &str,
+        type_changed: ChangeKind,
+        default_value_changed: ChangeKind,
+        old: &InputValueModel,
+        new: &InputValueModel,
+    ) {
+        if old.ty != new.ty {
+            let level = if is_safe_input_type_change(&old.ty, &new.ty) {
+                ChangeLevel::Safe
+            } else {
+                ChangeLevel::Breaking
+            };
+            self.add(
+                level,
+                type_changed,
+                path.to_string(),
+                format!(
+                    "{} \"{}\" changed type from \"{}\" to \"{}\".",
+                    noun, path, old.ty, new.ty
+                ),
+            );
+        }
+        if old.default_value != new.default_value {
+            // A non-null value without a default value becomes required.
+            let level = if new.default_value.is_none() && TypeName::create(&new.ty).is_non_null() {
+                ChangeLevel::Breaking
+            } else {
+                ChangeLevel::Dangerous
+            };
+            self.add(
+                level,
+                default_value_changed,
+                path.to_string(),
+                format!(
+                    "{} \"{}\" changed default value from {} to {}.",
+                    noun,
+                    path,
+                    old.default_value.as_deref().unwrap_or("none"),
+                    new.default_value.as_deref().unwrap_or("none")
+                ),
+            );
+        }
+    }
+
+    fn added_input_value(
+        &mut self,
+        path: &str,
+        noun: &str,
+        kind: ChangeKind,
+        new: &InputValueModel,
+    ) {
+        if TypeName::create(&new.ty).is_non_null() && new.default_value.is_none() {
+            self.add(
+                ChangeLevel::Breaking,
+                kind,
+                path.to_string(),
+                format!("Required {} \"{}\" was added.", noun, path),
+            );
+        } else {
+            self.add(
+                ChangeLevel::Safe,
+                kind,
+                path.to_string(),
+                format!("Optional {} \"{}\" was added.", noun, path),
+            );
+        }
+    }
+
+    fn diff_set(
+        &mut self,
+        type_name: &str,
+        old: &BTreeSet<String>,
+        new: &BTreeSet<String>,
+        (removed, removed_message): (ChangeKind, &str),
+        (added, added_message): (ChangeKind, &str),
+    ) {
+        for name in old.difference(new) {
+            self.add(
+                ChangeLevel::Breaking,
+                removed,
+                type_name.to_string(),
+                format!("\"{}\" {} \"{}\".", name, removed_message, type_name),
+            );
+        }
+        for name in new.difference(old) {
+            self.add(
+                ChangeLevel::Dangerous,
+                added,
+                type_name.to_string(),
+                format!("\"{}\" {} \"{}\".", name, added_message, type_name),
+            );
+        }
+    }
+
+    fn diff_type(&mut self, name: &str, old: &TypeModel, new: &TypeModel) {
+        if old.kind != new.kind {
+            self.add(
+                ChangeLevel::Breaking,
+                ChangeKind::TypeKindChanged,
+                name.to_string(),
+                format!(
+                    "Type \"{}\" changed from {:?} to {:?}.",
+                    name, old.kind, new.kind
+                ),
+            );
+            return;
+        }
+
+        match old.kind {
+            Kind::Scalar => {}
+            Kind::Object | Kind::Interface => {
+                self.diff_fields(name, &old.fields, &new.fields);
+                self.diff_set(
+                    name,
+                    &old.interfaces,
+                    &new.interfaces,
+                    (ChangeKind::InterfaceRemoved, "is no longer implemented by"),
+                    (ChangeKind::InterfaceAdded, "is implemented by"),
+                );
+            }
+            Kind::Union => self.diff_set(
+                name,
+                &old.possible_types,
+                &new.possible_types,
+                (ChangeKind::UnionMemberRemoved, "was removed from the union"),
+                (ChangeKind::UnionMemberAdded, "was added to the union"),
+            ),
+            Kind::Enum => {
+                for (value, old_deprecation) in &old.enum_values {
+                    let path = format!("{}.{}", name, value);
+                    match new.enum_values.get(value) {
+                        Some(new_deprecation) => {
+                            if old_deprecation.is_none() && new_deprecation.is_some() {
+                                self.add(
+                                    ChangeLevel::Safe,
+                                    ChangeKind::EnumValueDeprecated,
+                                    path.clone(),
+                                    format!("Enum value \"{}\" was deprecated.", path),
+                                );
+                            }
+                        }
+                        None => self.add(
+                            ChangeLevel::Breaking,
+                            ChangeKind::EnumValueRemoved,
+                            path.clone(),
+                            format!("Enum value \"{}\" was removed.", path),
+                        ),
+                    }
+                }
+                for value in new.enum_values.keys() {
+                    if !old.enum_values.contains_key(value) {
+                        let path = format!("{}.{}", name, value);
+                        self.add(
+                            ChangeLevel::Dangerous,
+                            ChangeKind::EnumValueAdded,
+                            path.clone(),
+                            format!("Enum value \"{}\" was added.", path),
+                        );
+                    }
+                }
+            }
+            Kind::InputObject => {
+                for (field_name, old_field) in &old.input_fields {
+                    let path = format!("{}.{}", name, field_name);
+                    match new.input_fields.get(field_name) {
+                        Some(new_field) => self.diff_input_value(
+                            &path,
+                            "Input field",
+                            ChangeKind::InputFieldTypeChanged,
+                            ChangeKind::InputFieldDefaultValueChanged,
+                            old_field,
+                            new_field,
+                        ),
+                        None => self.add(
+                            ChangeLevel::Breaking,
+                            ChangeKind::InputFieldRemoved,
+                            path.clone(),
+                            format!("Input field \"{}\" was removed.", path),
+                        ),
+                    }
+                }
+                for (field_name, new_field) in &new.input_fields {
+                    if !old.input_fields.contains_key(field_name) {
+                        self.added_input_value(
+                            &format!("{}.{}", name, field_name),
+                            "input field",
+                            ChangeKind::InputFieldAdded,
+                            new_field,
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn diff_models(old: &Model, new: &Model) -> SchemaDiff {
+    let mut differ = Differ {
+        changes: Vec::new(),
+    };
+
+    if old.query_type != new.query_type {
+        differ.add(
+            ChangeLevel::Breaking,
+            ChangeKind::RootTypeChanged,
+            "query".to_string(),
+            format!(
+                "Query root type changed from \"{}\" to \"{}\".",
+                old.query_type, new.query_type
+            ),
+        );
+    }
+    if old.mutation_type != new.mutation_type {
+        let level = if old.mutation_type.is_none() {
+            ChangeLevel::Safe
+        } else {
+            ChangeLevel::Breaking
+        };
+        differ.add(
+            level,
+            ChangeKind::RootTypeChanged,
+            "mutation".to_string(),
+            format!(
+                "Mutation root type changed from \"{}\" to \"{}\".",
+                old.mutation_type.as_deref().unwrap_or("none"),
+                new.mutation_type.as_deref().unwrap_or("none")
+            ),
+        );
+    }
+
+    for (name, old_type) in &old.types {
+        match new.types.get(name) {
+            Some(new_type) => differ.diff_type(name, old_type, new_type),
+            None => differ.add(
+                ChangeLevel::Breaking,
+                ChangeKind::TypeRemoved,
+                name.clone(),
+                format!("Type \"{}\" was removed.", name),
+            ),
+        }
+    }
+    for name in new.types.keys() {
+        if !old.types.contains_key(name) {
+            differ.add(
+                ChangeLevel::Safe,
+                ChangeKind::TypeAdded,
+                name.clone(),
+                format!("Type \"{}\" was added.", name),
+            );
+        }
+    }
+
+    let mut changes = differ.changes;
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    SchemaDiff { changes }
+}
@@ -0,0 +1,233 @@
+use crate::scalars::Any as AnyValue;
+use crate::{Context, ContextSelectionSet, GQLOutputValue, Result, Value};
+use futures::future::BoxFuture;
+use std::any::Any;
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// The future returned by the resolver of a dynamic field.
+pub type FieldFuture<'a> = BoxFuture<'a, Result<FieldValue>>;
+
+pub(crate) type BoxResolverFn =
+    Arc<dyn for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync>;
+
+#[async_trait::async_trait]
+pub(crate) trait ErasedOutputValue: Send + Sync {
+    async fn resolve(&self, ctx: &ContextSelectionSet<'_>) -> Result<serde_json::Value>;
+}
+
+#[async_trait::async_trait]
+impl<T: GQLOutputValue + Send + Sync> ErasedOutputValue for T {
+    async fn resolve(&self, ctx: &ContextSelectionSet<'_>) -> Result<serde_json::Value> {
+        GQLOutputValue::resolve(self, ctx).await
+    }
+}
+
+pub(crate) enum FieldValueInner {
+    Null,
+    Value(serde_json::Value),
+    OwnedAny(Box<dyn Any + Send + Sync>),
+    List(Vec<FieldValue>),
+    Output(Box<dyn ErasedOutputValue>),
+    WithType {
+        value: Box<FieldValue>,
+        type_name: String,
+    },
+}
+
+/// The value returned by the resolver of a dynamic field.
+///
+/// - The value of a scalar or an enum is a JSON value, created with `FieldValue::value`.
+/// - The value of a dynamic object is any Rust value, created with `FieldValue::owned_any`, it is the
+///   `parent_value` of the resolvers of the fields of the object.
+/// - The value of a statically typed object or scalar is created with `FieldValue::output`.
+/// - The value of an interface must specify its object type with `FieldValue::with_type`.
+pub struct FieldValue(pub(crate) FieldValueInner);
+
+impl FieldValue {
+    /// A null value.
+    pub const NULL: FieldValue = FieldValue(FieldValueInner::Null);
+
+    /// Create the value of a scalar or an enum.
+    pub fn value(value: impl Into<serde_json::Value>) -> Self {
+        FieldValue(FieldValueInner::Value(value.into()))
+    }
+
+    /// Create the value of a dynamic object.
+    pub fn owned_any<T: Any + Send + Sync>(value: T) -> Self {
+        FieldValue(FieldValueInner::OwnedAny(Box::new(value)))
+    }
+
+    /// Create the value of a list.
+    pub fn list(values: impl IntoIterator<Item = FieldValue>) -> Self {
+        FieldValue(FieldValueInner::List(values.into_iter().collect()))
+    }
+
+    /// Create a value that is resolved as a statically typed value, its type must be registered with
+    /// `SchemaBuilder::register_type`.
+    pub fn output<T: GQLOutputValue + Send + Sync + 'static>(value: T) -> Self {
+        FieldValue(FieldValueInner::Output(Box::new(value)))
+    }
+
+    /// Specify the object type of the value, it is required for the values of interfaces.
+    pub fn with_type(self, type_name: impl Into<String>) -> Self {
+        FieldValue(FieldValueInner::WithType {
+            value: Box::new(self),
+            type_name: type_name.into(),
+        })
+    }
+
+    /// Returns true if the value is null.
+    pub fn is_null(&self) -> bool {
+        matches!(self.inner(), FieldValueInner::Null)
+    }
+
+    /// Returns a reference to the value of a dynamic object created with `FieldValue::owned_any`, `None` if it is
+    /// not of type `T`.
+    pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+        match self.inner() {
+            FieldValueInner::OwnedAny(value) => value.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+
+    /// The value without the type specified with `FieldValue::with_type`.
+    pub(crate) fn inner(&self) -> &FieldValueInner {
+        match &self.0 {
+            FieldValueInner::WithType { value, .. } => value.inner(),
+            inner => inner,
+        }
+    }
+
+    /// The type specified with `FieldValue::with_type`.
+    pub(crate) fn type_name(&self) -> Option<&str> {
+        match &self.0 {
+            FieldValueInner::WithType { type_name, .. } => Some(type_name),
+            _ => None,
+        }
+    }
+}
+
+/// An argument of a dynamic field.
+pub struct InputValue {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) ty: String,
+    pub(crate) default_value: Option<Value>,
+}
+
+impl InputValue {
+    /// Create an argument with the `name` and the type `ty`, such as `Int!` or `[String]`.
+    pub fn new(name: impl Into<String>, ty: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            ty: ty.into(),
+            default_value: None,
+        }
+    }
+
+    /// Set the description of the argument.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Set the default value of the argument.
+    pub fn default_value(self, value: Value) -> Self {
+        Self {
+            default_value: Some(value),
+            ..self
+        }
+    }
+}
+
+/// A field of a dynamic object.
+pub struct Field {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) args: Vec<InputValue>,
+    pub(crate) ty: String,
+    pub(crate) deprecation: Option<String>,
+    pub(crate) resolver: BoxResolverFn,
+}
+
+impl Field {
+    /// Create a field with the `name`, the type `ty`, such as `String!` or `[User!]`, and the resolver that returns
+    /// its value.
+    pub fn new<F>(name: impl Into<String>, ty: impl Into<String>, resolver: F) -> Self
+    where
+        F: for<'a> Fn(ResolverContext<'a>) -> FieldFuture<'a> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            description: None,
+            args: Vec::new(),
+            ty: ty.into(),
+            deprecation: None,
+            resolver: Arc::new(resolver),
+        }
+    }
+
+    /// Set the description of the field.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add an argument to the field.
+    pub fn argument(mut self, input_value: InputValue) -> Self {
+        self.args.push(input_value);
+        self
+    }
+
+    /// Set the deprecation reason of the field.
+    pub fn deprecation(self, reason: impl Into<String>) -> Self {
+        Self {
+            deprecation: Some(reason.into()),
+            ..self
+        }
+    }
+}
+
+/// The context of the resolver of a dynamic field, it dereferences to the `Context` of the field.
+pub struct ResolverContext<'a> {
+    /// The context of the field.
+    pub ctx: &'a Context<'a>,
+
+    /// The value of the object of the field.
+    pub parent_value: &'a FieldValue,
+
+    pub(crate) field: &'a Field,
+}
+
+impl<'a> Deref for ResolverContext<'a> {
+    type Target = Context<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.ctx
+    }
+}
+
+impl<'a> ResolverContext<'a> {
+    /// Returns the value of the argument with the `name`, the variables are replaced with their values.
+    ///
+    /// If the argument is not provided, its default value is returned, or `Value::Null` if it has none.
+    pub async fn arg(&self, name: &str) -> Result<Value> {
+        let default = self
+            .field
+            .args
+            .iter()
+            .find(|arg| arg.name == name)
+            .and_then(|arg| arg.default_value.clone());
+        let value: AnyValue = self
+            .ctx
+            .param_value(name, default.map(|value| move || value))
+            .await?;
+        Ok(value.0)
+    }
+}
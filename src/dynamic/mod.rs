@@ -0,0 +1,71 @@
+//! Schemas with types that are defined at runtime.
+//!
+//! The objects, interfaces, fields and arguments of a dynamic schema are values, and the fields are resolved by
+//! closures that return futures. The types are added to the same registry as the statically typed ones, so the
+//! queries are validated by the same rules, and the introspection and the SDL include them.
+//!
+//! Statically typed types can be used by the dynamic fields: they are registered with
+//! `SchemaBuilder::register_type`, and the resolvers return them with `FieldValue::output`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use async_graphql::dynamic::*;
+//! use async_graphql::Value;
+//! use futures::FutureExt;
+//!
+//! struct Column {
+//!     name: String,
+//! }
+//!
+//! #[async_std::main]
+//! async fn main() {
+//!     let column = Object::new("Column").field(Field::new("name", "String!", |ctx| {
+//!         async move {
+//!             let column = ctx.parent_value.downcast_ref::<Column>().unwrap();
+//!             Ok(FieldValue::value(column.name.clone()))
+//!         }
+//!         .boxed()
+//!     }));
+//!     let query = Object::new("Query").field(
+//!         Field::new("columns", "[Column!]!", |ctx| {
+//!             async move {
+//!                 let prefix = match ctx.arg("prefix").await? {
+//!                     Value::String(prefix) => prefix,
+//!                     _ => String::new(),
+//!                 };
+//!                 Ok(FieldValue::list(["id", "name"].iter().map(|name| {
+//!                     FieldValue::owned_any(Column {
+//!                         name: format!("{}{}", prefix, name),
+//!                     })
+//!                 })))
+//!             }
+//!             .boxed()
+//!         })
+//!         .argument(InputValue::new("prefix", "String")),
+//!     );
+//!     let schema = Schema::build("Query", None)
+//!         .register(column)
+//!         .register(query)
+//!         .finish()
+//!         .unwrap();
+//!
+//!     let res = schema
+//!         .query(r#"{ columns(prefix: "user_") { name } }"#)
+//!         .execute()
+//!         .await
+//!         .unwrap();
+//!     assert_eq!(
+//!         res,
+//!         serde_json::json!({ "columns": [{ "name": "user_id" }, { "name": "user_name" }] })
+//!     );
+//! }
+//! ```
+
+mod field;
+mod object;
+mod schema;
+
+pub use field::{Field, FieldFuture, FieldValue, InputValue, ResolverContext};
+pub use object::{Interface, InterfaceField, Object, Type};
+pub use schema::{QueryBuilder, Schema, SchemaBuilder};
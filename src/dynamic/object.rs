@@ -0,0 +1,139 @@
+use crate::dynamic::{Field, InputValue};
+
+/// A dynamic object type.
+pub struct Object {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) fields: Vec<Field>,
+    pub(crate) implements: Vec<String>,
+}
+
+impl Object {
+    /// Create an object type with the `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Vec::new(),
+            implements: Vec::new(),
+        }
+    }
+
+    /// Set the description of the object.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add a field to the object.
+    pub fn field(mut self, field: Field) -> Self {
+        self.fields.push(field);
+        self
+    }
+
+    /// Declare that the object implements the interface with the `name`.
+    pub fn implement(mut self, name: impl Into<String>) -> Self {
+        self.implements.push(name.into());
+        self
+    }
+
+    pub(crate) fn field_by_name(&self, name: &str) -> Option<&Field> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+/// A field of a dynamic interface, it is resolved by the field with the same name of the object type of the value.
+pub struct InterfaceField {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) args: Vec<InputValue>,
+    pub(crate) ty: String,
+    pub(crate) deprecation: Option<String>,
+}
+
+impl InterfaceField {
+    /// Create an interface field with the `name` and the type `ty`.
+    pub fn new(name: impl Into<String>, ty: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            args: Vec::new(),
+            ty: ty.into(),
+            deprecation: None,
+        }
+    }
+
+    /// Set the description of the field.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add an argument to the field.
+    pub fn argument(mut self, input_value: InputValue) -> Self {
+        self.args.push(input_value);
+        self
+    }
+
+    /// Set the deprecation reason of the field.
+    pub fn deprecation(self, reason: impl Into<String>) -> Self {
+        Self {
+            deprecation: Some(reason.into()),
+            ..self
+        }
+    }
+}
+
+/// A dynamic interface type.
+pub struct Interface {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) fields: Vec<InterfaceField>,
+}
+
+impl Interface {
+    /// Create an interface type with the `name`.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            fields: Vec::new(),
+        }
+    }
+
+    /// Set the description of the interface.
+    pub fn description(self, description: impl Into<String>) -> Self {
+        Self {
+            description: Some(description.into()),
+            ..self
+        }
+    }
+
+    /// Add a field to the interface.
+    pub fn field(mut self, field: InterfaceField) -> Self {
+        self.fields.push(field);
+        self
+    }
+}
+
+/// A dynamic type, it is registered with `SchemaBuilder::register`.
+pub enum Type {
+    Object(Object),
+    Interface(Interface),
+}
+
+impl From<Object> for Type {
+    fn from(object: Object) -> Self {
+        Type::Object(object)
+    }
+}
+
+impl From<Interface> for Type {
+    fn from(interface: Interface) -> Self {
+        Type::Interface(interface)
+    }
+}
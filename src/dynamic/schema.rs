@@ -0,0 +1,628 @@
+use crate::context::Data;
+use crate::dynamic::field::FieldValueInner;
+use crate::dynamic::{FieldValue, InputValue, Object, ResolverContext, Type};
+use crate::registry::{self, Registry, TypeName};
+use crate::resolver::{resolve_object, ResolveObject};
+use crate::schema::{create_registry, Introspection, QueryOptions};
+use crate::timer::Timer;
+use crate::types::{
+    add_introspection_fields, resolve_introspection_field, resolve_list, ListItems,
+};
+use crate::validation::{check_default_values, check_sources};
+use crate::{
+    Context, ContextSelectionSet, GQLType, IncrementalResponse, QueryError, QueryResponse, Result,
+    SchemaError, Variables,
+};
+use futures::future::BoxFuture;
+use graphql_parser::query::Field;
+use std::any::Any;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+
+fn create_args(args: &[InputValue]) -> HashMap<Cow<'static, str>, registry::InputValue> {
+    args.iter()
+        .map(|arg| {
+            (
+                Cow::Owned(arg.name.clone()),
+                registry::InputValue {
+                    name: Cow::Owned(arg.name.clone()),
+                    description: arg.description.clone().map(Cow::Owned),
+                    ty: arg.ty.clone(),
+                    default_value: arg.default_value.clone(),
+                    validator: None,
+                },
+            )
+        })
+        .collect()
+}
+
+fn create_field(
+    name: &str,
+    description: &Option<String>,
+    args: &[InputValue],
+    ty: &str,
+    deprecation: &Option<String>,
+) -> registry::Field {
+    registry::Field {
+        name: Cow::Owned(name.to_string()),
+        description: description.clone().map(Cow::Owned),
+        args: create_args(args),
+        ty: ty.to_string(),
+        deprecation: deprecation.clone().map(Cow::Owned),
+        external: false,
+        requires: None,
+        provides: None,
+        cache_control: Default::default(),
+    }
+}
+
+fn check_field_types<'a>(
+    registry: &Registry,
+    type_name: &str,
+    fields: impl Iterator<Item = (&'a str, &'a str, &'a [InputValue])>,
+) -> std::result::Result<(), SchemaError> {
+    for (field_name, ty, args) in fields {
+        match registry.get_basic_type(ty) {
+            Some(registry::Type::InputObject { .. }) => {
+                return Err(SchemaError(format!(
+                    "The type \"{}\" of the field \"{}.{}\" is not an output type.",
+                    ty, type_name, field_name
+                )))
+            }
+            Some(_) => {}
+            None => {
+                return Err(SchemaError(format!(
+                    "The type \"{}\" of the field \"{}.{}\" is not defined.",
+                    ty, type_name, field_name
+                )))
+            }
+        }
+
+        for arg in args {
+            match registry.get_basic_type(&arg.ty) {
+                Some(registry::Type::Scalar { .. })
+                | Some(registry::Type::Enum { .. })
+                | Some(registry::Type::InputObject { .. }) => {}
+                Some(_) => {
+                    return Err(SchemaError(format!(
+                        "The type \"{}\" of the argument \"{}\" of the field \"{}.{}\" is not an input type.",
+                        arg.ty, arg.name, type_name, field_name
+                    )))
+                }
+                None => {
+                    return Err(SchemaError(format!(
+                        "The type \"{}\" of the argument \"{}\" of the field \"{}.{}\" is not defined.",
+                        arg.ty, arg.name, type_name, field_name
+                    )))
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The builder of a dynamic schema, created by `Schema::build`.
+pub struct SchemaBuilder {
+    registry: Registry,
+    types: Vec<Type>,
+    data: Data,
+    introspection: Introspection,
+    timeout: Option<Duration>,
+}
+
+impl SchemaBuilder {
+    /// Register a dynamic object or interface.
+    pub fn register(mut self, ty: impl Into<Type>) -> Self {
+        self.types.push(ty.into());
+        self
+    }
+
+    /// Register a statically typed type, such as an `Enum`, an `InputObject` or an `Object`, so that it can be used
+    /// by the dynamic fields and arguments.
+    pub fn register_type<T: GQLType>(mut self) -> Self {
+        T::create_type_info(&mut self.registry);
+        self
+    }
+
+    /// Add a global data that can be accessed in the `Context`.
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.data.insert(data);
+        self
+    }
+
+    /// Set the maximum duration of the execution of the queries, see `async_graphql::Schema::timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Disable introspection queries (`__schema` and `__type`), they will fail validation.
+    pub fn disable_introspection(mut self) -> Self {
+        self.introspection = Introspection::Disabled;
+        self
+    }
+
+    /// Only allow introspection queries when `f` returns true, see `async_graphql::Schema::introspection_predicate`.
+    pub fn introspection_predicate<F: Fn(&Data) -> bool + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.introspection = Introspection::Predicate(Box::new(f));
+        self
+    }
+
+    /// Check the types and build the schema.
+    pub fn finish(self) -> std::result::Result<Schema, SchemaError> {
+        let SchemaBuilder {
+            mut registry,
+            types,
+            data,
+            introspection,
+            timeout,
+        } = self;
+        let mut objects = HashMap::new();
+        let mut interfaces = Vec::new();
+
+        for ty in types {
+            let name = match &ty {
+                Type::Object(object) => &object.name,
+                Type::Interface(interface) => &interface.name,
+            };
+            if registry.types.contains_key(name) {
+                return Err(SchemaError(format!(
+                    "The type \"{}\" is already defined.",
+                    name
+                )));
+            }
+
+            match ty {
+                Type::Object(object) => {
+                    let mut fields = HashMap::new();
+                    for field in &object.fields {
+                        let registry_field = create_field(
+                            &field.name,
+                            &field.description,
+                            &field.args,
+                            &field.ty,
+                            &field.deprecation,
+                        );
+                        if fields
+                            .insert(registry_field.name.clone(), registry_field)
+                            .is_some()
+                        {
+                            return Err(SchemaError(format!(
+                                "The field \"{}.{}\" is already defined.",
+                                object.name, field.name
+                            )));
+                        }
+                    }
+                    registry.types.insert(
+                        object.name.clone(),
+                        registry::Type::Object {
+                            name: object.name.clone(),
+                            description: object.description.clone().map(Cow::Owned),
+                            fields,
+                            extends: false,
+                            keys: None,
+                            cache_control: Default::default(),
+                        },
+                    );
+                    for interface in &object.implements {
+                        registry.add_implements(&object.name, interface);
+                    }
+                    objects.insert(object.name.clone(), object);
+                }
+                Type::Interface(interface) => {
+                    let mut fields = HashMap::new();
+                    for field in &interface.fields {
+                        let registry_field = create_field(
+                            &field.name,
+                            &field.description,
+                            &field.args,
+                            &field.ty,
+                            &field.deprecation,
+                        );
+                        if fields
+                            .insert(registry_field.name.clone(), registry_field)
+                            .is_some()
+                        {
+                            return Err(SchemaError(format!(
+                                "The field \"{}.{}\" is already defined.",
+                                interface.name, field.name
+                            )));
+                        }
+                    }
+                    registry.types.insert(
+                        interface.name.clone(),
+                        registry::Type::Interface {
+                            name: Cow::Owned(interface.name.clone()),
+                            description: interface.description.clone().map(Cow::Owned),
+                            fields,
+                            possible_types: HashSet::new(),
+                        },
+                    );
+                    interfaces.push(interface);
+                }
+            }
+        }
+
+        for root_type in std::iter::once(&registry.query_type).chain(&registry.mutation_type) {
+            if !objects.contains_key(root_type) {
+                return Err(SchemaError(format!(
+                    "The root type \"{}\" is not a dynamic object.",
+                    root_type
+                )));
+            }
+        }
+
+        for object in objects.values() {
+            for interface_name in &object.implements {
+                let interface_fields = match registry.types.get_mut(interface_name) {
+                    Some(registry::Type::Interface {
+                        fields,
+                        possible_types,
+                        ..
+                    }) => {
+                        possible_types.insert(object.name.clone());
+                        fields
+                            .values()
+                            .map(|field| (field.name.clone(), field.ty.clone()))
+                            .collect::<Vec<_>>()
+                    }
+                    _ => {
+                        return Err(SchemaError(format!(
+                            "The object \"{}\" implements \"{}\", which is not an interface.",
+                            object.name, interface_name
+                        )))
+                    }
+                };
+                for (field_name, ty) in interface_fields {
+                    match object.field_by_name(&field_name) {
+                        Some(field) if field.ty == ty => {}
+                        _ => {
+                            return Err(SchemaError(format!(
+                                "The object \"{}\" does not have the field \"{}: {}\" of the interface \"{}\".",
+                                object.name, field_name, ty, interface_name
+                            )))
+                        }
+                    }
+                }
+            }
+
+            check_field_types(
+                &registry,
+                &object.name,
+                object.fields.iter().map(|field| {
+                    (
+                        field.name.as_str(),
+                        field.ty.as_str(),
+                        field.args.as_slice(),
+                    )
+                }),
+            )?;
+        }
+
+        for interface in &interfaces {
+            check_field_types(
+                &registry,
+                &interface.name,
+                interface.fields.iter().map(|field| {
+                    (
+                        field.name.as_str(),
+                        field.ty.as_str(),
+                        field.args.as_slice(),
+                    )
+                }),
+            )?;
+        }
+
+        let query_type = registry.query_type.clone();
+        add_introspection_fields(&mut registry, &query_type);
+
+        if let Some(err) = registry.errors.first() {
+            return Err(SchemaError(err.clone()));
+        }
+        if let Some(err) = check_default_values(&registry) {
+            return Err(SchemaError(err));
+        }
+
+        Ok(Schema {
+            registry,
+            objects,
+            data,
+            introspection,
+            timer: Timer::default(),
+            timeout,
+        })
+    }
+}
+
+/// A GraphQL schema with types that are defined at runtime.
+///
+/// See the [module documentation](index.html).
+pub struct Schema {
+    registry: Registry,
+    objects: HashMap<String, Object>,
+    data: Data,
+    introspection: Introspection,
+    timer: Timer,
+    timeout: Option<Duration>,
+}
+
+impl Schema {
+    /// Start building a schema with the names of the root types, they must be registered as dynamic objects.
+    pub fn build(query: &str, mutation: Option<&str>) -> SchemaBuilder {
+        SchemaBuilder {
+            registry: create_registry(query.to_string(), mutation.map(ToString::to_string)),
+            types: Vec::new(),
+            data: Default::default(),
+            introspection: Introspection::Enabled,
+            timeout: None,
+        }
+    }
+
+    /// Returns the SDL of the schema.
+    pub fn sdl(&self) -> String {
+        self.registry.export_sdl(false)
+    }
+
+    /// Returns the registry of the types of the schema, it can be compared with another one by
+    /// `diff::diff_registry`.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Validate a client document against the schema without executing it, see `async_graphql::Schema::validate`.
+    pub fn validate(&self, sources: &[&str]) -> Result<()> {
+        check_sources(
+            &self.registry,
+            sources,
+            !matches!(self.introspection, Introspection::Disabled),
+        )
+    }
+
+    /// Start a query and return `QueryBuilder`.
+    pub fn query<'a>(&'a self, query_source: &'a str) -> QueryBuilder<'a> {
+        QueryBuilder {
+            schema: self,
+            options: QueryOptions {
+                registry: &self.registry,
+                query_source,
+                operation_name: None,
+                variables: None,
+                data: &self.data,
+                ctx_data: None,
+                introspection: &self.introspection,
+                timer: &self.timer,
+                timeout: self.timeout,
+            },
+        }
+    }
+
+    /// The root objects of the queries and of the mutations, they are boxed to be owned by the execution.
+    fn roots(&self) -> (Box<ObjectValue<'_>>, Option<Box<ObjectValue<'_>>>) {
+        let root = |root_type: &String| {
+            Box::new(ObjectValue {
+                schema: self,
+                object: &self.objects[root_type],
+                value: &FieldValue::NULL,
+            })
+        };
+        (
+            root(&self.registry.query_type),
+            self.registry.mutation_type.as_ref().map(root),
+        )
+    }
+
+    /// Resolve the `value` of the type `ty` with the selection set of its field.
+    fn resolve_value<'a>(
+        &'a self,
+        ctx: &'a ContextSelectionSet<'a>,
+        ty: &'a str,
+        value: &'a FieldValue,
+    ) -> BoxFuture<'a, Result<serde_json::Value>> {
+        Box::pin(async move {
+            let invalid_value = |message: &str| QueryError::InvalidFieldValue {
+                ty: ty.to_string(),
+                message: message.to_string(),
+            };
+
+            match TypeName::create(ty) {
+                TypeName::NonNull(inner_ty) => {
+                    if value.is_null() {
+                        return Err(invalid_value("it cannot be null").into());
+                    }
+                    self.resolve_value(ctx, inner_ty, value).await
+                }
+                TypeName::List(item_ty) => match value.inner() {
+                    FieldValueInner::Null => Ok(serde_json::Value::Null),
+                    FieldValueInner::List(items) => {
+                        let items = DynamicList {
+                            schema: self,
+                            item_ty,
+                            items,
+                        };
+                        resolve_list(ctx, &items).await
+                    }
+                    _ => Err(invalid_value("expected a list").into()),
+                },
+                TypeName::Named(type_name) => match value.inner() {
+                    FieldValueInner::Null => Ok(serde_json::Value::Null),
+                    FieldValueInner::List(_) => Err(invalid_value("unexpected list").into()),
+                    FieldValueInner::Output(value) => value.resolve(ctx).await,
+                    FieldValueInner::Value(value) => match self.registry.types.get(type_name) {
+                        Some(registry::Type::Scalar { .. }) | Some(registry::Type::Enum { .. }) => {
+                            Ok(value.clone())
+                        }
+                        _ => Err(invalid_value("expected an object").into()),
+                    },
+                    FieldValueInner::OwnedAny(_) => {
+                        let object_type = match value.type_name() {
+                            Some(object_type) => object_type,
+                            None if self.objects.contains_key(type_name) => type_name,
+                            None => {
+                                return Err(invalid_value(
+                                    "the object type of the value must be specified with `FieldValue::with_type`",
+                                )
+                                .into())
+                            }
+                        };
+                        match self.objects.get(object_type) {
+                            Some(object)
+                                if object.name == type_name
+                                    || object.implements.iter().any(|name| name == type_name) =>
+                            {
+                                let mut result = serde_json::Map::new();
+                                resolve_object(
+                                    ctx,
+                                    &ObjectValue {
+                                        schema: self,
+                                        object,
+                                        value,
+                                    },
+                                    &mut result,
+                                )
+                                .await?;
+                                Ok(result.into())
+                            }
+                            _ => Err(invalid_value(&format!(
+                                "\"{}\" is not one of its object types",
+                                object_type
+                            ))
+                            .into()),
+                        }
+                    }
+                    FieldValueInner::WithType { .. } => unreachable!(),
+                },
+            }
+        })
+    }
+}
+
+/// The value of a dynamic object.
+struct ObjectValue<'a> {
+    schema: &'a Schema,
+    object: &'a Object,
+    value: &'a FieldValue,
+}
+
+#[async_trait::async_trait]
+impl<'a> ResolveObject for ObjectValue<'a> {
+    fn type_name(&self) -> Cow<'_, str> {
+        Cow::Borrowed(&self.object.name)
+    }
+
+    async fn resolve_field(&self, ctx: &Context<'_>, field: &Field) -> Result<serde_json::Value> {
+        if self.object.name == self.schema.registry.query_type {
+            if let Some(value) = resolve_introspection_field(ctx, field).await? {
+                return Ok(value);
+            }
+        }
+
+        let object_field = match self.object.field_by_name(&field.name) {
+            Some(object_field) => object_field,
+            None => anyhow::bail!(QueryError::FieldNotFound {
+                field_name: field.name.clone(),
+                object: self.object.name.clone(),
+            }),
+        };
+        let value = (object_field.resolver)(ResolverContext {
+            ctx,
+            parent_value: self.value,
+            field: object_field,
+        })
+        .await?;
+        let ctx_obj = ctx.with_item(&field.selection_set);
+        self.schema
+            .resolve_value(&ctx_obj, &object_field.ty, &value)
+            .await
+    }
+
+    async fn resolve_inline_fragment(
+        &self,
+        name: &str,
+        ctx: &ContextSelectionSet<'_>,
+        result: &mut serde_json::Map<String, serde_json::Value>,
+    ) -> Result<()> {
+        if name == self.object.name || self.object.implements.iter().any(|i| i == name) {
+            resolve_object(ctx, self, result).await?;
+        }
+        Ok(())
+    }
+}
+
+/// The items of a list value of a dynamic schema.
+struct DynamicList<'a> {
+    schema: &'a Schema,
+    item_ty: &'a str,
+    items: &'a [FieldValue],
+}
+
+impl<'a> ListItems for DynamicList<'a> {
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    fn resolve_item<'b>(
+        &'b self,
+        ctx: &'b ContextSelectionSet<'b>,
+        idx: usize,
+    ) -> BoxFuture<'b, Result<serde_json::Value>> {
+        self.schema
+            .resolve_value(ctx, self.item_ty, &self.items[idx])
+    }
+}
+
+/// Query builder of a dynamic schema
+///
+/// The queries are executed like the ones of the statically typed schemas, see `async_graphql::QueryBuilder`.
+pub struct QueryBuilder<'a> {
+    schema: &'a Schema,
+    options: QueryOptions<'a>,
+}
+
+impl<'a> QueryBuilder<'a> {
+    /// Specify the operation name.
+    pub fn operator_name(mut self, name: &'a str) -> Self {
+        self.options.operation_name = Some(name);
+        self
+    }
+
+    /// Specify the variables.
+    pub fn variables(mut self, vars: &'a Variables) -> Self {
+        self.options.variables = Some(vars);
+        self
+    }
+
+    /// Add a data of the current request that can be accessed in the `Context`.
+    ///
+    /// It takes precedence over the global data of the same type defined in the `SchemaBuilder`.
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.options.add_data(data);
+        self
+    }
+
+    /// Set the maximum duration of the execution of this query, it takes precedence over `SchemaBuilder::timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Execute the query with incremental delivery, see `async_graphql::QueryBuilder::execute_incremental`.
+    pub async fn execute_incremental(self) -> Result<IncrementalResponse<'a>> {
+        let (query, mutation) = self.schema.roots();
+        self.options.execute_incremental(query, mutation).await
+    }
+
+    /// Execute the query.
+    pub async fn execute(self) -> Result<serde_json::Value> {
+        self.execute_response().await.map(|response| response.data)
+    }
+
+    /// Execute the query, and return the data with the cache control hints of the resolved fields.
+    pub async fn execute_response(self) -> Result<QueryResponse> {
+        let (query, mutation) = self.schema.roots();
+        self.options.execute_response(query, mutation).await
+    }
+}
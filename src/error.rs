@@ -7,6 +7,16 @@ use std::fmt::{Debug, Display, Formatter};
 #[error("{0}")]
 pub struct QueryParseError(pub(crate) String);
 
+/// An error that occurs when building a schema, the types or the default values are invalid.
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct SchemaError(pub(crate) String);
+
+/// An error that occurs when parsing an SDL document.
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct SchemaParseError(pub(crate) String);
+
 #[derive(Debug, Error)]
 pub enum QueryError {
     #[error("Not supported.")]
@@ -33,6 +43,9 @@ pub enum QueryError {
     #[error("Schema is not configured for mutations.")]
     NotConfiguredMutations,
 
+    #[error("Invalid value for argument \"{path}\", {message}")]
+    InvalidArgumentValue { path: String, message: String },
+
     #[error("Invalid value for enum \"{ty}\".")]
     InvalidEnumValue { ty: String, value: String },
 
@@ -65,6 +78,240 @@ pub enum QueryError {
 
     #[error("Unrecognized inline fragment \"{name}\" on type \"{object}\"")]
     UnrecognizedInlineFragment { object: String, name: String },
+
+    #[error("The execution of the query timed out.")]
+    Timeout,
+
+    #[error("The entity representation must have a \"__typename\".")]
+    TypeNameNotExists,
+
+    #[error("No entity resolver matches the representation of type \"{type_name}\".")]
+    EntityNotFound { type_name: String },
+
+    #[error("Invalid value for type \"{ty}\", {message}.")]
+    InvalidFieldValue { ty: String, message: String },
+}
+
+/// The result of coercing an input value.
+pub type InputValueResult<T> = std::result::Result<T, InputValueError>;
+
+/// An error that occurs when coercing an input value, it is returned by `GQLInputValue::parse` and
+/// `GQLScalar::parse`.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// let err = InputValueError::expected_type("Int", &Value::Boolean(true))
+///     .with_field("age")
+///     .with_index(2)
+///     .with_field("users");
+/// assert_eq!(err.message, "expected type \"Int\", found true");
+/// assert_eq!(err.path, vec![serde_json::json!("users"), 2.into(), "age".into()]);
+/// assert_eq!(err.path_to_string("input"), "input.users[2].age");
+/// ```
+#[derive(Debug, Error, Clone, PartialEq)]
+#[error("{message}")]
+pub struct InputValueError {
+    /// The path of the invalid value in the input value, made of the input object field names and list indices.
+    pub path: Vec<serde_json::Value>,
+    /// The reason why the value is invalid.
+    pub message: String,
+}
+
+impl InputValueError {
+    /// Create an error with a custom message.
+    pub fn custom<T: Display>(message: T) -> Self {
+        Self {
+            path: Vec::new(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Create an error for a value that is not of the expected type.
+    pub fn expected_type<T: Display>(expect: T, actual: &Value) -> Self {
+        Self::custom(format!("expected type \"{}\", found {}", expect, actual))
+    }
+
+    /// Prepend an input object field name to the path.
+    pub fn with_field(mut self, name: &str) -> Self {
+        self.path.insert(0, name.into());
+        self
+    }
+
+    /// Prepend a list index to the path.
+    pub fn with_index(mut self, idx: usize) -> Self {
+        self.path.insert(0, idx.into());
+        self
+    }
+
+    /// Format the path starting with `root`, for example `input.users[2].age`.
+    pub fn path_to_string(&self, root: &str) -> String {
+        let mut s = root.to_string();
+        for segment in &self.path {
+            match segment {
+                serde_json::Value::String(name) => {
+                    s.push('.');
+                    s.push_str(name);
+                }
+                segment => s.push_str(&format!("[{}]", segment)),
+            }
+        }
+        s
+    }
+
+    #[doc(hidden)]
+    pub fn into_argument_error(self, arg_name: &str) -> QueryError {
+        QueryError::InvalidArgumentValue {
+            path: self.path_to_string(arg_name),
+            message: self.message,
+        }
+    }
+}
+
+/// An error that is sent to the client with a message and optional extensions.
+///
+/// Only `message` and `extensions` are serialized in the response, so converting domain errors into a `FieldError`
+/// is how you control what leaks to clients.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// let err = FieldError::new("Unauthorized").extension("code", "UNAUTHENTICATED");
+/// assert_eq!(err.message, "Unauthorized");
+/// assert_eq!(err.extensions.unwrap()["code"], "UNAUTHENTICATED");
+/// ```
+#[derive(Debug, Error, Clone, PartialEq)]
+#[error("{message}")]
+pub struct FieldError {
+    pub message: String,
+    pub extensions: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl FieldError {
+    /// Create a `FieldError` with a message and no extensions.
+    pub fn new<T: Into<String>>(message: T) -> Self {
+        Self {
+            message: message.into(),
+            extensions: None,
+        }
+    }
+
+    /// Add an extension entry, replacing any existing entry with the same name.
+    pub fn extension<K: Into<String>, V: Into<serde_json::Value>>(
+        mut self,
+        name: K,
+        value: V,
+    ) -> Self {
+        self.extensions
+            .get_or_insert_with(Default::default)
+            .insert(name.into(), value.into());
+        self
+    }
+}
+
+/// Converts an error into a `FieldError`.
+///
+/// Implement this trait for your own error types to decide which message and extensions are sent to the client.
+/// It is also implemented for every reference to a type that implements `Display`, in which case the message is
+/// the display string and there are no extensions.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// #[derive(Debug)]
+/// enum MyError {
+///     NotFound,
+///     Database(String),
+/// }
+///
+/// impl ErrorExtensions for MyError {
+///     fn extend(&self) -> FieldError {
+///         match self {
+///             MyError::NotFound => FieldError::new("Not found").extension("code", "NOT_FOUND"),
+///             // Do not leak the database error to the client
+///             MyError::Database(_) => FieldError::new("Internal error").extension("code", "INTERNAL"),
+///         }
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     #[field]
+///     async fn value(&self) -> Result<i32> {
+///         let res: std::result::Result<i32, MyError> = Err(MyError::Database("connection refused".to_string()));
+///         Ok(res.extend()?)
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+///     let err = schema.query("{ value }").execute().await.unwrap_err();
+///     assert_eq!(err.to_string(), "Internal error");
+/// }
+/// ```
+pub trait ErrorExtensions: Sized {
+    /// Convert the error to a `FieldError`.
+    fn extend(&self) -> FieldError;
+
+    /// Convert the error to a `FieldError` and merge the object returned by `f` into its extensions.
+    fn extend_with<F: FnOnce(&Self) -> serde_json::Value>(self, f: F) -> FieldError {
+        let mut err = self.extend();
+        if let serde_json::Value::Object(obj) = f(&self) {
+            err.extensions
+                .get_or_insert_with(Default::default)
+                .extend(obj);
+        }
+        err
+    }
+}
+
+impl ErrorExtensions for FieldError {
+    fn extend(&self) -> FieldError {
+        self.clone()
+    }
+}
+
+impl ErrorExtensions for Error {
+    fn extend(&self) -> FieldError {
+        match self.downcast_ref::<FieldError>() {
+            Some(err) => err.clone(),
+            None => FieldError::new(self.to_string()),
+        }
+    }
+}
+
+impl<E: Display> ErrorExtensions for &E {
+    fn extend(&self) -> FieldError {
+        FieldError::new(self.to_string())
+    }
+}
+
+/// Extends the error of a `Result` with `ErrorExtensions`.
+pub trait ResultExt<T, E> {
+    /// Convert the error to a `FieldError`.
+    fn extend(self) -> crate::Result<T>;
+
+    /// Convert the error to a `FieldError` and merge the object returned by `f` into its extensions.
+    fn extend_err<F: FnOnce(&E) -> serde_json::Value>(self, f: F) -> crate::Result<T>;
+}
+
+impl<T, E: ErrorExtensions> ResultExt<T, E> for std::result::Result<T, E> {
+    fn extend(self) -> crate::Result<T> {
+        self.map_err(|err| err.extend().into())
+    }
+
+    fn extend_err<F: FnOnce(&E) -> serde_json::Value>(self, f: F) -> crate::Result<T> {
+        self.map_err(|err| err.extend_with(f).into())
+    }
 }
 
 pub trait ErrorWithPosition {
@@ -76,9 +323,14 @@ impl<T: Into<Error>> ErrorWithPosition for T {
     type Result = PositionError;
 
     fn with_position(self, position: Pos) -> PositionError {
-        PositionError {
-            position,
-            inner: self.into(),
+        // Keep the innermost position if the error has already been positioned.
+        match self.into().downcast::<PositionError>() {
+            Ok(err) => err,
+            Err(inner) => PositionError {
+                position,
+                path: None,
+                inner,
+            },
         }
     }
 }
@@ -86,12 +338,26 @@ impl<T: Into<Error>> ErrorWithPosition for T {
 #[derive(Debug, Error)]
 pub struct PositionError {
     pub position: Pos,
+    /// The response path of the field that produced the error, e.g. `["hero", "friends", 2, "name"]`.
+    pub path: Option<serde_json::Value>,
     pub inner: Error,
 }
 
 impl PositionError {
     pub fn new(position: Pos, inner: Error) -> Self {
-        Self { position, inner }
+        Self {
+            position,
+            path: None,
+            inner,
+        }
+    }
+
+    /// Set the response path, unless the error already has one.
+    pub fn with_path(mut self, path: serde_json::Value) -> Self {
+        if self.path.is_none() {
+            self.path = Some(path);
+        }
+        self
     }
 
     pub fn into_inner(self) -> Error {
@@ -105,14 +371,24 @@ impl Display for PositionError {
     }
 }
 
+/// An error of a validation rule.
 #[derive(Debug)]
 pub struct RuleError {
+    /// The positions of the nodes that cause the error, in the source where they are defined.
     pub locations: Vec<Pos>,
+
+    /// The index of the source where the error is, when a document made of several sources is validated by
+    /// `Schema::validate`, otherwise 0.
+    pub source: usize,
+
+    /// The error message.
     pub message: String,
 }
 
+/// All the errors of the validation rules of a query.
 #[derive(Debug, Error)]
 pub struct RuleErrors {
+    /// The errors.
     pub errors: Vec<RuleError>,
 }
 
@@ -0,0 +1,99 @@
+//! Field guards
+
+use crate::{Context, Result};
+
+/// Field guard
+///
+/// Guards are checked before the field is resolved, and the field is not resolved if the check fails.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::guard::Guard;
+///
+/// #[derive(Eq, PartialEq, Copy, Clone)]
+/// enum Role {
+///     Admin,
+///     Guest,
+/// }
+///
+/// struct RoleGuard {
+///     role: Role,
+/// }
+///
+/// #[async_trait::async_trait]
+/// impl Guard for RoleGuard {
+///     async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+///         if ctx.data_opt::<Role>() == Some(&self.role) {
+///             Ok(())
+///         } else {
+///             Err(FieldError::new("Forbidden").extension("code", "FORBIDDEN").into())
+///         }
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     #[field(guard(RoleGuard(role = "Role::Admin")))]
+///     async fn value(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+///     assert!(schema.query("{ value }").data(Role::Guest).execute().await.is_err());
+///     assert_eq!(
+///         schema.query("{ value }").data(Role::Admin).execute().await.unwrap(),
+///         serde_json::json!({ "value": 10 })
+///     );
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait Guard {
+    /// Check whether the field can be resolved, return an error to deny it.
+    async fn check(&self, ctx: &Context<'_>) -> Result<()>;
+}
+
+/// An extension trait for `Guard`
+pub trait GuardExt: Guard + Sized {
+    /// Merge the two guards, both must pass.
+    fn and<R: Guard>(self, other: R) -> And<Self, R> {
+        And(self, other)
+    }
+
+    /// Merge the two guards, either one must pass.
+    fn or<R: Guard>(self, other: R) -> Or<Self, R> {
+        Or(self, other)
+    }
+}
+
+impl<T: Guard> GuardExt for T {}
+
+/// Guard for `GuardExt::and`
+pub struct And<A: Guard, B: Guard>(A, B);
+
+#[async_trait::async_trait]
+impl<A: Guard + Send + Sync, B: Guard + Send + Sync> Guard for And<A, B> {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        self.0.check(ctx).await?;
+        self.1.check(ctx).await
+    }
+}
+
+/// Guard for `GuardExt::or`
+pub struct Or<A: Guard, B: Guard>(A, B);
+
+#[async_trait::async_trait]
+impl<A: Guard + Send + Sync, B: Guard + Send + Sync> Guard for Or<A, B> {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        if self.0.check(ctx).await.is_ok() {
+            return Ok(());
+        }
+        self.1.check(ctx).await
+    }
+}
@@ -5,7 +5,11 @@ pub use graphiql_source::graphiql_source;
 pub use playground_source::playground_source;
 
 use crate::error::{RuleError, RuleErrors};
-use crate::{GQLObject, PositionError, Result, Schema, Variables};
+use crate::{
+    CacheControl, ErrorExtensions, GQLObject, IncrementalPatch, IncrementalResponse, PositionError,
+    QueryResponse, Result, Schema, Variables,
+};
+use futures::stream::{self, BoxStream, StreamExt};
 use graphql_parser::Pos;
 use serde::ser::{SerializeMap, SerializeSeq};
 use serde::{Serialize, Serializer};
@@ -41,11 +45,26 @@ impl GQLRequest {
             Some(operation_name) => query.operator_name(operation_name),
             None => query,
         };
-        GQLResponse(query.execute().await)
+        GQLResponse(query.execute_response().await)
     }
 }
 
-pub struct GQLResponse(Result<serde_json::Value>);
+pub struct GQLResponse(Result<QueryResponse>);
+
+impl GQLResponse {
+    /// Get the cache control hints of the response, there are no hints if the query failed.
+    pub fn cache_control(&self) -> CacheControl {
+        match &self.0 {
+            Ok(res) => res.cache_control,
+            Err(_) => CacheControl::default(),
+        }
+    }
+
+    /// Get the value of the `Cache-Control` HTTP header of the response, `None` if it should not be cached.
+    pub fn cache_control_header(&self) -> Option<String> {
+        self.cache_control().value()
+    }
+}
 
 impl Serialize for GQLResponse {
     fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
@@ -53,7 +72,7 @@ impl Serialize for GQLResponse {
             Ok(res) => {
                 let mut map = serializer.serialize_map(None)?;
                 map.serialize_key("data")?;
-                map.serialize_value(&res)?;
+                map.serialize_value(&res.data)?;
                 map.end()
             }
             Err(err) => {
@@ -66,6 +85,62 @@ impl Serialize for GQLResponse {
     }
 }
 
+/// The content type of the body returned by `multipart_stream`.
+pub const MULTIPART_CONTENT_TYPE: &str = "multipart/mixed; boundary=\"-\"";
+
+/// Encode the payloads of an incremental response as the parts of a `multipart/mixed` body.
+///
+/// Each part is a JSON object with the `data` of the payload, the `path`, `label` and `errors` of the patches, and
+/// `hasNext`.
+pub fn multipart_stream(response: Result<IncrementalResponse<'_>>) -> BoxStream<'_, String> {
+    let (initial, patches) = match response {
+        Ok(response) => (
+            serde_json::json!({ "data": response.data, "hasNext": response.has_next }),
+            response.patches,
+        ),
+        Err(err) => (
+            serde_json::json!({ "errors": GQLError(&err), "hasNext": false }),
+            stream::empty().boxed(),
+        ),
+    };
+    stream::once(async move { encode_part(&initial) })
+        .chain(patches.map(|patch| encode_part(&patch_to_json(patch))))
+        .chain(stream::once(async { "\r\n-----\r\n".to_string() }))
+        .boxed()
+}
+
+fn patch_to_json(patch: IncrementalPatch) -> serde_json::Value {
+    let IncrementalPatch {
+        label,
+        path,
+        data,
+        error,
+        has_next,
+    } = patch;
+    if data.is_none() && error.is_none() {
+        return serde_json::json!({ "hasNext": has_next });
+    }
+
+    let mut obj = serde_json::Map::new();
+    if let Some(label) = label {
+        obj.insert("label".to_string(), label.into());
+    }
+    obj.insert("path".to_string(), path);
+    obj.insert("data".to_string(), data.unwrap_or_default());
+    if let Some(err) = error {
+        obj.insert("errors".to_string(), serde_json::json!(GQLError(&err)));
+    }
+    obj.insert("hasNext".to_string(), has_next.into());
+    obj.into()
+}
+
+fn encode_part(value: &serde_json::Value) -> String {
+    format!(
+        "\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n{}",
+        value
+    )
+}
+
 struct GQLError<'a>(&'a anyhow::Error);
 
 impl<'a> Deref for GQLError<'a> {
@@ -93,9 +168,7 @@ impl<'a> Serialize for GQLError<'a> {
             seq.end()
         } else {
             let mut seq = serializer.serialize_seq(None)?;
-            seq.serialize_element(&serde_json::json!({
-                "message": self.0.to_string(),
-            }))?;
+            seq.serialize_element(&GQLFieldError(self.0, None, None))?;
             seq.end()
         }
     }
@@ -122,12 +195,34 @@ impl<'a> Serialize for GQLPositionError<'a> {
     where
         S: Serializer,
     {
+        GQLFieldError(&self.0.inner, Some(&self.0.position), self.0.path.as_ref())
+            .serialize(serializer)
+    }
+}
+
+struct GQLFieldError<'a>(
+    &'a anyhow::Error,
+    Option<&'a Pos>,
+    Option<&'a serde_json::Value>,
+);
+
+impl<'a> Serialize for GQLFieldError<'a> {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let err = self.0.extend();
         let mut map = serializer.serialize_map(None)?;
-        map.serialize_entry("message", &self.0.inner.to_string())?;
-        map.serialize_entry(
-            "locations",
-            std::slice::from_ref(&GQLErrorPos(&self.0.position)),
-        )?;
+        map.serialize_entry("message", &err.message)?;
+        if let Some(pos) = self.1 {
+            map.serialize_entry("locations", std::slice::from_ref(&GQLErrorPos(pos)))?;
+        }
+        if let Some(path) = self.2 {
+            map.serialize_entry("path", path)?;
+        }
+        if let Some(extensions) = &err.extensions {
+            map.serialize_entry("extensions", extensions)?;
+        }
         map.end()
     }
 }
@@ -157,7 +252,7 @@ impl<'a> Serialize for GQLRuleError<'a> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::ErrorWithPosition;
+    use crate::{ErrorWithPosition, FieldError};
     use graphql_parser::Pos;
     use serde_json::json;
 
@@ -209,7 +304,10 @@ mod tests {
 
     #[test]
     fn test_response_data() {
-        let resp = GQLResponse(Ok(json!({"ok": true})));
+        let resp = GQLResponse(Ok(QueryResponse {
+            data: json!({"ok": true}),
+            cache_control: Default::default(),
+        }));
         assert_eq!(
             serde_json::to_value(resp).unwrap(),
             json! ({
@@ -233,6 +331,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_response_error_with_extensions() {
+        let resp = GQLResponse(Err(FieldError::new("error")
+            .extension("code", "UNAUTHENTICATED")
+            .with_position(Pos {
+                line: 10,
+                column: 20,
+            })
+            .into()));
+        assert_eq!(
+            serde_json::to_value(resp).unwrap(),
+            json!({
+                "errors": [{
+                    "message":"error",
+                    "locations": [
+                        {"line": 10, "column": 20}
+                    ],
+                    "extensions": {
+                        "code": "UNAUTHENTICATED"
+                    }
+                }]
+            })
+        );
+    }
+
     #[test]
     fn test_response_error_with_pos() {
         let resp = GQLResponse(Err(anyhow::anyhow!("error")
@@ -0,0 +1,299 @@
+use crate::context::Data;
+use crate::registry::Registry;
+use crate::resolver::{resolve_object, ResolveObject};
+use crate::timer::{with_delay, Delay, Timer};
+use crate::{ContextBase, Error, Result, Variables};
+use futures::channel::{mpsc, oneshot};
+use futures::future::{self, BoxFuture, Either};
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use graphql_parser::query::{Definition, Document, OperationDefinition};
+use std::collections::HashMap;
+use std::future::Future;
+use std::ops::Deref;
+use std::pin::Pin;
+use std::sync::Mutex;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+/// The response of an incremental execution, returned by `QueryBuilder::execute_incremental`.
+pub struct IncrementalResponse<'a> {
+    /// The data of the initial payload, without the deferred fragments and the streamed list items.
+    pub data: serde_json::Value,
+
+    /// Whether the initial payload is followed by subsequent payloads.
+    pub has_next: bool,
+
+    /// The subsequent payloads.
+    pub patches: BoxStream<'a, IncrementalPatch>,
+}
+
+/// A subsequent payload of an incremental response.
+#[derive(Debug)]
+pub struct IncrementalPatch {
+    /// The label of the `@defer` or `@stream` directive.
+    pub label: Option<String>,
+
+    /// The path of the data in the response.
+    pub path: serde_json::Value,
+
+    /// The fields of a deferred fragment or a streamed list item, `None` if it failed, or in the last payload when the
+    /// deferred fragments had no fields.
+    pub data: Option<serde_json::Value>,
+
+    /// The error of a deferred fragment or a streamed list item that failed, e.g. when it times out.
+    pub error: Option<Error>,
+
+    /// Whether more payloads follow this one.
+    pub has_next: bool,
+}
+
+/// Sends the patches of an incremental execution to the stream of its response.
+pub(crate) struct IncrementalPatches {
+    sender: mpsc::UnboundedSender<IncrementalPatch>,
+    delay: Option<Delay>,
+}
+
+impl IncrementalPatches {
+    /// Resolve a deferred fragment or a streamed list item, it times out before the rest of the operation so the
+    /// initial payload is still returned.
+    pub(crate) async fn resolve<T, F: Future<Output = Result<T>>>(&self, fut: F) -> Result<T> {
+        with_delay(self.delay.clone(), fut).await
+    }
+
+    /// Send the patch of a deferred fragment or a streamed list item at `path`, an error patch if it failed.
+    pub(crate) fn send(
+        &self,
+        label: Option<String>,
+        path: serde_json::Value,
+        res: Result<serde_json::Value>,
+    ) {
+        let (data, error) = match res {
+            Ok(data) => (Some(data), None),
+            Err(err) => (None, Some(err)),
+        };
+        // The receiver is only dropped with the execution.
+        let _ = self.sender.unbounded_send(IncrementalPatch {
+            label,
+            path,
+            data,
+            error,
+            has_next: true,
+        });
+    }
+}
+
+/// Where a value sends its initial value, without its deferred parts, before they are resolved.
+///
+/// The resolution of the value waits after sending it until its parent resumes it, so the patches of the deferred
+/// parts follow the initial value of the parent.
+pub(crate) struct InitialValue {
+    sender: Mutex<Option<oneshot::Sender<serde_json::Value>>>,
+    resume: Mutex<Option<oneshot::Receiver<()>>>,
+}
+
+impl InitialValue {
+    /// Send the initial value, and wait until the resolution is resumed.
+    pub(crate) async fn send(&self, value: serde_json::Value) {
+        if let Some(sender) = self.sender.lock().unwrap().take() {
+            let _ = sender.send(value);
+        }
+        let resume = self.resume.lock().unwrap().take();
+        if let Some(resume) = resume {
+            let _ = resume.await;
+        }
+    }
+}
+
+/// The rest of the resolution of a value after its initial value is sent.
+pub(crate) struct Tail<'a> {
+    resume: oneshot::Sender<()>,
+    fut: BoxFuture<'a, Result<serde_json::Value>>,
+}
+
+impl<'a> Tail<'a> {
+    /// Resume the resolution of the deferred parts of the value.
+    pub(crate) fn resolve(self) -> BoxFuture<'a, ()> {
+        Box::pin(async move {
+            let _ = self.resume.send(());
+            // The errors of the deferred parts are delivered by their patches.
+            let _ = self.fut.await;
+        })
+    }
+}
+
+/// Resolve the future created by `resolve` until the initial value is known, which is either its output, or the value
+/// sent to its `InitialValue` if it has deferred parts, then the tail resolves them.
+pub(crate) async fn resolve_initial<'a, F>(
+    resolve: F,
+) -> Result<(serde_json::Value, Option<Tail<'a>>)>
+where
+    F: FnOnce(InitialValue) -> BoxFuture<'a, Result<serde_json::Value>>,
+{
+    let (sender, receiver) = oneshot::channel();
+    let (resume, resume_receiver) = oneshot::channel();
+    let fut = resolve(InitialValue {
+        sender: Mutex::new(Some(sender)),
+        resume: Mutex::new(Some(resume_receiver)),
+    });
+    match future::select(fut, receiver).await {
+        Either::Left((res, _)) => Ok((res?, None)),
+        Either::Right((Ok(value), fut)) => Ok((value, Some(Tail { resume, fut }))),
+        Either::Right((Err(_), fut)) => Ok((fut.await?, None)),
+    }
+}
+
+/// The patches of an incremental response, the tail of the execution is resolved while they are consumed.
+///
+/// A patch is held back until the next one is sent or the execution is completed, to know whether it is the last one.
+struct Patches<'a> {
+    tail: Option<BoxFuture<'a, ()>>,
+    receiver: mpsc::UnboundedReceiver<IncrementalPatch>,
+    next: Option<IncrementalPatch>,
+    sent: bool,
+}
+
+impl<'a> Stream for Patches<'a> {
+    type Item = IncrementalPatch;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if let Some(tail) = &mut this.tail {
+            if tail.as_mut().poll(cx).is_ready() {
+                // The sender of the patches is dropped with the execution.
+                this.tail = None;
+            }
+        }
+
+        loop {
+            match this.receiver.poll_next_unpin(cx) {
+                Poll::Ready(Some(patch)) => {
+                    if let Some(patch) = this.next.replace(patch) {
+                        this.sent = true;
+                        return Poll::Ready(Some(patch));
+                    }
+                }
+                Poll::Ready(None) => {
+                    let patch = match this.next.take() {
+                        Some(patch) => Some(patch),
+                        // The deferred parts had no fields, the last payload only tells that nothing follows.
+                        None if !this.sent => Some(IncrementalPatch {
+                            label: None,
+                            path: serde_json::Value::Array(Vec::new()),
+                            data: None,
+                            error: None,
+                            has_next: true,
+                        }),
+                        None => None,
+                    };
+                    this.sent = true;
+                    return Poll::Ready(patch.map(|patch| IncrementalPatch {
+                        has_next: false,
+                        ..patch
+                    }));
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// The execution of a query operation with `@defer` and `@stream`.
+pub(crate) struct IncrementalExecution<'a, Query> {
+    pub(crate) query: Query,
+    pub(crate) registry: &'a Registry,
+    pub(crate) data: &'a Data,
+    pub(crate) ctx_data: Option<Data>,
+    pub(crate) variables: Option<Variables>,
+    pub(crate) document: Document,
+    pub(crate) operation: usize,
+    pub(crate) timer: &'a Timer,
+    pub(crate) deadline: Option<Instant>,
+}
+
+impl<'a, Query> IncrementalExecution<'a, Query>
+where
+    Query: Deref + Send + Sync + 'a,
+    Query::Target: ResolveObject,
+{
+    pub(crate) async fn execute(self) -> Result<IncrementalResponse<'a>> {
+        let IncrementalExecution {
+            query,
+            registry,
+            data,
+            ctx_data,
+            variables,
+            document,
+            operation,
+            timer,
+            deadline,
+        } = self;
+        let delay = deadline.map(|deadline| timer.delay(deadline));
+        let (sender, receiver) = mpsc::unbounded();
+        let patches = IncrementalPatches {
+            sender,
+            delay: delay.clone(),
+        };
+
+        let (data, tail) = with_delay(
+            delay,
+            resolve_initial(move |initial| {
+                Box::pin(async move {
+                    let (selection_set, variable_definitions) =
+                        match &document.definitions[operation] {
+                            Definition::Operation(OperationDefinition::Query(query)) => {
+                                (&query.selection_set, Some(&query.variable_definitions[..]))
+                            }
+                            Definition::Operation(OperationDefinition::SelectionSet(
+                                selection_set,
+                            )) => (selection_set, None),
+                            _ => unreachable!(),
+                        };
+                    let fragments = document
+                        .definitions
+                        .iter()
+                        .filter_map(|definition| match definition {
+                            Definition::Fragment(fragment) => {
+                                Some((fragment.name.clone(), fragment))
+                            }
+                            _ => None,
+                        })
+                        .collect::<HashMap<_, _>>();
+                    let cache_control = Mutex::new(None);
+                    let ctx = ContextBase {
+                        path_node: None,
+                        item: selection_set,
+                        variables: variables.as_ref(),
+                        variable_definitions,
+                        registry,
+                        data,
+                        ctx_data: ctx_data.as_ref(),
+                        fragments: &fragments,
+                        incremental: Some(&patches),
+                        initial: Some(&initial),
+                        stream: None,
+                        cache_control: &cache_control,
+                    };
+                    let mut data = serde_json::Map::new();
+                    resolve_object(&ctx, &*query, &mut data).await?;
+                    Ok(data.into())
+                })
+            }),
+        )
+        .await?;
+
+        Ok(IncrementalResponse {
+            data,
+            has_next: tail.is_some(),
+            patches: match tail {
+                Some(tail) => Patches {
+                    tail: Some(tail.resolve()),
+                    receiver,
+                    next: None,
+                    sent: false,
+                }
+                .boxed(),
+                None => stream::empty().boxed(),
+            },
+        })
+    }
+}
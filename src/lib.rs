@@ -34,6 +34,7 @@
 //! * Custom scalar.
 //! * Minimal overhead.
 //! * Easy integration (hyper, actix_web, tide ...).
+//! * Optional [tracing](https://crates.io/crates/tracing) instrumentation (`tracing` feature).
 //!
 //! ## License
 //!
@@ -54,12 +55,16 @@ extern crate thiserror;
 extern crate serde_derive;
 
 mod base;
+mod cache_control;
 mod context;
 mod error;
+mod incremental;
+mod look_ahead;
 mod model;
 mod resolver;
 mod scalars;
 mod schema;
+mod timer;
 mod types;
 mod validation;
 
@@ -72,15 +77,28 @@ pub use graphql_parser;
 #[doc(hidden)]
 pub use serde_json;
 
+pub mod diff;
+pub mod dynamic;
+pub mod guard;
 pub mod http;
+pub mod validators;
 
 pub use base::GQLScalar;
-pub use context::{Context, Variables};
-pub use error::{ErrorWithPosition, PositionError, QueryError, QueryParseError};
+pub use cache_control::CacheControl;
+pub use context::{Context, Data, QueryPathNode, QueryPathSegment, Variables};
+pub use error::{
+    ErrorExtensions, ErrorWithPosition, FieldError, InputValueError, InputValueResult,
+    PositionError, QueryError, QueryParseError, ResultExt, RuleError, RuleErrors, SchemaError,
+    SchemaParseError,
+};
 pub use graphql_parser::query::Value;
-pub use scalars::ID;
-pub use schema::{QueryBuilder, Schema};
-pub use types::GQLEmptyMutation;
+pub use incremental::{IncrementalPatch, IncrementalResponse};
+pub use look_ahead::{Lookahead, SelectionField};
+#[cfg(feature = "base64")]
+pub use scalars::Base64;
+pub use scalars::{BigInt, Int64, Json, ID};
+pub use schema::{QueryBuilder, QueryResponse, Schema};
+pub use types::{GQLEmptyMutation, MaybeUndefined};
 
 pub type Result<T> = anyhow::Result<T>;
 pub type Error = anyhow::Error;
@@ -107,6 +125,27 @@ pub use types::{GQLEnum, GQLEnumItem};
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Object name               | string   | Y        |
 /// | desc        | Object description        | string   | Y        |
+/// | extends     | Add `extend` to the type in the federation SDL | bool | Y |
+/// | cache_control | Cache control hints of the fields of the object, `cache_control(max_age = 60, public)` | [CacheControl] | Y |
+/// | concretes   | Register a concrete type of a generic object with a name, `concretes(name = "UserPage", params(User))`, it can be repeated | ConcreteType | Y |
+///
+/// The name of a generic object without `concretes` is prefixed by the names of its type parameters, for example
+/// `Page<User>` is named `UserPage`, `Page<Vec<User>>` is named `UserListPage` and `Page<Option<User>>` is named
+/// `UserNullablePage`. A generic object cannot have a `name`:
+///
+/// ```compile_fail
+/// use async_graphql::*;
+///
+/// struct Page<T>(Vec<T>);
+///
+/// #[Object(name = "Page")]
+/// impl<T: GQLOutputValue + Send + Sync> Page<T> {
+///     #[field]
+///     async fn items(&self) -> &Vec<T> {
+///         &self.0
+///     }
+/// }
+/// ```
 ///
 /// # Field parameters
 ///
@@ -115,6 +154,11 @@ pub use types::{GQLEnum, GQLEnumItem};
 /// | name        | Field name                | string   | Y        |
 /// | desc        | Field description         | string   | Y        |
 /// | deprecation | Field deprecation reason  | string   | Y        |
+/// | guard       | Field guards              | [Guard]  | Y        |
+/// | external    | Mark the field as owned by another service, `@external` | bool | Y |
+/// | requires    | Fields of the entity required to resolve the field, `@requires` | string | Y |
+/// | provides    | Fields of the returned entity provided by this service, `@provides` | string | Y |
+/// | cache_control | Cache control hints of the field, `cache_control(max_age = 60, private)` | [CacheControl] | Y |
 ///
 /// # Field argument parameters
 ///
@@ -122,7 +166,30 @@ pub use types::{GQLEnum, GQLEnumItem};
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Argument name             | string   | Y        |
 /// | desc        | Argument description      | string   | Y        |
-/// | default     | Argument default value    | string   | Y        |
+/// | default     | Use `Default::default` as the default value | none | Y |
+/// | default     | Argument default value, a GraphQL literal | string | Y |
+/// | default_with | Expression to generate the default value | code string | Y |
+/// | validator   | Argument validators       | [InputValueValidator] | Y |
+///
+/// # Entity resolvers
+///
+/// A method with the `#[entity]` attribute resolves the entities of the federation: its arguments are the fields of
+/// the `@key` of the returned object, and it is called by `_entities` for the representations with the same
+/// `__typename` and keys. See `Schema::enable_federation`.
+///
+/// ```ignore
+/// #[Object]
+/// impl Query {
+///     #[entity]
+///     async fn find_user_by_id(&self, id: ID) -> User { ... }
+/// }
+/// ```
+///
+/// # Guards
+///
+/// `guard(A(..), B(..))` checks all the guards before the field is resolved, the parameters of a guard are Rust
+/// expressions in strings (`RoleGuard(role = "Role::Admin")`), and the guards can be combined with
+/// `and(A(..), B(..))` and `or(A(..), B(..))`. See [`Guard`](guard/trait.Guard.html).
 ///
 /// # The field returns the value type
 ///
@@ -199,6 +266,55 @@ pub use types::{GQLEnum, GQLEnumItem};
 /// ```
 pub use async_graphql_derive::Object;
 
+/// Define a GraphQL object by merging several objects
+///
+/// The fields of all the objects are exposed by the merged object, so the root objects can be split into
+/// several parts. `Schema::new` panics if the same field is defined by more than one of the objects.
+///
+/// # Macro parameters
+///
+/// | Attribute   | description               | Type     | Optional |
+/// |-------------|---------------------------|----------|----------|
+/// | name        | Object name               | string   | Y        |
+/// | desc        | Object description        | string   | Y        |
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct UserQuery;
+///
+/// #[Object]
+/// impl UserQuery {
+///     #[field]
+///     async fn user_name(&self) -> &str {
+///         "abc"
+///     }
+/// }
+///
+/// struct OrderQuery;
+///
+/// #[Object]
+/// impl OrderQuery {
+///     #[field]
+///     async fn order_count(&self) -> i32 {
+///         10
+///     }
+/// }
+///
+/// #[MergedObject]
+/// struct QueryRoot(UserQuery, OrderQuery);
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot(UserQuery, OrderQuery), GQLEmptyMutation);
+///     let res = schema.query("{ userName orderCount }").execute().await.unwrap();
+///     assert_eq!(res, serde_json::json!({ "userName": "abc", "orderCount": 10 }));
+/// }
+/// ```
+pub use async_graphql_derive::MergedObject;
+
 /// Define a GraphQL enum
 ///
 /// # Macro parameters
@@ -269,7 +385,11 @@ pub use async_graphql_derive::Enum;
 /// |-------------|---------------------------|----------|----------|
 /// | name        | Field name                | string   | Y        |
 /// | desc        | Field description         | string   | Y        |
-/// | default     | Field default value       | string   | Y        |
+/// | default     | Use `Default::default` as the default value | none | Y |
+/// | default     | Field default value, a GraphQL literal | string | Y |
+/// | default_with | Expression to generate the default value | code string | Y |
+/// | guard       | Field guards              | [Guard]  | Y        |
+/// | validator   | Field validators          | [InputValueValidator] | Y |
 ///
 /// # Examples
 ///
@@ -333,7 +453,9 @@ pub use async_graphql_derive::InputObject;
 /// | name        | Argument name             | string   | N        |
 /// | type        | Argument type             | string   | N        |
 /// | desc        | Argument description      | string   | Y        |
-/// | default     | Argument default value    | string   | Y        |
+/// | default     | Use `Default::default` as the default value | none | Y |
+/// | default     | Argument default value, a GraphQL literal | string | Y |
+/// | default_with | Expression to generate the default value | code string | Y |
 ///
 /// # Define an interface
 ///
@@ -424,3 +546,65 @@ pub use async_graphql_derive::Interface;
 ///
 /// It's similar to Interface, but it doesn't have fields.
 pub use async_graphql_derive::Union;
+
+/// Define a GraphQL scalar
+///
+/// Apply it to an implementation of `GQLScalar` to generate the rest of the glue, generic scalars are supported.
+/// It can also be applied to a newtype struct to create a scalar with the same representation as the inner scalar.
+///
+/// # Macro parameters
+///
+/// | Attribute        | description                                 | Type     | Optional |
+/// |------------------|---------------------------------------------|----------|----------|
+/// | name             | Scalar name, the type name by default       | string   | Y        |
+/// | desc             | Scalar description                          | string   | Y        |
+/// | specified_by_url | URL of the specification (`specifiedByURL`) | string   | Y        |
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct MyInt(i32);
+///
+/// #[Scalar(desc = "A 32-bit integer")]
+/// impl GQLScalar for MyInt {
+///     fn parse(value: &Value) -> InputValueResult<Self> {
+///         if let Value::Int(n) = value {
+///             Ok(MyInt(n.as_i64().unwrap() as i32))
+///         } else {
+///             Err(InputValueError::expected_type("MyInt", value))
+///         }
+///     }
+///
+///     fn to_json(&self) -> Result<serde_json::Value> {
+///         Ok(self.0.into())
+///     }
+/// }
+///
+/// #[Scalar(specified_by_url = "https://tools.ietf.org/html/rfc5322")]
+/// struct Email(String);
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     #[field]
+///     async fn double(&self, n: MyInt) -> MyInt {
+///         MyInt(n.0 * 2)
+///     }
+///
+///     #[field]
+///     async fn email(&self, email: Email) -> Email {
+///         email
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+///     let res = schema.query(r#"{ double(n: 10) email(email: "a@b.c") }"#).execute().await.unwrap();
+///     assert_eq!(res, serde_json::json!({ "double": 20, "email": "a@b.c" }));
+/// }
+/// ```
+pub use async_graphql_derive::Scalar;
@@ -0,0 +1,181 @@
+use crate::{Context, Result};
+use graphql_parser::query::{Field, Selection, SelectionSet, Value};
+
+/// Collect the fields of the selection set, expanding the fragment spreads and inline fragments and leaving out
+/// the fields skipped by `@skip` or `@include`.
+///
+/// All fragments are expanded regardless of their type condition.
+fn collect_fields<'a>(
+    ctx: &Context<'a>,
+    selection_set: &'a SelectionSet,
+    name: Option<&str>,
+    fields: &mut Vec<&'a Field>,
+) {
+    for selection in &selection_set.items {
+        match selection {
+            Selection::Field(field) => {
+                if ctx.is_skip(&field.directives).unwrap_or(false) {
+                    continue;
+                }
+                if name.map(|name| field.name == name).unwrap_or(true) {
+                    fields.push(field);
+                }
+            }
+            Selection::FragmentSpread(fragment_spread) => {
+                if ctx.is_skip(&fragment_spread.directives).unwrap_or(false) {
+                    continue;
+                }
+                if let Some(fragment) = ctx.fragments.get(&fragment_spread.fragment_name) {
+                    collect_fields(ctx, &fragment.selection_set, name, fields);
+                }
+            }
+            Selection::InlineFragment(inline_fragment) => {
+                if ctx.is_skip(&inline_fragment.directives).unwrap_or(false) {
+                    continue;
+                }
+                collect_fields(ctx, &inline_fragment.selection_set, name, fields);
+            }
+        }
+    }
+}
+
+/// A lookahead into the selection set of the current field, created by `Context::look_ahead`.
+///
+/// It points to all the selections of a field with the given name, so the presence of a nested field can be
+/// checked without resolving anything. The fields skipped by `@skip` or `@include` are not visible, and the
+/// fragments are expanded regardless of their type condition.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct User {
+///     with_friend_names: bool,
+/// }
+///
+/// #[Object]
+/// impl User {
+///     #[field]
+///     async fn with_friend_names(&self) -> bool {
+///         self.with_friend_names
+///     }
+///
+///     #[field]
+///     async fn friends(&self) -> Vec<User> {
+///         Vec::new()
+///     }
+///
+///     #[field]
+///     async fn name(&self) -> &str {
+///         "abc"
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     #[field]
+///     async fn user(&self, ctx: &Context<'_>) -> User {
+///         User {
+///             with_friend_names: ctx.look_ahead().field("friends").field("name").exists(),
+///         }
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+///     let res = schema.query(r#"{
+///         a: user { withFriendNames ...F }
+///         b: user { withFriendNames friends { name @include(if: false) } }
+///     }
+///     fragment F on User { friends { name } }"#).execute().await.unwrap();
+///     assert_eq!(res, serde_json::json!({
+///         "a": { "withFriendNames": true, "friends": [] },
+///         "b": { "withFriendNames": false, "friends": [] },
+///     }));
+/// }
+/// ```
+pub struct Lookahead<'a> {
+    ctx: &'a Context<'a>,
+    fields: Vec<&'a Field>,
+}
+
+impl<'a> Lookahead<'a> {
+    pub(crate) fn new(ctx: &'a Context<'a>, fields: Vec<&'a Field>) -> Self {
+        Self { ctx, fields }
+    }
+
+    /// Look ahead into the selections of the child field with the given name.
+    pub fn field(&self, name: &str) -> Lookahead<'a> {
+        let mut fields = Vec::new();
+        for field in &self.fields {
+            collect_fields(self.ctx, &field.selection_set, Some(name), &mut fields);
+        }
+        Lookahead {
+            ctx: self.ctx,
+            fields,
+        }
+    }
+
+    /// Returns true if the field is selected.
+    pub fn exists(&self) -> bool {
+        !self.fields.is_empty()
+    }
+
+    /// Returns the selected child fields, a field selected several times is returned once for each selection.
+    pub fn selection_fields(&self) -> Vec<SelectionField<'a>> {
+        let mut fields = Vec::new();
+        for field in &self.fields {
+            collect_fields(self.ctx, &field.selection_set, None, &mut fields);
+        }
+        fields
+            .into_iter()
+            .map(|field| SelectionField {
+                ctx: self.ctx,
+                field,
+            })
+            .collect()
+    }
+}
+
+/// A field in the selection set of the query.
+pub struct SelectionField<'a> {
+    ctx: &'a Context<'a>,
+    field: &'a Field,
+}
+
+impl<'a> SelectionField<'a> {
+    /// Returns the name of the field.
+    pub fn name(&self) -> &'a str {
+        &self.field.name
+    }
+
+    /// Returns the alias of the field.
+    pub fn alias(&self) -> Option<&'a str> {
+        self.field.alias.as_deref()
+    }
+
+    /// Returns the arguments of the field, the variables are replaced with their values.
+    ///
+    /// Only the arguments present in the query are returned, the default values of the schema are not applied.
+    pub fn arguments(&self) -> Result<Vec<(&'a str, Value)>> {
+        let mut arguments = Vec::with_capacity(self.field.arguments.len());
+        for (name, value) in &self.field.arguments {
+            arguments.push((name.as_str(), self.ctx.resolve_input_value(value.clone())?));
+        }
+        Ok(arguments)
+    }
+
+    /// Returns the selected child fields.
+    pub fn selection_fields(&self) -> Vec<SelectionField<'a>> {
+        self.look_ahead().selection_fields()
+    }
+
+    /// Look ahead into the selection set of this field.
+    pub fn look_ahead(&self) -> Lookahead<'a> {
+        Lookahead::new(self.ctx, vec![self.field])
+    }
+}
@@ -18,7 +18,7 @@ impl<'a> __EnumValue<'a> {
 
     #[field]
     async fn description(&self, _: &Context<'_>) -> Option<String> {
-        self.value.description.map(|s| s.to_string())
+        self.value.description.as_deref().map(|s| s.to_string())
     }
 
     #[field]
@@ -28,6 +28,6 @@ impl<'a> __EnumValue<'a> {
 
     #[field]
     async fn deprecation_reason(&self, _: &Context<'_>) -> Option<String> {
-        self.value.deprecation.map(|s| s.to_string())
+        self.value.deprecation.as_deref().map(|s| s.to_string())
     }
 }
@@ -19,7 +19,7 @@ impl<'a> __Field<'a> {
 
     #[field]
     async fn description(&self) -> Option<String> {
-        self.field.description.map(|s| s.to_string())
+        self.field.description.as_deref().map(|s| s.to_string())
     }
 
     #[field]
@@ -46,6 +46,6 @@ impl<'a> __Field<'a> {
 
     #[field]
     async fn deprecation_reason(&self) -> Option<String> {
-        self.field.deprecation.map(|s| s.to_string())
+        self.field.deprecation.as_deref().map(|s| s.to_string())
     }
 }
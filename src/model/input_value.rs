@@ -19,7 +19,10 @@ impl<'a> __InputValue<'a> {
 
     #[field]
     async fn description(&self) -> Option<String> {
-        self.input_value.description.map(|s| s.to_string())
+        self.input_value
+            .description
+            .as_deref()
+            .map(|s| s.to_string())
     }
 
     #[field(name = "type")]
@@ -29,6 +32,9 @@ impl<'a> __InputValue<'a> {
 
     #[field]
     async fn default_value(&self) -> Option<String> {
-        self.input_value.default_value.map(|s| s.to_string())
+        self.input_value
+            .default_value
+            .as_ref()
+            .map(|value| value.to_string())
     }
 }
@@ -78,13 +78,23 @@ impl<'a> __Type<'a> {
     async fn description(&self) -> Option<String> {
         match &self.detail {
             TypeDetail::Simple(ty) => match ty {
-                registry::Type::Scalar { description, .. } => description.map(|s| s.to_string()),
-                registry::Type::Object { description, .. } => description.map(|s| s.to_string()),
-                registry::Type::Interface { description, .. } => description.map(|s| s.to_string()),
-                registry::Type::Union { description, .. } => description.map(|s| s.to_string()),
-                registry::Type::Enum { description, .. } => description.map(|s| s.to_string()),
+                registry::Type::Scalar { description, .. } => {
+                    description.as_deref().map(|s| s.to_string())
+                }
+                registry::Type::Object { description, .. } => {
+                    description.as_deref().map(|s| s.to_string())
+                }
+                registry::Type::Interface { description, .. } => {
+                    description.as_deref().map(|s| s.to_string())
+                }
+                registry::Type::Union { description, .. } => {
+                    description.as_deref().map(|s| s.to_string())
+                }
+                registry::Type::Enum { description, .. } => {
+                    description.as_deref().map(|s| s.to_string())
+                }
                 registry::Type::InputObject { description, .. } => {
-                    description.map(|s| s.to_string())
+                    description.as_deref().map(|s| s.to_string())
                 }
             },
             TypeDetail::NonNull(_) => None,
@@ -124,7 +134,7 @@ impl<'a> __Type<'a> {
             Some(
                 self.registry
                     .implements
-                    .get(*name)
+                    .get(name)
                     .unwrap_or(&Default::default())
                     .iter()
                     .map(|ty| __Type::new(self.registry, ty))
@@ -204,4 +214,16 @@ impl<'a> __Type<'a> {
             None
         }
     }
+
+    #[field(name = "specifiedByURL")]
+    async fn specified_by_url(&self) -> Option<&'static str> {
+        if let TypeDetail::Simple(Type::Scalar {
+            specified_by_url, ..
+        }) = &self.detail
+        {
+            *specified_by_url
+        } else {
+            None
+        }
+    }
 }
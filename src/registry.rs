@@ -1,5 +1,9 @@
-use crate::{model, GQLType, Value};
+use crate::validators::InputValueValidator;
+use crate::{model, CacheControl, GQLType, InputValueResult, Value};
+use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+use std::sync::Arc;
 
 fn parse_non_null(type_name: &str) -> Option<&str> {
     if type_name.ends_with("!") {
@@ -42,6 +46,23 @@ impl<'a> TypeName<'a> {
         }
     }
 
+    /// The name of a type parameter in the name of a generic type, the wrappers are spelled out so the names of
+    /// different types never collide, for example `[User!]!` is `UserList` and `User` is `UserNullable`.
+    pub fn get_generic_param_name(type_name: &str) -> String {
+        match TypeName::create(type_name) {
+            TypeName::NonNull(type_name) => match TypeName::create(type_name) {
+                TypeName::List(type_name) => {
+                    format!("{}List", Self::get_generic_param_name(type_name))
+                }
+                _ => type_name.to_string(),
+            },
+            TypeName::List(type_name) => {
+                format!("{}ListNullable", Self::get_generic_param_name(type_name))
+            }
+            TypeName::Named(type_name) => format!("{}Nullable", type_name),
+        }
+    }
+
     pub fn is_non_null(&self) -> bool {
         if let TypeName::NonNull(_) = self {
             true
@@ -51,57 +72,70 @@ impl<'a> TypeName<'a> {
     }
 }
 
+#[derive(Clone)]
 pub struct InputValue {
-    pub name: &'static str,
-    pub description: Option<&'static str>,
+    pub name: Cow<'static, str>,
+    pub description: Option<Cow<'static, str>>,
     pub ty: String,
-    pub default_value: Option<&'static str>,
+    pub default_value: Option<Value>,
+    pub validator: Option<Arc<dyn InputValueValidator>>,
 }
 
+#[derive(Clone)]
 pub struct Field {
-    pub name: &'static str,
-    pub description: Option<&'static str>,
-    pub args: HashMap<&'static str, InputValue>,
+    pub name: Cow<'static, str>,
+    pub description: Option<Cow<'static, str>>,
+    pub args: HashMap<Cow<'static, str>, InputValue>,
     pub ty: String,
-    pub deprecation: Option<&'static str>,
+    pub deprecation: Option<Cow<'static, str>>,
+    pub external: bool,
+    pub requires: Option<&'static str>,
+    pub provides: Option<&'static str>,
+    pub cache_control: Option<CacheControl>,
 }
 
 pub struct EnumValue {
-    pub name: &'static str,
-    pub description: Option<&'static str>,
-    pub deprecation: Option<&'static str>,
+    pub name: Cow<'static, str>,
+    pub description: Option<Cow<'static, str>>,
+    pub deprecation: Option<Cow<'static, str>>,
 }
 
 pub enum Type {
     Scalar {
         name: String,
-        description: Option<&'static str>,
+        description: Option<Cow<'static, str>>,
         is_valid: fn(value: &Value) -> bool,
+        /// Parse a value of the scalar and drop it, used for the reason when `is_valid` returns false.
+        parse: fn(value: &Value) -> InputValueResult<()>,
+        specified_by_url: Option<&'static str>,
     },
     Object {
-        name: &'static str,
-        description: Option<&'static str>,
-        fields: HashMap<&'static str, Field>,
+        name: String,
+        description: Option<Cow<'static, str>>,
+        fields: HashMap<Cow<'static, str>, Field>,
+        extends: bool,
+        keys: Option<Vec<String>>,
+        cache_control: Option<CacheControl>,
     },
     Interface {
-        name: &'static str,
-        description: Option<&'static str>,
-        fields: HashMap<&'static str, Field>,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        fields: HashMap<Cow<'static, str>, Field>,
         possible_types: HashSet<String>,
     },
     Union {
-        name: &'static str,
-        description: Option<&'static str>,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
         possible_types: HashSet<String>,
     },
     Enum {
-        name: &'static str,
-        description: Option<&'static str>,
-        enum_values: HashMap<&'static str, EnumValue>,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        enum_values: HashMap<Cow<'static, str>, EnumValue>,
     },
     InputObject {
-        name: &'static str,
-        description: Option<&'static str>,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
         input_fields: Vec<InputValue>,
     },
 }
@@ -111,7 +145,7 @@ impl Type {
         self.fields().and_then(|fields| fields.get(name))
     }
 
-    pub fn fields(&self) -> Option<&HashMap<&'static str, Field>> {
+    pub fn fields(&self) -> Option<&HashMap<Cow<'static, str>, Field>> {
         match self {
             Type::Object { fields, .. } => Some(&fields),
             Type::Interface { fields, .. } => Some(&fields),
@@ -169,7 +203,7 @@ pub struct Directive {
     pub name: &'static str,
     pub description: Option<&'static str>,
     pub locations: Vec<model::__DirectiveLocation>,
-    pub args: HashMap<&'static str, InputValue>,
+    pub args: HashMap<Cow<'static, str>, InputValue>,
 }
 
 pub struct Registry {
@@ -178,6 +212,8 @@ pub struct Registry {
     pub implements: HashMap<String, HashSet<String>>,
     pub query_type: String,
     pub mutation_type: Option<String>,
+    /// The errors found while the types are created, they are reported when the schema is built.
+    pub errors: Vec<String>,
 }
 
 impl Registry {
@@ -187,21 +223,28 @@ impl Registry {
             self.types.insert(
                 name.to_string(),
                 Type::Object {
-                    name: "",
+                    name: String::new(),
                     description: None,
                     fields: Default::default(),
+                    extends: false,
+                    keys: None,
+                    cache_control: Default::default(),
                 },
             );
             let mut ty = f(self);
             if let Type::Object { fields, .. } = &mut ty {
                 fields.insert(
-                    "__typename",
+                    "__typename".into(),
                     Field {
-                        name: "__typename",
+                        name: "__typename".into(),
                         description: None,
                         args: Default::default(),
                         ty: "String!".to_string(),
                         deprecation: None,
+                        external: false,
+                        requires: None,
+                        provides: None,
+                        cache_control: Default::default(),
                     },
                 );
             }
@@ -210,6 +253,44 @@ impl Registry {
         T::qualified_type_name()
     }
 
+    /// Collect the fields of the object types that make up a merged object.
+    ///
+    /// A field that is defined by more than one of the object types is recorded in `errors`.
+    pub fn merge_object_fields(
+        &mut self,
+        merged_type: &str,
+        type_names: &[&str],
+    ) -> HashMap<Cow<'static, str>, Field> {
+        let mut fields = HashMap::new();
+        let mut owners = HashMap::new();
+        for type_name in type_names {
+            let object_fields = match self.types.get(*type_name) {
+                Some(Type::Object { fields, .. }) => fields,
+                _ => {
+                    self.errors.push(format!(
+                        "\"{}\" in the merged object \"{}\" is not an object type",
+                        type_name, merged_type
+                    ));
+                    continue;
+                }
+            };
+            for (name, field) in object_fields {
+                if *name == "__typename" {
+                    continue;
+                }
+                if let Some(owner) = owners.insert(name.clone(), *type_name) {
+                    self.errors.push(format!(
+                        "Field \"{}\" of the merged object \"{}\" is defined by both \"{}\" and \"{}\"",
+                        name, merged_type, owner, type_name
+                    ));
+                    continue;
+                }
+                fields.insert(name.clone(), field.clone());
+            }
+        }
+        fields
+    }
+
     pub fn add_directive(&mut self, directive: Directive) {
         self.directives
             .insert(directive.name.to_string(), directive);
@@ -231,4 +312,181 @@ impl Registry {
     pub fn get_basic_type(&self, type_name: &str) -> Option<&Type> {
         self.types.get(TypeName::get_basic_typename(type_name))
     }
+
+    /// Export the schema as an SDL document.
+    ///
+    /// If `federation` is true, the document is the one returned by `_service { sdl }`: it includes the federation
+    /// directives (`@key`, `@external`, `@requires` and `@provides`) and omits the types and fields added by the
+    /// federation.
+    pub fn export_sdl(&self, federation: bool) -> String {
+        let mut sdl = String::new();
+        let mut types = self
+            .types
+            .values()
+            .filter(|ty| !is_builtin_type(ty.name(), federation))
+            .collect::<Vec<_>>();
+        types.sort_by(|a, b| a.name().cmp(b.name()));
+
+        for ty in types {
+            self.export_type(ty, &mut sdl, federation);
+        }
+
+        if !federation {
+            writeln!(sdl, "schema {{").unwrap();
+            writeln!(sdl, "\tquery: {}", self.query_type).unwrap();
+            if let Some(mutation_type) = &self.mutation_type {
+                writeln!(sdl, "\tmutation: {}", mutation_type).unwrap();
+            }
+            writeln!(sdl, "}}").unwrap();
+        }
+        sdl
+    }
+
+    fn export_type(&self, ty: &Type, sdl: &mut String, federation: bool) {
+        match ty {
+            Type::Scalar {
+                name,
+                specified_by_url,
+                ..
+            } => {
+                write!(sdl, "scalar {}", name).unwrap();
+                if let Some(url) = specified_by_url {
+                    write!(sdl, " @specifiedBy(url: {:?})", url).unwrap();
+                }
+                writeln!(sdl).unwrap();
+            }
+            Type::Object {
+                name,
+                fields,
+                extends,
+                keys,
+                ..
+            } => {
+                let fields = sorted_fields(fields, federation && *name == self.query_type);
+                if fields.is_empty() {
+                    return;
+                }
+                if federation && *extends {
+                    write!(sdl, "extend type {}", name).unwrap();
+                } else {
+                    write!(sdl, "type {}", name).unwrap();
+                }
+                if let Some(interfaces) = self.implements.get(name) {
+                    let mut interfaces = interfaces.iter().map(String::as_str).collect::<Vec<_>>();
+                    interfaces.sort();
+                    write!(sdl, " implements {}", interfaces.join(" & ")).unwrap();
+                }
+                if federation {
+                    for key in keys.iter().flatten() {
+                        write!(sdl, " @key(fields: {:?})", key).unwrap();
+                    }
+                }
+                writeln!(sdl, " {{").unwrap();
+                for field in fields {
+                    export_field(field, sdl, federation);
+                }
+                writeln!(sdl, "}}").unwrap();
+            }
+            Type::Interface { name, fields, .. } => {
+                writeln!(sdl, "interface {} {{", name).unwrap();
+                for field in sorted_fields(fields, false) {
+                    export_field(field, sdl, federation);
+                }
+                writeln!(sdl, "}}").unwrap();
+            }
+            Type::Union {
+                name,
+                possible_types,
+                ..
+            } => {
+                let mut possible_types = possible_types
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>();
+                possible_types.sort();
+                writeln!(sdl, "union {} = {}", name, possible_types.join(" | ")).unwrap();
+            }
+            Type::Enum {
+                name, enum_values, ..
+            } => {
+                let mut enum_values = enum_values.values().collect::<Vec<_>>();
+                enum_values.sort_by(|a, b| a.name.cmp(&b.name));
+                writeln!(sdl, "enum {} {{", name).unwrap();
+                for value in enum_values {
+                    write!(sdl, "\t{}", value.name).unwrap();
+                    if let Some(reason) = &value.deprecation {
+                        write!(sdl, " @deprecated(reason: {:?})", reason).unwrap();
+                    }
+                    writeln!(sdl).unwrap();
+                }
+                writeln!(sdl, "}}").unwrap();
+            }
+            Type::InputObject {
+                name, input_fields, ..
+            } => {
+                writeln!(sdl, "input {} {{", name).unwrap();
+                for field in input_fields {
+                    writeln!(sdl, "\t{}", export_input_value(field)).unwrap();
+                }
+                writeln!(sdl, "}}").unwrap();
+            }
+        }
+    }
+}
+
+fn is_builtin_type(name: &str, federation: bool) -> bool {
+    name.starts_with("__")
+        || ["Int", "Float", "String", "Boolean", "ID"].contains(&name)
+        || (federation && ["_Any", "_Entity", "_Service"].contains(&name))
+}
+
+fn sorted_fields<'a>(
+    fields: &'a HashMap<Cow<'static, str>, Field>,
+    skip_federation: bool,
+) -> Vec<&'a Field> {
+    let mut fields = fields
+        .values()
+        .filter(|field| !field.name.starts_with("__"))
+        .filter(|field| {
+            !skip_federation || !["_service", "_entities"].contains(&field.name.as_ref())
+        })
+        .collect::<Vec<_>>();
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+    fields
+}
+
+fn export_field(field: &Field, sdl: &mut String, federation: bool) {
+    write!(sdl, "\t{}", field.name).unwrap();
+    if !field.args.is_empty() {
+        let mut args = field.args.values().collect::<Vec<_>>();
+        args.sort_by(|a, b| a.name.cmp(&b.name));
+        let args = args.into_iter().map(export_input_value).collect::<Vec<_>>();
+        write!(sdl, "({})", args.join(", ")).unwrap();
+    }
+    write!(sdl, ": {}", field.ty).unwrap();
+    if federation {
+        if field.external {
+            write!(sdl, " @external").unwrap();
+        }
+        if let Some(requires) = field.requires {
+            write!(sdl, " @requires(fields: {:?})", requires).unwrap();
+        }
+        if let Some(provides) = field.provides {
+            write!(sdl, " @provides(fields: {:?})", provides).unwrap();
+        }
+    }
+    if let Some(reason) = &field.deprecation {
+        write!(sdl, " @deprecated(reason: {:?})", reason).unwrap();
+    }
+    writeln!(sdl).unwrap();
+}
+
+fn export_input_value(input_value: &InputValue) -> String {
+    match &input_value.default_value {
+        Some(default_value) => format!(
+            "{}: {} = {}",
+            input_value.name, input_value.ty, default_value
+        ),
+        None => format!("{}: {}", input_value.name, input_value.ty),
+    }
 }
@@ -1,99 +1,353 @@
-use crate::{ContextSelectionSet, ErrorWithPosition, GQLObject, QueryError, Result};
-use graphql_parser::query::{Selection, TypeCondition};
+use crate::incremental::{resolve_initial, InitialValue, Tail};
+use crate::{
+    Context, ContextBase, ContextSelectionSet, ErrorWithPosition, GQLObject, QueryError, Result,
+};
+use futures::future::{self, BoxFuture};
+use graphql_parser::query::{Directive, Field, Selection, SelectionSet, TypeCondition};
+use std::borrow::Cow;
 use std::future::Future;
 use std::pin::Pin;
+#[cfg(feature = "tracing")]
+use tracing::Instrument;
 
-struct Resolver<'a, T> {
+/// An object whose selection sets are resolved by the `Resolver`, it is implemented by the `GQLObject` types and by
+/// the objects of the dynamic schemas.
+#[async_trait::async_trait]
+pub(crate) trait ResolveObject: Send + Sync {
+    /// The name of the object type.
+    fn type_name(&self) -> Cow<'_, str>;
+
+    /// Resolves a field value and outputs it as a json value `serde_json::Value`.
+    async fn resolve_field(&self, ctx: &Context<'_>, field: &Field) -> Result<serde_json::Value>;
+
+    /// Resolve an inline fragment with the `name`.
+    async fn resolve_inline_fragment(
+        &self,
+        name: &str,
+        ctx: &ContextSelectionSet<'_>,
+        result: &mut serde_json::Map<String, serde_json::Value>,
+    ) -> Result<()>;
+}
+
+#[async_trait::async_trait]
+impl<T: GQLObject + Send + Sync> ResolveObject for T {
+    fn type_name(&self) -> Cow<'_, str> {
+        T::type_name()
+    }
+
+    async fn resolve_field(&self, ctx: &Context<'_>, field: &Field) -> Result<serde_json::Value> {
+        GQLObject::resolve_field(self, ctx, field).await
+    }
+
+    async fn resolve_inline_fragment(
+        &self,
+        name: &str,
+        ctx: &ContextSelectionSet<'_>,
+        result: &mut serde_json::Map<String, serde_json::Value>,
+    ) -> Result<()> {
+        GQLObject::resolve_inline_fragment(self, name, ctx, result).await
+    }
+}
+
+/// The deferred parts of a selection set, resolved after its initial value is sent in an incremental execution.
+type Pending<'a> = Vec<BoxFuture<'a, ()>>;
+
+struct Resolver<'a, 'r, T: ?Sized> {
     ctx: &'a ContextSelectionSet<'a>,
     obj: &'a T,
-    result: &'a mut serde_json::Map<String, serde_json::Value>,
+    result: &'r mut serde_json::Map<String, serde_json::Value>,
 }
 
-impl<'a, T: GQLObject + Send + Sync> Resolver<'a, T> {
-    pub fn resolve(&'a mut self) -> Pin<Box<dyn Future<Output = Result<()>> + 'a + Send>> {
+impl<'a: 'r, 'r, T: ResolveObject + ?Sized> Resolver<'a, 'r, T> {
+    /// Resolve the selection set into `result`, and return its deferred parts.
+    pub fn resolve(self) -> Pin<Box<dyn Future<Output = Result<Pending<'a>>> + 'r + Send>> {
         Box::pin(async move {
-            if self.ctx.items.is_empty() {
+            let Resolver { ctx, obj, result } = self;
+            if ctx.items.is_empty() {
                 anyhow::bail!(QueryError::MustHaveSubFields {
-                    object: T::type_name().to_string(),
+                    object: obj.type_name().to_string(),
                 }
-                .with_position(self.ctx.span.0));
+                .with_position(ctx.span.0));
             }
 
-            for selection in &self.ctx.item.items {
+            let mut pending = Vec::new();
+            for selection in &ctx.item.items {
                 match selection {
                     Selection::Field(field) => {
-                        if self.ctx.is_skip(&field.directives)? {
+                        if ctx.is_skip(&field.directives)? {
                             continue;
                         }
 
-                        let ctx_field = self.ctx.with_item(field);
+                        let result_name = field.alias.clone().unwrap_or_else(|| field.name.clone());
                         if field.name.as_str() == "__typename" {
-                            self.result
-                                .insert(ctx_field.result_name(), T::type_name().to_string().into());
+                            result.insert(result_name, obj.type_name().to_string().into());
                             continue;
                         }
 
-                        self.result.insert(
-                            ctx_field.result_name(),
-                            self.obj.resolve_field(&ctx_field, field).await?,
-                        );
+                        ctx.merge_cache_control(&obj.type_name(), field.name.as_str());
+
+                        if is_incremental(ctx) {
+                            let stream =
+                                ctx.find_incremental_directive(&field.directives, "stream")?;
+                            let (value, tail) = resolve_initial(|initial| {
+                                Box::pin(async move {
+                                    let ctx_field = ContextBase {
+                                        initial: Some(&initial),
+                                        stream,
+                                        ..ctx.with_field(field)
+                                    };
+                                    resolve_field(obj, &ctx_field, field).await
+                                })
+                            })
+                            .await?;
+                            result.insert(result_name, value);
+                            pending.extend(tail.map(Tail::resolve));
+                        } else {
+                            let ctx_field = ctx.with_field(field);
+                            let value = resolve_field(obj, &ctx_field, field).await?;
+                            result.insert(result_name, value);
+                        }
                     }
                     Selection::FragmentSpread(fragment_spread) => {
-                        if self.ctx.is_skip(&fragment_spread.directives)? {
+                        if ctx.is_skip(&fragment_spread.directives)? {
                             continue;
                         }
 
-                        if let Some(fragment) =
-                            self.ctx.fragments.get(&fragment_spread.fragment_name)
-                        {
-                            Resolver {
-                                ctx: &self.ctx.with_item(&fragment.selection_set),
-                                obj: self.obj,
-                                result: self.result,
+                        let fragment = match ctx.fragments.get(&fragment_spread.fragment_name) {
+                            Some(fragment) => fragment,
+                            None => {
+                                return Err(QueryError::UnknownFragment {
+                                    name: fragment_spread.fragment_name.clone(),
+                                }
+                                .into())
                             }
-                            .resolve()
-                            .await?;
-                        } else {
-                            return Err(QueryError::UnknownFragment {
-                                name: fragment_spread.fragment_name.clone(),
-                            }
-                            .into());
-                        }
+                        };
+                        let TypeCondition::On(name) = &fragment.type_condition;
+                        resolve_fragment(
+                            ctx,
+                            obj,
+                            &fragment_spread.directives,
+                            Some(name),
+                            &fragment.selection_set,
+                            result,
+                            &mut pending,
+                        )
+                        .await?;
                     }
                     Selection::InlineFragment(inline_fragment) => {
-                        if self.ctx.is_skip(&inline_fragment.directives)? {
+                        if ctx.is_skip(&inline_fragment.directives)? {
                             continue;
                         }
 
-                        if let Some(TypeCondition::On(name)) = &inline_fragment.type_condition {
-                            self.obj
-                                .resolve_inline_fragment(
-                                    &name,
-                                    &self.ctx.with_item(&inline_fragment.selection_set),
-                                    self.result,
-                                )
-                                .await?;
-                        }
+                        let type_condition = inline_fragment
+                            .type_condition
+                            .as_ref()
+                            .map(|TypeCondition::On(name)| name.as_str());
+                        resolve_fragment(
+                            ctx,
+                            obj,
+                            &inline_fragment.directives,
+                            type_condition,
+                            &inline_fragment.selection_set,
+                            result,
+                            &mut pending,
+                        )
+                        .await?;
                     }
                 }
             }
 
-            Ok(())
+            Ok(pending)
         })
     }
 }
 
+/// Whether the selection set is resolved incrementally, it needs a parent to send its initial value to, e.g. the
+/// entities of `_entities` are resolved at once.
+pub(crate) fn is_incremental<T>(ctx: &ContextBase<'_, T>) -> bool {
+    ctx.incremental.is_some() && ctx.initial.is_some()
+}
+
+/// Resolve a field value of the object, the errors are given the position and the path of the field.
+async fn resolve_field<T: ResolveObject + ?Sized>(
+    obj: &T,
+    ctx_field: &Context<'_>,
+    field: &Field,
+) -> Result<serde_json::Value> {
+    let value = obj.resolve_field(ctx_field, field);
+    #[cfg(feature = "tracing")]
+    let value = async move {
+        let res = value.await;
+        if let Err(err) = &res {
+            // The errors of the nested fields have a path, and are recorded in their spans.
+            let nested = err
+                .downcast_ref::<crate::PositionError>()
+                .map(|err| err.path.is_some())
+                .unwrap_or_default();
+            if !nested {
+                tracing::error!(error = %err, "field failed");
+            }
+        }
+        res
+    }
+    .instrument(tracing::info_span!(
+        "field",
+        parent_type = %obj.type_name(),
+        field_name = %field.name,
+        path = %ctx_field.path_node.as_ref().unwrap(),
+    ));
+    value.await.map_err(|err| {
+        let err = err.with_position(field.position);
+        match &ctx_field.path_node {
+            Some(path_node) => err.with_path(path_node.to_json()).into(),
+            None => err.into(),
+        }
+    })
+}
+
+/// Resolve a fragment of the object into `result`, a deferred fragment of an incremental execution is added to
+/// `pending` to be resolved into a patch.
+async fn resolve_fragment<'a, T: ResolveObject + ?Sized>(
+    ctx: &'a ContextSelectionSet<'a>,
+    obj: &'a T,
+    directives: &'a [Directive],
+    type_condition: Option<&'a str>,
+    selection_set: &'a SelectionSet,
+    result: &mut serde_json::Map<String, serde_json::Value>,
+    pending: &mut Pending<'a>,
+) -> Result<()> {
+    if !is_incremental(ctx) {
+        let ctx_fragment = ctx.with_item(selection_set);
+        return match type_condition {
+            Some(name) if obj.type_name() != name => {
+                obj.resolve_inline_fragment(name, &ctx_fragment, result)
+                    .await
+            }
+            _ => Resolver {
+                ctx: &ctx_fragment,
+                obj,
+                result,
+            }
+            .resolve()
+            .await
+            .map(|_| ()),
+        };
+    }
+
+    if let Some(directive) = ctx.find_incremental_directive(directives, "defer")? {
+        pending.push(Box::pin(resolve_deferred(
+            ctx,
+            obj,
+            directive.label,
+            type_condition,
+            selection_set,
+        )));
+        return Ok(());
+    }
+
+    let (value, tail) = resolve_initial(|initial| {
+        resolve_fragment_initial(ctx, obj, type_condition, selection_set, initial)
+    })
+    .await?;
+    if let serde_json::Value::Object(value) = value {
+        result.extend(value);
+    }
+    pending.extend(tail.map(Tail::resolve));
+    Ok(())
+}
+
+/// Resolve a fragment of the object into a new map, its deferred parts send the map to `initial` first.
+fn resolve_fragment_initial<'a, T: ResolveObject + ?Sized>(
+    ctx: &'a ContextSelectionSet<'a>,
+    obj: &'a T,
+    type_condition: Option<&'a str>,
+    selection_set: &'a SelectionSet,
+    initial: InitialValue,
+) -> BoxFuture<'a, Result<serde_json::Value>> {
+    Box::pin(async move {
+        let ctx_fragment = ContextBase {
+            initial: Some(&initial),
+            ..ctx.with_item(selection_set)
+        };
+        let mut data = serde_json::Map::new();
+        match type_condition {
+            Some(name) if obj.type_name() != name => {
+                obj.resolve_inline_fragment(name, &ctx_fragment, &mut data)
+                    .await?
+            }
+            _ => resolve_object(&ctx_fragment, obj, &mut data).await?,
+        }
+        Ok(data.into())
+    })
+}
+
+/// Resolve a deferred fragment of the object into a patch, after the initial value of the object is sent.
+async fn resolve_deferred<'a, T: ResolveObject + ?Sized>(
+    ctx: &'a ContextSelectionSet<'a>,
+    obj: &'a T,
+    label: Option<String>,
+    type_condition: Option<&'a str>,
+    selection_set: &'a SelectionSet,
+) {
+    let patches = match ctx.incremental {
+        Some(patches) => patches,
+        None => return,
+    };
+    let path = ctx
+        .path_node
+        .as_ref()
+        .map(|path_node| path_node.to_json())
+        .unwrap_or_else(|| serde_json::Value::Array(Vec::new()));
+    let res = patches
+        .resolve(resolve_initial(|initial| {
+            resolve_fragment_initial(ctx, obj, type_condition, selection_set, initial)
+        }))
+        .await;
+    match res {
+        Ok((data, tail)) => {
+            // A fragment whose fields are all deferred or skipped has no patch.
+            if data
+                .as_object()
+                .map(|data| !data.is_empty())
+                .unwrap_or(true)
+            {
+                patches.send(label, path, Ok(data));
+            }
+            if let Some(tail) = tail {
+                tail.resolve().await;
+            }
+        }
+        Err(err) => patches.send(label, path, Err(err)),
+    }
+}
+
 pub async fn do_resolve<'a, T: GQLObject + Send + Sync>(
     ctx: &'a ContextSelectionSet<'a>,
     root: &'a T,
     result: &mut serde_json::Map<String, serde_json::Value>,
 ) -> Result<()> {
-    Resolver {
+    resolve_object(ctx, root, result).await
+}
+
+/// Resolve the selection set of an object into `result`, in an incremental execution the object with deferred parts
+/// sends `result` as its initial value and then resolves them.
+pub(crate) async fn resolve_object<'a, T: ResolveObject + ?Sized>(
+    ctx: &'a ContextSelectionSet<'a>,
+    root: &'a T,
+    result: &mut serde_json::Map<String, serde_json::Value>,
+) -> Result<()> {
+    let pending = Resolver {
         ctx,
         obj: root,
         result,
     }
     .resolve()
     .await?;
+    if !pending.is_empty() {
+        if let Some(initial) = ctx.initial {
+            initial.send(std::mem::take(result).into()).await;
+        }
+        future::join_all(pending).await;
+    }
     Ok(())
 }
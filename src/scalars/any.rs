@@ -0,0 +1,21 @@
+use crate::scalars::json::gql_value_to_json_value;
+use crate::{GQLScalar, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+
+/// The `_Any` scalar of the federation, it represents the entity representations passed to `_entities`.
+pub(crate) struct Any(pub Value);
+
+#[Scalar(internal, name = "_Any")]
+impl GQLScalar for Any {
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        Ok(Any(value.clone()))
+    }
+
+    fn is_valid(_value: &Value) -> bool {
+        true
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(gql_value_to_json_value(&self.0))
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use std::ops::{Deref, DerefMut};
+
+/// Binary data, transferred as a standard base64 encoded string.
+///
+/// `Vec<u8>` is already a list of integers, so the bytes are wrapped in this type to be
+/// exposed as a scalar.
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Base64(pub Vec<u8>);
+
+impl Deref for Base64 {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Base64 {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<u8>> for Base64 {
+    fn from(value: Vec<u8>) -> Self {
+        Base64(value)
+    }
+}
+
+#[Scalar(internal)]
+impl GQLScalar for Base64 {
+    fn type_name() -> &'static str {
+        "Base64"
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => base64::decode(s)
+                .map(Base64)
+                .map_err(|err| InputValueError::custom(format!("invalid Base64, {}", err))),
+            _ => Err(InputValueError::expected_type("Base64", value)),
+        }
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(base64::encode(&self.0).into())
+    }
+}
@@ -1,5 +1,7 @@
-use crate::{impl_scalar_internal, GQLScalar, Result, Value};
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
 
+#[Scalar(internal)]
 impl GQLScalar for bool {
     fn type_name() -> &'static str {
         "Boolean"
@@ -9,10 +11,10 @@ impl GQLScalar for bool {
         Some("The `Boolean` scalar type represents `true` or `false`.")
     }
 
-    fn parse(value: &Value) -> Option<Self> {
+    fn parse(value: &Value) -> InputValueResult<Self> {
         match value {
-            Value::Boolean(n) => Some(*n),
-            _ => None,
+            Value::Boolean(n) => Ok(*n),
+            _ => Err(InputValueError::expected_type("Boolean", value)),
         }
     }
 
@@ -20,5 +22,3 @@ impl GQLScalar for bool {
         Ok((*self).into())
     }
 }
-
-impl_scalar_internal!(bool);
@@ -0,0 +1,22 @@
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use bson::oid::ObjectId;
+
+#[Scalar(internal)]
+impl GQLScalar for ObjectId {
+    fn type_name() -> &'static str {
+        "ObjectId"
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => ObjectId::with_string(s)
+                .map_err(|err| InputValueError::custom(format!("invalid ObjectId, {}", err))),
+            _ => Err(InputValueError::expected_type("ObjectId", value)),
+        }
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(self.to_hex().into())
+    }
+}
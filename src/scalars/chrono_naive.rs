@@ -0,0 +1,60 @@
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+#[Scalar(internal)]
+impl GQLScalar for NaiveDate {
+    fn type_name() -> &'static str {
+        "NaiveDate"
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => NaiveDate::parse_from_str(s, "%Y-%m-%d")
+                .map_err(|err| InputValueError::custom(format!("invalid NaiveDate, {}", err))),
+            _ => Err(InputValueError::expected_type("NaiveDate", value)),
+        }
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(self.format("%Y-%m-%d").to_string().into())
+    }
+}
+
+#[Scalar(internal)]
+impl GQLScalar for NaiveTime {
+    fn type_name() -> &'static str {
+        "NaiveTime"
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => NaiveTime::parse_from_str(s, "%H:%M:%S%.f")
+                .map_err(|err| InputValueError::custom(format!("invalid NaiveTime, {}", err))),
+            _ => Err(InputValueError::expected_type("NaiveTime", value)),
+        }
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(self.format("%H:%M:%S%.f").to_string().into())
+    }
+}
+
+#[Scalar(internal)]
+impl GQLScalar for NaiveDateTime {
+    fn type_name() -> &'static str {
+        "NaiveDateTime"
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f")
+                .map_err(|err| InputValueError::custom(format!("invalid NaiveDateTime, {}", err))),
+            _ => Err(InputValueError::expected_type("NaiveDateTime", value)),
+        }
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(self.format("%Y-%m-%dT%H:%M:%S%.f").to_string().into())
+    }
+}
@@ -1,16 +1,24 @@
-use crate::{impl_scalar_internal, GQLScalar, Result, Value};
-use chrono::{DateTime, TimeZone, Utc};
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use chrono::{DateTime, FixedOffset, Utc};
 
+/// Both `DateTime<Utc>` and `DateTime<FixedOffset>` are the `DateTime` scalar, so they accept the same values.
+fn parse_datetime(value: &Value) -> InputValueResult<DateTime<FixedOffset>> {
+    match value {
+        Value::String(s) => DateTime::parse_from_rfc3339(s)
+            .map_err(|err| InputValueError::custom(format!("invalid DateTime, {}", err))),
+        _ => Err(InputValueError::expected_type("DateTime", value)),
+    }
+}
+
+#[Scalar(internal)]
 impl GQLScalar for DateTime<Utc> {
     fn type_name() -> &'static str {
         "DateTime"
     }
 
-    fn parse(value: &Value) -> Option<Self> {
-        match value {
-            Value::String(s) => Some(Utc.datetime_from_str(&s, "%+").ok()?),
-            _ => None,
-        }
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        parse_datetime(value).map(|datetime| datetime.with_timezone(&Utc))
     }
 
     fn to_json(&self) -> Result<serde_json::Value> {
@@ -18,4 +26,17 @@ impl GQLScalar for DateTime<Utc> {
     }
 }
 
-impl_scalar_internal!(DateTime<Utc>);
+#[Scalar(internal)]
+impl GQLScalar for DateTime<FixedOffset> {
+    fn type_name() -> &'static str {
+        "DateTime"
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        parse_datetime(value)
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(self.to_rfc3339().into())
+    }
+}
@@ -0,0 +1,28 @@
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+#[Scalar(internal)]
+impl GQLScalar for Decimal {
+    fn type_name() -> &'static str {
+        "Decimal"
+    }
+
+    fn description() -> Option<&'static str> {
+        Some("A fixed-precision decimal number, serialized as a string.")
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        match value {
+            Value::Int(n) => Ok(Decimal::from(n.as_i64().unwrap())),
+            Value::String(s) => Decimal::from_str(s)
+                .map_err(|err| InputValueError::custom(format!("invalid Decimal, {}", err))),
+            _ => Err(InputValueError::expected_type("Decimal", value)),
+        }
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(self.to_string().into())
+    }
+}
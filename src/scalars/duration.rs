@@ -0,0 +1,152 @@
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use chrono::Duration;
+
+/// Returns `n` units of `unit_secs` seconds, `None` if it is out of the range of `Duration`.
+fn checked_seconds(n: i64, unit_secs: i64) -> Option<Duration> {
+    let secs = n.checked_mul(unit_secs)?;
+    if secs > Duration::max_value().num_seconds() || secs < Duration::min_value().num_seconds() {
+        return None;
+    }
+    Some(Duration::seconds(secs))
+}
+
+/// Parses an ISO 8601 duration such as `P1DT2H30M`, `PT1.5S` or `-P2W`.
+///
+/// Years and months are rejected because their length is not fixed, and each designator can appear once, in the
+/// order `W`, `D`, `H`, `M` and `S`.
+fn parse_duration(s: &str) -> Option<Duration> {
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s),
+    };
+    let mut chars = s.strip_prefix('P')?;
+    if chars.is_empty() {
+        return None;
+    }
+
+    let mut duration = Duration::zero();
+    let mut in_time = false;
+    let mut last_rank = None;
+    while !chars.is_empty() {
+        if let Some(rest) = chars.strip_prefix('T') {
+            if in_time || rest.is_empty() {
+                return None;
+            }
+            in_time = true;
+            chars = rest;
+            continue;
+        }
+
+        let end = chars.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let (number, rest) = chars.split_at(end);
+        let designator = rest.chars().next()?;
+        chars = &rest[1..];
+
+        let rank = match (designator, in_time) {
+            ('W', false) => 0,
+            ('D', false) => 1,
+            ('H', true) => 2,
+            ('M', true) => 3,
+            ('S', true) => 4,
+            _ => return None,
+        };
+        if matches!(last_rank, Some(last_rank) if rank <= last_rank) {
+            return None;
+        }
+        last_rank = Some(rank);
+
+        if designator == 'S' {
+            let (secs, nanos) = match number.find('.') {
+                Some(idx) => {
+                    let frac = &number[idx + 1..];
+                    if frac.is_empty() || frac.len() > 9 {
+                        return None;
+                    }
+                    let nanos: i64 = frac.parse().ok()?;
+                    (&number[..idx], nanos * 10i64.pow(9 - frac.len() as u32))
+                }
+                None => (number, 0),
+            };
+            let secs: i64 = secs.parse().ok()?;
+            duration = duration
+                .checked_add(&checked_seconds(secs, 1)?)?
+                .checked_add(&Duration::nanoseconds(nanos))?;
+            continue;
+        }
+
+        let n: i64 = number.parse().ok()?;
+        let part = match designator {
+            'W' => checked_seconds(n, 7 * 24 * 3600)?,
+            'D' => checked_seconds(n, 24 * 3600)?,
+            'H' => checked_seconds(n, 3600)?,
+            _ => checked_seconds(n, 60)?,
+        };
+        duration = duration.checked_add(&part)?;
+    }
+
+    Some(if negative { -duration } else { duration })
+}
+
+#[Scalar(internal)]
+impl GQLScalar for Duration {
+    fn type_name() -> &'static str {
+        "Duration"
+    }
+
+    fn description() -> Option<&'static str> {
+        Some("An ISO 8601 duration, such as `P1DT2H30M` or `PT1.5S`.")
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => parse_duration(s)
+                .ok_or_else(|| InputValueError::custom(format!("invalid Duration \"{}\"", s))),
+            _ => Err(InputValueError::expected_type("Duration", value)),
+        }
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(self.to_string().into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_duration;
+    use chrono::Duration;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("PT0S"), Some(Duration::zero()));
+        assert_eq!(
+            parse_duration("P1DT2H30M"),
+            Some(Duration::days(1) + Duration::hours(2) + Duration::minutes(30))
+        );
+        assert_eq!(parse_duration("P2W"), Some(Duration::weeks(2)));
+        assert_eq!(parse_duration("PT1.5S"), Some(Duration::milliseconds(1500)));
+        assert_eq!(parse_duration("-P1D"), Some(Duration::days(-1)));
+        assert_eq!(parse_duration("P"), None);
+        assert_eq!(parse_duration("P1Y"), None);
+        assert_eq!(parse_duration("PT"), None);
+        assert_eq!(parse_duration("P1H"), None);
+        assert_eq!(parse_duration("PT1.S"), None);
+        assert_eq!(parse_duration("PT1H1H"), None);
+        assert_eq!(parse_duration("P1D2D"), None);
+        assert_eq!(parse_duration("PT1M1H"), None);
+        assert_eq!(parse_duration("PT1S1M"), None);
+        assert_eq!(parse_duration("P1DT1H1M1S"), Some(Duration::seconds(90061)));
+        assert_eq!(parse_duration("P9999999999999999W"), None);
+        assert_eq!(parse_duration("PT9223372036854775807S"), None);
+        assert_eq!(parse_duration("PT99999999999999999999S"), None);
+        assert_eq!(parse_duration("P106751991168D"), None);
+        assert_eq!(parse_duration("-PT9223372036854775H"), None);
+        assert_eq!(
+            parse_duration("P100000000D"),
+            Some(Duration::days(100_000_000))
+        );
+
+        let duration = Duration::days(3) + Duration::nanoseconds(120);
+        assert_eq!(parse_duration(&duration.to_string()), Some(duration));
+    }
+}
@@ -1,8 +1,10 @@
-use crate::{impl_scalar_internal, GQLScalar, Result, Value};
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
 
 macro_rules! impl_float_scalars {
     ($($ty:ty),*) => {
         $(
+        #[Scalar(internal)]
         impl GQLScalar for $ty {
             fn type_name() -> &'static str {
                 "Float"
@@ -12,11 +14,11 @@ macro_rules! impl_float_scalars {
                 Some("The `Float` scalar type represents signed double-precision fractional values as specified by [IEEE 754](https://en.wikipedia.org/wiki/IEEE_floating_point).")
             }
 
-            fn parse(value: &Value) -> Option<Self> {
+            fn parse(value: &Value) -> InputValueResult<Self> {
                 match value {
-                    Value::Int(n) => Some(n.as_i64().unwrap() as Self),
-                    Value::Float(n) => Some(*n as Self),
-                    _ => None
+                    Value::Int(n) => Ok(n.as_i64().unwrap() as Self),
+                    Value::Float(n) => Ok(*n as Self),
+                    _ => Err(InputValueError::expected_type("Float", value)),
                 }
             }
 
@@ -24,8 +26,6 @@ macro_rules! impl_float_scalars {
                 Ok((*self).into())
             }
         }
-
-        impl_scalar_internal!($ty);
         )*
     };
 }
@@ -1,4 +1,5 @@
-use crate::{impl_scalar_internal, GQLScalar, Result, Value};
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
 use std::ops::{Deref, DerefMut};
 
 /// ID scalar
@@ -21,16 +22,17 @@ impl DerefMut for ID {
     }
 }
 
+#[Scalar(internal)]
 impl GQLScalar for ID {
     fn type_name() -> &'static str {
         "ID"
     }
 
-    fn parse(value: &Value) -> Option<Self> {
+    fn parse(value: &Value) -> InputValueResult<Self> {
         match value {
-            Value::Int(n) => Some(ID(n.as_i64().unwrap().to_string())),
-            Value::String(s) => Some(ID(s.clone())),
-            _ => None,
+            Value::Int(n) => Ok(ID(n.as_i64().unwrap().to_string())),
+            Value::String(s) => Ok(ID(s.clone())),
+            _ => Err(InputValueError::expected_type("ID", value)),
         }
     }
 
@@ -38,5 +40,3 @@ impl GQLScalar for ID {
         Ok(self.0.clone().into())
     }
 }
-
-impl_scalar_internal!(ID);
@@ -0,0 +1,89 @@
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use std::ops::{Deref, DerefMut};
+
+fn parse_i64(type_name: &str, value: &Value) -> InputValueResult<i64> {
+    match value {
+        Value::Int(n) => Ok(n.as_i64().unwrap()),
+        Value::String(s) => s.parse().map_err(|_| {
+            InputValueError::custom(format!(
+                "{} cannot represent the value: \"{}\"",
+                type_name, s
+            ))
+        }),
+        _ => Err(InputValueError::expected_type(type_name, value)),
+    }
+}
+
+macro_rules! impl_int64_scalars {
+    ($($(#[$attr:meta])* $ty:ident = $name:expr, $desc:expr, |$n:ident| $to_json:expr;)*) => {
+        $(
+        $(#[$attr])*
+        #[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+        pub struct $ty(pub i64);
+
+        impl Deref for $ty {
+            type Target = i64;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl DerefMut for $ty {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.0
+            }
+        }
+
+        impl From<i64> for $ty {
+            fn from(n: i64) -> Self {
+                $ty(n)
+            }
+        }
+
+        impl From<$ty> for i64 {
+            fn from(n: $ty) -> Self {
+                n.0
+            }
+        }
+
+        #[Scalar(internal)]
+        impl GQLScalar for $ty {
+            fn type_name() -> &'static str {
+                $name
+            }
+
+            fn description() -> Option<&'static str> {
+                Some($desc)
+            }
+
+            fn parse(value: &Value) -> InputValueResult<Self> {
+                parse_i64($name, value).map($ty)
+            }
+
+            fn to_json(&self) -> Result<serde_json::Value> {
+                let $n = self.0;
+                Ok($to_json)
+            }
+        }
+        )*
+    };
+}
+
+impl_int64_scalars! {
+    /// Int64 scalar
+    ///
+    /// A 64-bit signed integer, the input is an integer or a string, and the output is a number.
+    Int64 = "Int64",
+        "The `Int64` scalar type represents non-fractional signed whole numeric values between -(2^63) and 2^63 - 1, it is serialized as a number.",
+        |n| n.into();
+
+    /// BigInt scalar
+    ///
+    /// A 64-bit signed integer, the input is an integer or a string, and the output is a string, for the clients
+    /// that cannot represent 64-bit integers as numbers, such as JavaScript.
+    BigInt = "BigInt",
+        "The `BigInt` scalar type represents non-fractional signed whole numeric values between -(2^63) and 2^63 - 1, it is serialized as a string.",
+        |n| n.to_string().into();
+}
@@ -1,8 +1,11 @@
-use crate::{impl_scalar_internal, GQLScalar, Result, Value};
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use std::convert::TryFrom;
 
 macro_rules! impl_integer_scalars {
     ($($ty:ty),*) => {
         $(
+        #[Scalar(internal)]
         impl GQLScalar for $ty {
             fn type_name() -> &'static str {
                 "Int"
@@ -12,19 +15,46 @@ macro_rules! impl_integer_scalars {
                 Some("The `Int` scalar type represents non-fractional signed whole numeric values. Int can represent values between -(2^31) and 2^31 - 1.")
             }
 
-            fn parse(value: &Value) -> Option<Self> {
+            fn parse(value: &Value) -> InputValueResult<Self> {
                 match value {
-                    Value::Int(n) => Some(n.as_i64().unwrap() as Self),
-                    _ => None
+                    Value::Int(n) => {
+                        let n = n.as_i64().unwrap();
+                        if i32::try_from(n).is_err() {
+                            return Err(InputValueError::custom(format!(
+                                "Int cannot represent non 32-bit signed integer value: {}",
+                                n
+                            )));
+                        }
+                        <Self as TryFrom<i64>>::try_from(n).map_err(|_| {
+                            InputValueError::custom(format!(
+                                "the value {} is out of range for \"{}\"",
+                                n,
+                                stringify!($ty)
+                            ))
+                        })
+                    }
+                    _ => Err(InputValueError::expected_type("Int", value)),
+                }
+            }
+
+            fn is_valid(value: &Value) -> bool {
+                // All the integer types share the `Int` type of the registry, so only the range of `Int` is checked.
+                match value {
+                    Value::Int(n) => i32::try_from(n.as_i64().unwrap()).is_ok(),
+                    _ => false,
                 }
             }
 
             fn to_json(&self) -> Result<serde_json::Value> {
-                Ok((*self).into())
+                match i32::try_from(*self) {
+                    Ok(n) => Ok(n.into()),
+                    Err(_) => anyhow::bail!(
+                        "Int cannot represent non 32-bit signed integer value: {}",
+                        self
+                    ),
+                }
             }
         }
-
-        impl_scalar_internal!($ty);
         )*
     };
 }
@@ -0,0 +1,131 @@
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::ops::{Deref, DerefMut};
+
+const JSON_DESC: &str = "The `JSON` scalar type represents arbitrary JSON values.";
+
+/// Converts an input value into a JSON value, the enum values are converted into strings.
+pub(crate) fn gql_value_to_json_value(value: &Value) -> serde_json::Value {
+    match value {
+        Value::Variable(_) | Value::Null => serde_json::Value::Null,
+        Value::Int(n) => n.as_i64().unwrap().into(),
+        Value::Float(n) => (*n).into(),
+        Value::String(s) => s.clone().into(),
+        Value::Boolean(b) => (*b).into(),
+        Value::Enum(name) => name.clone().into(),
+        Value::List(values) => values.iter().map(gql_value_to_json_value).collect(),
+        Value::Object(obj) => serde_json::Value::Object(
+            obj.iter()
+                .map(|(name, value)| (name.clone(), gql_value_to_json_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[Scalar(internal)]
+impl GQLScalar for serde_json::Value {
+    fn type_name() -> &'static str {
+        "JSON"
+    }
+
+    fn description() -> Option<&'static str> {
+        Some(JSON_DESC)
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        Ok(gql_value_to_json_value(value))
+    }
+
+    fn is_valid(_value: &Value) -> bool {
+        true
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(self.clone())
+    }
+}
+
+/// JSON scalar
+///
+/// A scalar that accepts and returns arbitrary JSON values, converted from and to `T` with serde.
+/// Use `serde_json::Value` directly for untyped values.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use serde_derive::{Deserialize, Serialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Metadata {
+///     tags: Vec<String>,
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     #[field]
+///     async fn tags(&self, metadata: Json<Metadata>) -> Json<Vec<String>> {
+///         Json(metadata.0.tags)
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+///     assert_eq!(
+///         schema.query(r#"{ tags(metadata: { tags: ["a", "b"] }) }"#).execute().await.unwrap(),
+///         serde_json::json!({ "tags": ["a", "b"] })
+///     );
+/// }
+/// ```
+#[derive(Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Json<T>(pub T);
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Json<T> {
+    fn from(value: T) -> Self {
+        Json(value)
+    }
+}
+
+#[Scalar(internal)]
+impl<T: Serialize + DeserializeOwned + Send> GQLScalar for Json<T> {
+    fn type_name() -> &'static str {
+        "JSON"
+    }
+
+    fn description() -> Option<&'static str> {
+        Some(JSON_DESC)
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        serde_json::from_value(gql_value_to_json_value(value))
+            .map(Json)
+            .map_err(|err| InputValueError::custom(format!("invalid JSON value, {}", err)))
+    }
+
+    fn is_valid(_value: &Value) -> bool {
+        true
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(serde_json::to_value(&self.0)?)
+    }
+}
@@ -1,19 +1,40 @@
+mod any;
 mod bool;
 mod floats;
 mod id;
+mod int64;
 mod integers;
+mod json;
 mod string;
 
+#[cfg(feature = "base64")]
+mod base64;
+#[cfg(feature = "bson")]
+mod bson;
+#[cfg(feature = "chrono")]
+mod chrono_naive;
 #[cfg(feature = "chrono")]
 mod datetime;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+#[cfg(feature = "chrono")]
+mod duration;
+#[cfg(feature = "url")]
+mod url;
 #[cfg(feature = "uuid")]
 mod uuid;
 
+pub(crate) use any::Any;
 pub use id::ID;
+pub use int64::{BigInt, Int64};
+pub use json::Json;
+
+#[cfg(feature = "base64")]
+pub use self::base64::Base64;
 
 #[cfg(test)]
 mod tests {
-    use super::ID;
+    use super::{BigInt, Int64, Json, ID};
     use crate::GQLType;
     use chrono::{DateTime, Utc};
     use uuid::Uuid;
@@ -38,6 +59,21 @@ mod tests {
         assert_eq!(<ID as GQLType>::type_name(), "ID");
         assert_eq!(<ID as GQLType>::qualified_type_name(), "ID!");
 
+        assert_eq!(<Int64 as GQLType>::type_name(), "Int64");
+        assert_eq!(<Int64 as GQLType>::qualified_type_name(), "Int64!");
+
+        assert_eq!(<BigInt as GQLType>::type_name(), "BigInt");
+        assert_eq!(<BigInt as GQLType>::qualified_type_name(), "BigInt!");
+
+        assert_eq!(<Json<Vec<i32>> as GQLType>::type_name(), "JSON");
+        assert_eq!(<Json<Vec<i32>> as GQLType>::qualified_type_name(), "JSON!");
+
+        assert_eq!(<serde_json::Value as GQLType>::type_name(), "JSON");
+        assert_eq!(
+            <serde_json::Value as GQLType>::qualified_type_name(),
+            "JSON!"
+        );
+
         #[cfg(feature = "chrono")]
         {
             assert_eq!(<DateTime::<Utc> as GQLType>::type_name(), "DateTime");
@@ -45,6 +81,13 @@ mod tests {
                 <DateTime::<Utc> as GQLType>::qualified_type_name(),
                 "DateTime!"
             );
+            assert_eq!(<chrono::NaiveDate as GQLType>::type_name(), "NaiveDate");
+            assert_eq!(<chrono::NaiveTime as GQLType>::type_name(), "NaiveTime");
+            assert_eq!(
+                <chrono::NaiveDateTime as GQLType>::type_name(),
+                "NaiveDateTime"
+            );
+            assert_eq!(<chrono::Duration as GQLType>::type_name(), "Duration");
         }
 
         #[cfg(feature = "uuid")]
@@ -52,5 +95,35 @@ mod tests {
             assert_eq!(<Uuid as GQLType>::type_name(), "UUID");
             assert_eq!(<Uuid as GQLType>::qualified_type_name(), "UUID!");
         }
+
+        #[cfg(feature = "url")]
+        {
+            assert_eq!(<url::Url as GQLType>::type_name(), "Url");
+            assert_eq!(<url::Url as GQLType>::qualified_type_name(), "Url!");
+        }
+
+        #[cfg(feature = "rust_decimal")]
+        {
+            assert_eq!(<rust_decimal::Decimal as GQLType>::type_name(), "Decimal");
+            assert_eq!(
+                <rust_decimal::Decimal as GQLType>::qualified_type_name(),
+                "Decimal!"
+            );
+        }
+
+        #[cfg(feature = "bson")]
+        {
+            assert_eq!(<bson::oid::ObjectId as GQLType>::type_name(), "ObjectId");
+            assert_eq!(
+                <bson::oid::ObjectId as GQLType>::qualified_type_name(),
+                "ObjectId!"
+            );
+        }
+
+        #[cfg(feature = "base64")]
+        {
+            assert_eq!(<super::Base64 as GQLType>::type_name(), "Base64");
+            assert_eq!(<super::Base64 as GQLType>::qualified_type_name(), "Base64!");
+        }
     }
 }
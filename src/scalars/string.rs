@@ -1,11 +1,13 @@
 use crate::{
-    impl_scalar_internal, registry, ContextSelectionSet, GQLOutputValue, GQLScalar, GQLType,
-    Result, Value,
+    registry, ContextSelectionSet, GQLOutputValue, GQLScalar, GQLType, InputValueError,
+    InputValueResult, Result, Value,
 };
+use async_graphql_derive::Scalar;
 use std::borrow::Cow;
 
 const STRING_DESC:&'static str = "The `String` scalar type represents textual data, represented as UTF-8 character sequences. The String type is most often used by GraphQL to represent free-form human-readable text.";
 
+#[Scalar(internal)]
 impl GQLScalar for String {
     fn type_name() -> &'static str {
         "String"
@@ -15,10 +17,10 @@ impl GQLScalar for String {
         Some(STRING_DESC)
     }
 
-    fn parse(value: &Value) -> Option<Self> {
+    fn parse(value: &Value) -> InputValueResult<Self> {
         match value {
-            Value::String(s) => Some(s.clone()),
-            _ => None,
+            Value::String(s) => Ok(s.clone()),
+            _ => Err(InputValueError::expected_type("String", value)),
         }
     }
 
@@ -34,8 +36,6 @@ impl GQLScalar for String {
     }
 }
 
-impl_scalar_internal!(String);
-
 impl<'a> GQLType for &'a str {
     fn type_name() -> Cow<'static, str> {
         Cow::Borrowed("String")
@@ -44,11 +44,13 @@ impl<'a> GQLType for &'a str {
     fn create_type_info(registry: &mut registry::Registry) -> String {
         registry.create_type::<Self, _>(|_| registry::Type::Scalar {
             name: Self::type_name().to_string(),
-            description: Some(STRING_DESC),
+            description: Some(STRING_DESC.into()),
             is_valid: |value| match value {
                 Value::String(_) => true,
                 _ => false,
             },
+            parse: |value| <String as GQLScalar>::parse(value).map(|_| ()),
+            specified_by_url: None,
         })
     }
 }
@@ -0,0 +1,22 @@
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
+use url::Url;
+
+#[Scalar(internal)]
+impl GQLScalar for Url {
+    fn type_name() -> &'static str {
+        "Url"
+    }
+
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        match value {
+            Value::String(s) => Url::parse(s)
+                .map_err(|err| InputValueError::custom(format!("invalid Url, {}", err))),
+            _ => Err(InputValueError::expected_type("Url", value)),
+        }
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value> {
+        Ok(self.as_str().into())
+    }
+}
@@ -1,15 +1,18 @@
-use crate::{impl_scalar_internal, GQLScalar, Result, Value};
+use crate::{GQLScalar, InputValueError, InputValueResult, Result, Value};
+use async_graphql_derive::Scalar;
 use uuid::Uuid;
 
+#[Scalar(internal)]
 impl GQLScalar for Uuid {
     fn type_name() -> &'static str {
         "UUID"
     }
 
-    fn parse(value: &Value) -> Option<Self> {
+    fn parse(value: &Value) -> InputValueResult<Self> {
         match value {
-            Value::String(s) => Some(Uuid::parse_str(&s).ok()?),
-            _ => None,
+            Value::String(s) => Uuid::parse_str(&s)
+                .map_err(|err| InputValueError::custom(format!("invalid UUID, {}", err))),
+            _ => Err(InputValueError::expected_type("UUID", value)),
         }
     }
 
@@ -17,5 +20,3 @@ impl GQLScalar for Uuid {
         Ok(self.to_string().into())
     }
 }
-
-impl_scalar_internal!(Uuid);
@@ -1,15 +1,33 @@
 use crate::context::Data;
+use crate::incremental::IncrementalExecution;
 use crate::model::__DirectiveLocation;
 use crate::registry::{Directive, InputValue, Registry};
+use crate::resolver::{resolve_object, ResolveObject};
+use crate::timer::{with_deadline, Timer};
 use crate::types::QueryRoot;
-use crate::validation::check_rules;
+use crate::validation::{check_default_values, check_rules, check_sources, coerce_variables};
 use crate::{
-    ContextBase, GQLObject, GQLOutputValue, GQLType, QueryError, QueryParseError, Result, Variables,
+    CacheControl, ContextBase, ContextSelectionSet, GQLObject, GQLType, IncrementalResponse,
+    QueryError, QueryParseError, Result, SchemaError, Value, Variables,
 };
+use futures::future::Either;
+use futures::StreamExt;
 use graphql_parser::parse_query;
-use graphql_parser::query::{Definition, OperationDefinition};
+use graphql_parser::query::{Definition, Document, OperationDefinition};
 use std::any::Any;
 use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+#[cfg(feature = "tracing")]
+use tracing::Instrument;
+
+/// Whether the introspection queries are allowed.
+pub(crate) enum Introspection {
+    Enabled,
+    Disabled,
+    Predicate(Box<dyn Fn(&Data) -> bool + Send + Sync>),
+}
 
 /// GraphQL schema
 pub struct Schema<Query, Mutation> {
@@ -17,6 +35,9 @@ pub struct Schema<Query, Mutation> {
     mutation: Mutation,
     registry: Registry,
     data: Data,
+    introspection: Introspection,
+    timer: Timer,
+    timeout: Option<Duration>,
 }
 
 impl<Query: GQLObject, Mutation: GQLObject> Schema<Query, Mutation> {
@@ -24,76 +45,75 @@ impl<Query: GQLObject, Mutation: GQLObject> Schema<Query, Mutation> {
     ///
     /// The root object for the query and Mutation needs to be specified.
     /// If there is no mutation, you can use `GQLEmptyMutation`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the types are invalid, see `Schema::try_new` to handle it as an error.
     pub fn new(query: Query, mutation: Mutation) -> Self {
-        let mut registry = Registry {
-            types: Default::default(),
-            directives: Default::default(),
-            implements: Default::default(),
-            query_type: Query::type_name().to_string(),
-            mutation_type: if Mutation::is_empty() {
+        match Self::try_new(query, mutation) {
+            Ok(schema) => schema,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Create a schema, returns a `SchemaError` if a field of a merged object is defined twice, or if the default value
+    /// of an argument or of an input object field does not match its type.
+    pub fn try_new(query: Query, mutation: Mutation) -> std::result::Result<Self, SchemaError> {
+        let mut registry = create_registry(
+            Query::type_name().to_string(),
+            if Mutation::is_empty() {
                 None
             } else {
                 Some(Mutation::type_name().to_string())
             },
-        };
-
-        registry.add_directive(Directive {
-            name: "include",
-            description: Some("Directs the executor to include this field or fragment only when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = HashMap::new();
-                args.insert("if", InputValue {
-                    name: "if",
-                    description: Some("Included when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None
-                });
-                args
-            }
-        });
-
-        registry.add_directive(Directive {
-            name: "skip",
-            description: Some("Directs the executor to skip this field or fragment when the `if` argument is true."),
-            locations: vec![
-                __DirectiveLocation::FIELD,
-                __DirectiveLocation::FRAGMENT_SPREAD,
-                __DirectiveLocation::INLINE_FRAGMENT
-            ],
-            args: {
-                let mut args = HashMap::new();
-                args.insert("if", InputValue {
-                    name: "if",
-                    description: Some("Skipped when true."),
-                    ty: "Boolean!".to_string(),
-                    default_value: None
-                });
-                args
-            }
-        });
-
-        // register scalars
-        bool::create_type_info(&mut registry);
-        i32::create_type_info(&mut registry);
-        f32::create_type_info(&mut registry);
-        String::create_type_info(&mut registry);
+        );
 
         QueryRoot::<Query>::create_type_info(&mut registry);
         if !Mutation::is_empty() {
             Mutation::create_type_info(&mut registry);
         }
 
-        Self {
-            query: QueryRoot { inner: query },
+        if let Some(err) = registry.errors.first() {
+            return Err(SchemaError(err.clone()));
+        }
+        if let Some(err) = check_default_values(&registry) {
+            return Err(SchemaError(err));
+        }
+
+        Ok(Self {
+            query: QueryRoot {
+                inner: query,
+                federation: false,
+            },
             mutation,
             registry,
             data: Default::default(),
-        }
+            introspection: Introspection::Enabled,
+            timer: Timer::default(),
+            timeout: None,
+        })
+    }
+
+    /// Enable the Apollo Federation support.
+    ///
+    /// It adds the `_service { sdl }` field to the query type, and if there are entities, the
+    /// `_entities(representations: [_Any!]!)` field and the `_Entity` union of the object types that have an entity
+    /// resolver.
+    pub fn enable_federation(mut self) -> Self {
+        QueryRoot::<Query>::create_federation_types(&mut self.registry);
+        self.query.federation = true;
+        self
+    }
+
+    /// Returns the SDL of the schema.
+    pub fn sdl(&self) -> String {
+        self.registry.export_sdl(false)
+    }
+
+    /// Returns the registry of the types of the schema, it can be compared with another one by
+    /// `diff::diff_registry`.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
     }
 
     /// Add a global data that can be accessed in the `Context`.
@@ -102,46 +122,176 @@ impl<Query: GQLObject, Mutation: GQLObject> Schema<Query, Mutation> {
         self
     }
 
+    /// Set the maximum duration of the execution of the queries, it can be overridden by `QueryBuilder::timeout`.
+    ///
+    /// When it is exceeded, the resolvers that are still running are dropped and `QueryError::Timeout` is returned.
+    /// The timer does not depend on an async runtime.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Disable introspection queries (`__schema` and `__type`), they will fail validation.
+    pub fn disable_introspection(mut self) -> Self {
+        self.introspection = Introspection::Disabled;
+        self
+    }
+
+    /// Only allow introspection queries when `f` returns true.
+    ///
+    /// The argument of `f` is the data of the current request, added with `QueryBuilder::data`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct StaffToken;
+    ///
+    /// struct QueryRoot;
+    ///
+    /// #[Object]
+    /// impl QueryRoot {}
+    ///
+    /// #[async_std::main]
+    /// async fn main() {
+    ///     let schema = Schema::new(QueryRoot, GQLEmptyMutation)
+    ///         .introspection_predicate(|data| data.get::<StaffToken>().is_some());
+    ///     let query = "{ __type(name: \"QueryRoot\") { name } }";
+    ///     assert!(schema.query(query).execute().await.is_err());
+    ///     assert!(schema.query(query).data(StaffToken).execute().await.is_ok());
+    /// }
+    /// ```
+    pub fn introspection_predicate<F: Fn(&Data) -> bool + Send + Sync + 'static>(
+        mut self,
+        f: F,
+    ) -> Self {
+        self.introspection = Introspection::Predicate(Box::new(f));
+        self
+    }
+
+    /// Validate a client document against the schema without executing it.
+    ///
+    /// The document can be split into several sources, such as the operations and the fragments they use defined
+    /// in separate files, their definitions are merged into one document. The document can contain multiple
+    /// operations. The variables are not validated, because their values are not known. The introspection fields
+    /// are allowed unless the introspection is disabled with `disable_introspection`.
+    ///
+    /// Returns `QueryParseError` with the index of the source that cannot be parsed, or `RuleErrors` with all the
+    /// errors of the validation rules. The `source` of a `RuleError` is the index of the source where it is, and its
+    /// locations are relative to that source.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use async_graphql::*;
+    ///
+    /// struct QueryRoot;
+    ///
+    /// #[Object]
+    /// impl QueryRoot {
+    ///     #[field]
+    ///     async fn value(&self) -> i32 {
+    ///         10
+    ///     }
+    /// }
+    ///
+    /// let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+    /// assert!(schema.validate(&["query A { ...Value }", "fragment Value on QueryRoot { value }"]).is_ok());
+    ///
+    /// let err = schema.validate(&["{ value unknown }"]).unwrap_err();
+    /// let errors = &err.downcast_ref::<RuleErrors>().unwrap().errors;
+    /// assert_eq!(errors.len(), 1);
+    /// ```
+    pub fn validate(&self, sources: &[&str]) -> Result<()> {
+        check_sources(
+            &self.registry,
+            sources,
+            !matches!(self.introspection, Introspection::Disabled),
+        )
+    }
+
     /// Start a query and return `QueryBuilder`.
     pub fn query<'a>(&'a self, query_source: &'a str) -> QueryBuilder<'a, Query, Mutation> {
         QueryBuilder {
             query: &self.query,
             mutation: &self.mutation,
-            registry: &self.registry,
-            query_source,
-            operation_name: None,
-            variables: None,
-            data: &self.data,
+            options: QueryOptions {
+                registry: &self.registry,
+                query_source,
+                operation_name: None,
+                variables: None,
+                data: &self.data,
+                ctx_data: None,
+                introspection: &self.introspection,
+                timer: &self.timer,
+                timeout: self.timeout,
+            },
         }
     }
 }
 
+/// The response of `QueryBuilder::execute_response`.
+#[derive(Debug)]
+pub struct QueryResponse {
+    /// The data of the response.
+    pub data: serde_json::Value,
+
+    /// The cache control hints of the types and fields that were resolved.
+    pub cache_control: CacheControl,
+}
+
 /// Query builder
 pub struct QueryBuilder<'a, Query, Mutation> {
     query: &'a QueryRoot<Query>,
     mutation: &'a Mutation,
-    registry: &'a Registry,
-    query_source: &'a str,
-    operation_name: Option<&'a str>,
-    variables: Option<&'a Variables>,
-    data: &'a Data,
+    options: QueryOptions<'a>,
 }
 
 impl<'a, Query, Mutation> QueryBuilder<'a, Query, Mutation> {
     /// Specify the operation name.
-    pub fn operator_name(self, name: &'a str) -> Self {
-        QueryBuilder {
-            operation_name: Some(name),
-            ..self
-        }
+    pub fn operator_name(mut self, name: &'a str) -> Self {
+        self.options.operation_name = Some(name);
+        self
     }
 
     /// Specify the variables.
-    pub fn variables(self, vars: &'a Variables) -> Self {
-        QueryBuilder {
-            variables: Some(vars),
-            ..self
-        }
+    pub fn variables(mut self, vars: &'a Variables) -> Self {
+        self.options.variables = Some(vars);
+        self
+    }
+
+    /// Add a data of the current request that can be accessed in the `Context`.
+    ///
+    /// It takes precedence over the global data of the same type defined in the `Schema`.
+    pub fn data<D: Any + Send + Sync>(mut self, data: D) -> Self {
+        self.options.add_data(data);
+        self
+    }
+
+    /// Set the maximum duration of the execution of this query, it takes precedence over `Schema::timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = Some(timeout);
+        self
+    }
+
+    /// Execute the query with incremental delivery.
+    ///
+    /// The fragments with `@defer`, and the items after the first `initialCount` ones of the list fields with
+    /// `@stream`, are delivered by the patches that follow the initial payload.
+    ///
+    /// The initial payload is returned as soon as the rest of the selection set is resolved, the deferred fragments and
+    /// streamed items are resolved while the patches are consumed, and dropping the patches cancels them. A deferred
+    /// fragment or a streamed item that times out is delivered as an error patch. The directives are ignored by
+    /// mutations.
+    pub async fn execute_incremental(self) -> Result<IncrementalResponse<'a>>
+    where
+        Query: GQLObject + Send + Sync,
+        Mutation: GQLObject + Send + Sync,
+    {
+        self.options
+            .execute_incremental(self.query, Some(self.mutation))
+            .await
     }
 
     /// Execute the query.
@@ -150,73 +300,424 @@ impl<'a, Query, Mutation> QueryBuilder<'a, Query, Mutation> {
         Query: GQLObject + Send + Sync,
         Mutation: GQLObject + Send + Sync,
     {
-        let document =
-            parse_query(self.query_source).map_err(|err| QueryParseError(err.to_string()))?;
-        let mut fragments = HashMap::new();
+        self.execute_response().await.map(|response| response.data)
+    }
 
-        check_rules(self.registry, &document)?;
+    /// Execute the query, and return the data with the cache control hints of the resolved fields.
+    pub async fn execute_response(self) -> Result<QueryResponse>
+    where
+        Query: GQLObject + Send + Sync,
+        Mutation: GQLObject + Send + Sync,
+    {
+        self.options
+            .execute_response(self.query, Some(self.mutation))
+            .await
+    }
+}
 
-        for definition in &document.definitions {
-            if let Definition::Fragment(fragment) = definition {
-                fragments.insert(fragment.name.clone(), fragment);
-            }
+/// The options of a query and the execution of its operation, it is shared by the query builders of the statically
+/// typed schemas and of the dynamic ones, which only differ by their root objects.
+pub(crate) struct QueryOptions<'a> {
+    pub(crate) registry: &'a Registry,
+    pub(crate) query_source: &'a str,
+    pub(crate) operation_name: Option<&'a str>,
+    pub(crate) variables: Option<&'a Variables>,
+    pub(crate) data: &'a Data,
+    pub(crate) ctx_data: Option<Data>,
+    pub(crate) introspection: &'a Introspection,
+    pub(crate) timer: &'a Timer,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl<'a> QueryOptions<'a> {
+    pub(crate) fn add_data<D: Any + Send + Sync>(&mut self, data: D) {
+        self.ctx_data
+            .get_or_insert_with(Default::default)
+            .insert(data);
+    }
+
+    fn allow_introspection(&self) -> bool {
+        match self.introspection {
+            Introspection::Enabled => true,
+            Introspection::Disabled => false,
+            Introspection::Predicate(f) => match &self.ctx_data {
+                Some(ctx_data) => f(ctx_data),
+                None => f(&Data::default()),
+            },
         }
+    }
 
-        for definition in &document.definitions {
-            match definition {
-                Definition::Operation(OperationDefinition::SelectionSet(selection_set)) => {
-                    if self.operation_name.is_none() {
-                        let ctx = ContextBase {
-                            item: selection_set,
-                            variables: self.variables.as_deref(),
-                            variable_definitions: None,
-                            registry: &self.registry,
-                            data: self.data,
-                            fragments: &fragments,
-                        };
-                        return GQLOutputValue::resolve(self.query, &ctx).await;
-                    }
+    /// Returns the index of the operation to execute in the definitions of the document.
+    fn find_operation(&self, document: &Document) -> Option<usize> {
+        document
+            .definitions
+            .iter()
+            .position(|definition| match definition {
+                Definition::Operation(OperationDefinition::SelectionSet(_)) => {
+                    self.operation_name.is_none()
                 }
                 Definition::Operation(OperationDefinition::Query(query)) => {
-                    if self.operation_name.is_none()
-                        || self.operation_name == query.name.as_ref().map(|s| s.as_str())
-                    {
-                        let ctx = ContextBase {
-                            item: &query.selection_set,
-                            variables: self.variables.as_deref(),
-                            variable_definitions: Some(&query.variable_definitions),
-                            registry: self.registry.clone(),
-                            data: self.data,
-                            fragments: &fragments,
-                        };
-                        return GQLOutputValue::resolve(self.query, &ctx).await;
-                    }
+                    self.operation_name.is_none() || self.operation_name == query.name.as_deref()
                 }
                 Definition::Operation(OperationDefinition::Mutation(mutation)) => {
-                    if self.operation_name.is_none()
-                        || self.operation_name == mutation.name.as_ref().map(|s| s.as_str())
-                    {
-                        let ctx = ContextBase {
-                            item: &mutation.selection_set,
-                            variables: self.variables.as_deref(),
-                            variable_definitions: Some(&mutation.variable_definitions),
-                            registry: self.registry.clone(),
-                            data: self.data,
-                            fragments: &fragments,
-                        };
-                        return GQLOutputValue::resolve(self.mutation, &ctx).await;
-                    }
+                    self.operation_name.is_none() || self.operation_name == mutation.name.as_deref()
+                }
+                _ => false,
+            })
+    }
+
+    /// Execute the query with incremental delivery, the `mutation` is `None` if the schema has no mutation type.
+    pub(crate) async fn execute_incremental<Q, M>(
+        self,
+        query: Q,
+        mutation: Option<M>,
+    ) -> Result<IncrementalResponse<'a>>
+    where
+        Q: Deref + Send + Sync + 'a,
+        Q::Target: ResolveObject,
+        M: Deref + Send + Sync,
+        M::Target: ResolveObject,
+    {
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let document = trace_error(
+            parse_query(self.query_source).map_err(|err| QueryParseError(err.to_string()).into()),
+        )?;
+        let operation = self.find_operation(&document);
+        let variable_definitions = match operation.map(|idx| &document.definitions[idx]) {
+            Some(Definition::Operation(OperationDefinition::Query(query))) => {
+                Some(&query.variable_definitions)
+            }
+            Some(Definition::Operation(OperationDefinition::SelectionSet(_))) => None,
+            _ => {
+                return Ok(IncrementalResponse {
+                    data: self.execute_response(query, mutation).await?.data,
+                    has_next: false,
+                    patches: futures::stream::empty().boxed(),
+                })
+            }
+        };
+
+        trace_error(check_rules(
+            self.registry,
+            &document,
+            self.variables,
+            self.allow_introspection(),
+        ))?;
+        let variables = match variable_definitions {
+            Some(variable_definitions) => Some(trace_error(coerce_variables(
+                self.registry,
+                variable_definitions,
+                self.variables,
+            ))?),
+            None => None,
+        };
+
+        trace_error(
+            IncrementalExecution {
+                query,
+                registry: self.registry,
+                data: self.data,
+                ctx_data: self.ctx_data,
+                variables,
+                document,
+                operation: operation.unwrap(),
+                timer: self.timer,
+                deadline,
+            }
+            .execute()
+            .await,
+        )
+    }
+
+    /// Execute the query, the `mutation` is `None` if the schema has no mutation type.
+    pub(crate) async fn execute_response<Q, M>(
+        self,
+        query: Q,
+        mutation: Option<M>,
+    ) -> Result<QueryResponse>
+    where
+        Q: Deref,
+        Q::Target: ResolveObject,
+        M: Deref,
+        M::Target: ResolveObject,
+    {
+        let cache_control = Mutex::new(None);
+        let deadline = self.timeout.map(|timeout| Instant::now() + timeout);
+        let document = trace_error(
+            parse_query(self.query_source).map_err(|err| QueryParseError(err.to_string()).into()),
+        )?;
+
+        trace_error(check_rules(
+            self.registry,
+            &document,
+            self.variables,
+            self.allow_introspection(),
+        ))?;
+
+        let fragments = document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Fragment(fragment) => Some((fragment.name.clone(), fragment)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+
+        let (is_mutation, operation_name, selection_set, variable_definitions) = match self
+            .find_operation(&document)
+            .map(|idx| &document.definitions[idx])
+        {
+            Some(Definition::Operation(OperationDefinition::SelectionSet(selection_set))) => {
+                (false, None, selection_set, None)
+            }
+            Some(Definition::Operation(OperationDefinition::Query(query))) => (
+                false,
+                query.name.as_deref(),
+                &query.selection_set,
+                Some(&query.variable_definitions),
+            ),
+            Some(Definition::Operation(OperationDefinition::Mutation(mutation))) => (
+                true,
+                mutation.name.as_deref(),
+                &mutation.selection_set,
+                Some(&mutation.variable_definitions),
+            ),
+            _ => {
+                if let Some(operation_name) = self.operation_name {
+                    anyhow::bail!(QueryError::UnknownOperationNamed {
+                        name: operation_name.to_string()
+                    });
                 }
-                _ => {}
+                return Ok(QueryResponse {
+                    data: serde_json::Value::Null,
+                    cache_control: CacheControl::default(),
+                });
             }
+        };
+
+        let coerced_variables;
+        let variables = match variable_definitions {
+            Some(variable_definitions) => {
+                coerced_variables = trace_error(coerce_variables(
+                    self.registry,
+                    variable_definitions,
+                    self.variables,
+                ))?;
+                Some(&coerced_variables)
+            }
+            None => self.variables,
+        };
+        let ctx = ContextBase {
+            path_node: None,
+            item: selection_set,
+            variables,
+            variable_definitions: variable_definitions.map(Vec::as_slice),
+            registry: self.registry,
+            data: self.data,
+            ctx_data: self.ctx_data.as_ref(),
+            fragments: &fragments,
+            incremental: None,
+            initial: None,
+            stream: None,
+            cache_control: &cache_control,
+        };
+
+        let res = if is_mutation {
+            let mutation = match &mutation {
+                Some(mutation) => mutation,
+                None => anyhow::bail!(QueryError::NotConfiguredMutations),
+            };
+            Either::Left(resolve_root(&ctx, &**mutation))
+        } else {
+            Either::Right(resolve_root(&ctx, &*query))
+        };
+        #[cfg(feature = "tracing")]
+        let res = res.instrument(tracing::info_span!(
+            "operation",
+            operation_type = if is_mutation { "mutation" } else { "query" },
+            operation_name = operation_name,
+        ));
+        #[cfg(not(feature = "tracing"))]
+        let _ = operation_name;
+        let data = trace_error(with_deadline(self.timer, deadline, res).await)?;
+
+        Ok(QueryResponse {
+            data,
+            // The result of a mutation is never cached.
+            cache_control: if is_mutation {
+                CacheControl::default()
+            } else {
+                cache_control.into_inner().unwrap().unwrap_or_default()
+            },
+        })
+    }
+}
+
+/// Resolve the selection set of the root object of an operation.
+async fn resolve_root<T: ResolveObject + ?Sized>(
+    ctx: &ContextSelectionSet<'_>,
+    root: &T,
+) -> Result<serde_json::Value> {
+    let mut result = serde_json::Map::new();
+    resolve_object(ctx, root, &mut result).await?;
+    Ok(result.into())
+}
+
+/// Create a registry with the built-in directives and scalars.
+pub(crate) fn create_registry(query_type: String, mutation_type: Option<String>) -> Registry {
+    let mut registry = Registry {
+        types: Default::default(),
+        directives: Default::default(),
+        implements: Default::default(),
+        query_type,
+        mutation_type,
+        errors: Vec::new(),
+    };
+
+    registry.add_directive(Directive {
+        name: "include",
+        description: Some("Directs the executor to include this field or fragment only when the `if` argument is true."),
+        locations: vec![
+            __DirectiveLocation::FIELD,
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT
+        ],
+        args: {
+            let mut args = HashMap::new();
+            args.insert("if".into(), InputValue {
+                name: "if".into(),
+                description: Some("Included when true.".into()),
+                ty: "Boolean!".to_string(),
+                default_value: None,
+                validator: None,
+            });
+            args
+        }
+    });
+
+    registry.add_directive(Directive {
+        name: "skip",
+        description: Some(
+            "Directs the executor to skip this field or fragment when the `if` argument is true.",
+        ),
+        locations: vec![
+            __DirectiveLocation::FIELD,
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT,
+        ],
+        args: {
+            let mut args = HashMap::new();
+            args.insert(
+                "if".into(),
+                InputValue {
+                    name: "if".into(),
+                    description: Some("Skipped when true.".into()),
+                    ty: "Boolean!".to_string(),
+                    default_value: None,
+                    validator: None,
+                },
+            );
+            args
+        },
+    });
+
+    registry.add_directive(Directive {
+        name: "specifiedBy",
+        description: Some("Exposes a URL that specifies the behaviour of this scalar."),
+        locations: vec![__DirectiveLocation::SCALAR],
+        args: {
+            let mut args = HashMap::new();
+            args.insert(
+                "url".into(),
+                InputValue {
+                    name: "url".into(),
+                    description: Some(
+                        "The URL that specifies the behaviour of this scalar.".into(),
+                    ),
+                    ty: "String!".to_string(),
+                    default_value: None,
+                    validator: None,
+                },
+            );
+            args
+        },
+    });
+
+    registry.add_directive(Directive {
+        name: "defer",
+        description: Some("Directs the executor to deliver this fragment in a subsequent payload of an incremental response."),
+        locations: vec![
+            __DirectiveLocation::FRAGMENT_SPREAD,
+            __DirectiveLocation::INLINE_FRAGMENT
+        ],
+        args: {
+            let mut args = HashMap::new();
+            args.insert("if".into(), InputValue {
+                name: "if".into(),
+                description: Some("Deferred when true.".into()),
+                ty: "Boolean".to_string(),
+                default_value: Some(Value::Boolean(true)),
+                validator: None,
+            });
+            args.insert("label".into(), InputValue {
+                name: "label".into(),
+                description: Some("A label to identify the subsequent payloads of the fragment.".into()),
+                ty: "String".to_string(),
+                default_value: None,
+                validator: None,
+            });
+            args
         }
+    });
 
-        if let Some(operation_name) = self.operation_name {
-            anyhow::bail!(QueryError::UnknownOperationNamed {
-                name: operation_name.to_string()
+    registry.add_directive(Directive {
+        name: "stream",
+        description: Some("Directs the executor to deliver the items of this list field after the first `initialCount` ones in subsequent payloads of an incremental response."),
+        locations: vec![__DirectiveLocation::FIELD],
+        args: {
+            let mut args = HashMap::new();
+            args.insert("if".into(), InputValue {
+                name: "if".into(),
+                description: Some("Streamed when true.".into()),
+                ty: "Boolean".to_string(),
+                default_value: Some(Value::Boolean(true)),
+                validator: None,
+            });
+            args.insert("label".into(), InputValue {
+                name: "label".into(),
+                description: Some("A label to identify the subsequent payloads of the field.".into()),
+                ty: "String".to_string(),
+                default_value: None,
+                validator: None,
             });
+            args.insert("initialCount".into(), InputValue {
+                name: "initialCount".into(),
+                description: Some("The number of items delivered in the initial payload.".into()),
+                ty: "Int".to_string(),
+                default_value: Some(Value::Int(0.into())),
+                validator: None,
+            });
+            args
         }
+    });
+
+    // register scalars
+    bool::create_type_info(&mut registry);
+    i32::create_type_info(&mut registry);
+    f32::create_type_info(&mut registry);
+    String::create_type_info(&mut registry);
 
-        Ok(serde_json::Value::Null)
+    registry
+}
+
+/// Records the error of an operation as a tracing event.
+#[inline]
+fn trace_error<T>(res: Result<T>) -> Result<T> {
+    #[cfg(feature = "tracing")]
+    {
+        if let Err(err) = &res {
+            tracing::error!(error = %err, "operation failed");
+        }
     }
+    res
 }
@@ -0,0 +1,172 @@
+use crate::{QueryError, Result};
+use futures::future::{self, Either};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Instant;
+
+#[derive(Default)]
+struct DelayState {
+    fired: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl DelayState {
+    fn fire(&self) {
+        self.fired.store(true, atomic::Ordering::SeqCst);
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+}
+
+struct Entry {
+    deadline: Instant,
+    state: Arc<DelayState>,
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.deadline == other.deadline
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // The earliest deadline is at the top of the heap.
+        other.deadline.cmp(&self.deadline)
+    }
+}
+
+/// A timer that does not depend on an async runtime, the delays are fired by a thread that is started when the first
+/// delay is created, and stops when the timer is dropped.
+#[derive(Default)]
+pub(crate) struct Timer {
+    sender: Mutex<Option<Sender<Entry>>>,
+}
+
+impl Timer {
+    /// Create a future that completes at the `deadline`.
+    pub(crate) fn delay(&self, deadline: Instant) -> Delay {
+        let state = Arc::new(DelayState::default());
+        let mut sender = self.sender.lock().unwrap();
+        let sender = sender.get_or_insert_with(|| {
+            let (sender, receiver) = mpsc::channel();
+            std::thread::spawn(move || run(receiver));
+            sender
+        });
+        if sender
+            .send(Entry {
+                deadline,
+                state: state.clone(),
+            })
+            .is_err()
+        {
+            state.fire();
+        }
+        Delay { state }
+    }
+}
+
+fn run(receiver: mpsc::Receiver<Entry>) {
+    let mut entries = BinaryHeap::<Entry>::new();
+    loop {
+        let res = match entries.peek() {
+            Some(entry) => {
+                receiver.recv_timeout(entry.deadline.saturating_duration_since(Instant::now()))
+            }
+            None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match res {
+            Ok(entry) => entries.push(entry),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => return,
+        }
+
+        let now = Instant::now();
+        while entries.peek().map(|entry| entry.deadline <= now) == Some(true) {
+            entries.pop().unwrap().state.fire();
+        }
+    }
+}
+
+/// Resolve the future `fut`, a `QueryError::Timeout` is returned if it is not completed at the `deadline`.
+pub(crate) async fn with_deadline<T, F: Future<Output = Result<T>>>(
+    timer: &Timer,
+    deadline: Option<Instant>,
+    fut: F,
+) -> Result<T> {
+    with_delay(deadline.map(|deadline| timer.delay(deadline)), fut).await
+}
+
+/// Resolve the future `fut`, a `QueryError::Timeout` is returned if it is not completed when the `delay` fires.
+///
+/// The future is polled before the delay, so a future that shares a clone of the delay times out first.
+pub(crate) async fn with_delay<T, F: Future<Output = Result<T>>>(
+    delay: Option<Delay>,
+    fut: F,
+) -> Result<T> {
+    match delay {
+        Some(delay) => {
+            futures::pin_mut!(fut);
+            match future::select(fut, delay).await {
+                Either::Left((res, _)) => res,
+                Either::Right(_) => Err(QueryError::Timeout.into()),
+            }
+        }
+        None => fut.await,
+    }
+}
+
+/// A future returned by `Timer::delay`, the clones of a delay fire together.
+#[derive(Clone)]
+pub(crate) struct Delay {
+    state: Arc<DelayState>,
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.state.fired.load(atomic::Ordering::SeqCst) {
+            return Poll::Ready(());
+        }
+        *self.state.waker.lock().unwrap() = Some(cx.waker().clone());
+        if self.state.fired.load(atomic::Ordering::SeqCst) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Timer;
+    use std::time::{Duration, Instant};
+
+    #[async_std::test]
+    async fn test_delay() {
+        let timer = Timer::default();
+        let start = Instant::now();
+        let long = timer.delay(start + Duration::from_millis(200));
+        timer.delay(start + Duration::from_millis(50)).await;
+        assert!(start.elapsed() >= Duration::from_millis(50));
+        assert!(start.elapsed() < Duration::from_millis(200));
+        long.await;
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+}
@@ -30,9 +30,12 @@ impl GQLType for GQLEmptyMutation {
 
     fn create_type_info(registry: &mut registry::Registry) -> String {
         registry.create_type::<Self, _>(|_| registry::Type::Object {
-            name: "EmptyMutation",
+            name: "EmptyMutation".to_string(),
             description: None,
             fields: Default::default(),
+            extends: false,
+            keys: None,
+            cache_control: Default::default(),
         })
     }
 }
@@ -1,4 +1,4 @@
-use crate::{GQLType, Result};
+use crate::{GQLType, InputValueError, InputValueResult, Result};
 use graphql_parser::query::Value;
 
 pub struct GQLEnumItem<T> {
@@ -10,22 +10,24 @@ pub struct GQLEnumItem<T> {
 pub trait GQLEnum: GQLType + Sized + Eq + Send + Copy + Sized + 'static {
     fn items() -> &'static [GQLEnumItem<Self>];
 
-    fn parse_enum(value: &Value) -> Option<Self> {
-        let value = match value {
-            Value::Enum(s) => Some(s.as_str()),
-            Value::String(s) => Some(s.as_str()),
-            _ => None,
+    fn parse_enum(value: &Value) -> InputValueResult<Self> {
+        let name = match value {
+            Value::Enum(s) => s.as_str(),
+            Value::String(s) => s.as_str(),
+            _ => return Err(InputValueError::expected_type(Self::type_name(), value)),
         };
 
-        value.and_then(|value| {
-            let items = Self::items();
-            for item in items {
-                if item.name == value {
-                    return Some(item.value);
-                }
+        let items = Self::items();
+        for item in items {
+            if item.name == name {
+                return Ok(item.value);
             }
-            None
-        })
+        }
+        Err(InputValueError::custom(format!(
+            "enumeration type \"{}\" does not contain the value \"{}\"",
+            Self::type_name(),
+            name
+        )))
     }
 
     fn resolve_enum(&self) -> Result<serde_json::Value> {
@@ -37,4 +39,14 @@ pub trait GQLEnum: GQLType + Sized + Eq + Send + Copy + Sized + 'static {
         }
         unreachable!()
     }
+
+    fn to_enum_value(&self) -> Value {
+        let items = Self::items();
+        for item in items {
+            if item.value == *self {
+                return Value::Enum(item.name.to_string());
+            }
+        }
+        unreachable!()
+    }
 }
@@ -1,5 +1,128 @@
-use crate::{registry, ContextSelectionSet, GQLInputValue, GQLOutputValue, GQLType, Result, Value};
+use crate::incremental::{resolve_initial, Tail};
+use crate::resolver::is_incremental;
+use crate::{
+    registry, Context, ContextBase, ContextSelectionSet, GQLInputValue, GQLOutputValue, GQLType,
+    InputValueError, InputValueResult, Result, Value,
+};
+use futures::future::{self, BoxFuture};
 use std::borrow::Cow;
+use std::future::Future;
+use std::pin::Pin;
+
+/// The items of a list value, it is implemented by the slices of output values and by the lists of the dynamic
+/// schemas.
+pub(crate) trait ListItems: Sync {
+    /// The number of items.
+    fn len(&self) -> usize;
+
+    /// Resolve the item at `idx`.
+    fn resolve_item<'b>(
+        &'b self,
+        ctx: &'b ContextSelectionSet<'b>,
+        idx: usize,
+    ) -> BoxFuture<'b, Result<serde_json::Value>>;
+}
+
+impl<T: GQLOutputValue + Send + Sync> ListItems for [T] {
+    fn len(&self) -> usize {
+        <[T]>::len(self)
+    }
+
+    fn resolve_item<'b>(
+        &'b self,
+        ctx: &'b ContextSelectionSet<'b>,
+        idx: usize,
+    ) -> BoxFuture<'b, Result<serde_json::Value>> {
+        GQLOutputValue::resolve(&self[idx], ctx)
+    }
+}
+
+/// Resolve the items of a list, the items after the first `initialCount` ones of a list field with `@stream` are
+/// resolved into patches in an incremental execution, after the initial value of the list is sent.
+pub(crate) async fn resolve_list<'a, L: ListItems + ?Sized>(
+    ctx: &'a ContextSelectionSet<'a>,
+    items: &'a L,
+) -> Result<serde_json::Value> {
+    let len = items.len();
+    let initial = match ctx.initial {
+        Some(initial) if is_incremental(ctx) => initial,
+        _ => {
+            let mut res = Vec::new();
+            for idx in 0..len {
+                res.push(items.resolve_item(&ctx.with_index(idx), idx).await?);
+            }
+            return Ok(res.into());
+        }
+    };
+
+    let initial_count = match &ctx.stream {
+        Some(stream) => stream.initial_count.min(len),
+        None => len,
+    };
+    let mut res = Vec::new();
+    let mut pending = Vec::new();
+    for idx in 0..initial_count {
+        let (value, tail) = resolve_item_initial(ctx, items, idx).await?;
+        res.push(value);
+        pending.extend(tail.map(Tail::resolve));
+    }
+    if let Some(stream) = &ctx.stream {
+        for idx in initial_count..len {
+            pending.push(Box::pin(resolve_streamed(
+                ctx,
+                items,
+                stream.label.clone(),
+                idx,
+            )));
+        }
+    }
+    if !pending.is_empty() {
+        initial.send(std::mem::take(&mut res).into()).await;
+        future::join_all(pending).await;
+    }
+    Ok(res.into())
+}
+
+/// Resolve the item at `idx` until its initial value is known.
+async fn resolve_item_initial<'a, L: ListItems + ?Sized>(
+    ctx: &'a ContextSelectionSet<'a>,
+    items: &'a L,
+    idx: usize,
+) -> Result<(serde_json::Value, Option<Tail<'a>>)> {
+    resolve_initial(|initial| {
+        Box::pin(async move {
+            let ctx_idx = ContextBase {
+                initial: Some(&initial),
+                ..ctx.with_index(idx)
+            };
+            items.resolve_item(&ctx_idx, idx).await
+        })
+    })
+    .await
+}
+
+/// Resolve a streamed item into a patch, after the initial value of the list is sent.
+async fn resolve_streamed<'a, L: ListItems + ?Sized>(
+    ctx: &'a ContextSelectionSet<'a>,
+    items: &'a L,
+    label: Option<String>,
+    idx: usize,
+) {
+    let patches = match ctx.incremental {
+        Some(patches) => patches,
+        None => return,
+    };
+    let path = ctx.with_index(idx).path_node.as_ref().unwrap().to_json();
+    match patches.resolve(resolve_item_initial(ctx, items, idx)).await {
+        Ok((data, tail)) => {
+            patches.send(label, path, Ok(data));
+            if let Some(tail) = tail {
+                tail.resolve().await;
+            }
+        }
+        Err(err) => patches.send(label, path, Err(err)),
+    }
+}
 
 impl<T: GQLType> GQLType for Vec<T> {
     fn type_name() -> Cow<'static, str> {
@@ -17,28 +140,42 @@ impl<T: GQLType> GQLType for Vec<T> {
 }
 
 impl<T: GQLInputValue> GQLInputValue for Vec<T> {
-    fn parse(value: &Value) -> Option<Self> {
+    fn parse(value: &Value) -> InputValueResult<Self> {
         match value {
             Value::List(values) => {
                 let mut result = Vec::new();
-                for value in values {
-                    result.push(GQLInputValue::parse(value)?);
+                for (idx, value) in values.iter().enumerate() {
+                    result.push(GQLInputValue::parse(value).map_err(|err| err.with_index(idx))?);
                 }
-                Some(result)
+                Ok(result)
             }
-            _ => None,
+            _ => Err(InputValueError::expected_type(Self::type_name(), value)),
         }
     }
+
+    fn to_value(&self) -> Value {
+        Value::List(self.iter().map(GQLInputValue::to_value).collect())
+    }
+
+    fn check_guards<'a>(
+        ctx: &'a Context<'a>,
+        value: &'a Value,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        Box::pin(async move {
+            if let Value::List(values) = value {
+                for value in values {
+                    T::check_guards(ctx, value).await?;
+                }
+            }
+            Ok(())
+        })
+    }
 }
 
 #[async_trait::async_trait]
 impl<T: GQLOutputValue + Send + Sync> GQLOutputValue for Vec<T> {
     async fn resolve(value: &Self, ctx: &ContextSelectionSet<'_>) -> Result<serde_json::Value> {
-        let mut res = Vec::new();
-        for item in value {
-            res.push(GQLOutputValue::resolve(item, &ctx).await?);
-        }
-        Ok(res.into())
+        resolve_list(ctx, value.as_slice()).await
     }
 }
 
@@ -55,11 +192,7 @@ impl<T: GQLType> GQLType for &[T] {
 #[async_trait::async_trait]
 impl<T: GQLOutputValue + Send + Sync> GQLOutputValue for &[T] {
     async fn resolve(value: &Self, ctx: &ContextSelectionSet<'_>) -> Result<serde_json::Value> {
-        let mut res = Vec::new();
-        for item in value.iter() {
-            res.push(GQLOutputValue::resolve(item, &ctx).await?);
-        }
-        Ok(res.into())
+        resolve_list(ctx, *value).await
     }
 }
 
@@ -76,11 +209,7 @@ impl<T: GQLType> GQLType for &Vec<T> {
 #[async_trait::async_trait]
 impl<T: GQLOutputValue + Send + Sync> GQLOutputValue for &Vec<T> {
     async fn resolve(value: &Self, ctx: &ContextSelectionSet<'_>) -> Result<serde_json::Value> {
-        let mut res = Vec::new();
-        for item in value.iter() {
-            res.push(GQLOutputValue::resolve(item, &ctx).await?);
-        }
-        Ok(res.into())
+        resolve_list(ctx, value.as_slice()).await
     }
 }
 
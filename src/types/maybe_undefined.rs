@@ -0,0 +1,172 @@
+use crate::{registry, Context, GQLInputValue, GQLType, InputValueResult, Result, Value};
+use std::borrow::Cow;
+use std::future::Future;
+use std::pin::Pin;
+
+/// Similar to `Option`, but it has three states, `undefined`, `null` and `x`.
+///
+/// It can be used as an argument or an input object field to distinguish a value that is not provided from
+/// an explicit `null`, for example in the update mutations.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     #[field]
+///     async fn value(&self, input: MaybeUndefined<i32>) -> i32 {
+///         match input {
+///             MaybeUndefined::Undefined => 1,
+///             MaybeUndefined::Null => 2,
+///             MaybeUndefined::Value(value) => value,
+///         }
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+///     let query = r#"
+///         {
+///             v1:value(input: 99)
+///             v2:value(input: null)
+///             v3:value
+///         }"#;
+///     assert_eq!(
+///         schema.query(query).execute().await.unwrap(),
+///         serde_json::json!({
+///             "v1": 99,
+///             "v2": 2,
+///             "v3": 1,
+///         })
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum MaybeUndefined<T> {
+    /// The value is not provided.
+    Undefined,
+
+    /// The value is an explicit `null`.
+    Null,
+
+    /// The value is provided.
+    Value(T),
+}
+
+impl<T> MaybeUndefined<T> {
+    /// Returns true if the value is not provided.
+    pub fn is_undefined(&self) -> bool {
+        matches!(self, MaybeUndefined::Undefined)
+    }
+
+    /// Returns true if the value is an explicit `null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, MaybeUndefined::Null)
+    }
+
+    /// Returns true if the value is provided and not `null`.
+    pub fn is_value(&self) -> bool {
+        matches!(self, MaybeUndefined::Value(_))
+    }
+
+    /// Borrow the value, returns `None` if it is undefined or `null`.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            MaybeUndefined::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Converts the value into an `Option<T>`, both undefined and `null` become `None`.
+    pub fn take(self) -> Option<T> {
+        match self {
+            MaybeUndefined::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Converts the value into an `Option<Option<T>>`, returns `None` if it is undefined.
+    pub fn into_option(self) -> Option<Option<T>> {
+        match self {
+            MaybeUndefined::Undefined => None,
+            MaybeUndefined::Null => Some(None),
+            MaybeUndefined::Value(value) => Some(Some(value)),
+        }
+    }
+}
+
+impl<T: GQLType> GQLType for MaybeUndefined<T> {
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn qualified_type_name() -> String {
+        T::type_name().to_string()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        T::create_type_info(registry);
+        T::type_name().to_string()
+    }
+}
+
+impl<T: GQLInputValue> GQLInputValue for MaybeUndefined<T> {
+    fn parse(value: &Value) -> InputValueResult<Self> {
+        match value {
+            Value::Null => Ok(MaybeUndefined::Null),
+            _ => Ok(MaybeUndefined::Value(T::parse(value)?)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            MaybeUndefined::Value(value) => value.to_value(),
+            _ => Value::Null,
+        }
+    }
+
+    fn parse_optional(value: Option<&Value>) -> InputValueResult<Self> {
+        match value {
+            None => Ok(MaybeUndefined::Undefined),
+            Some(value) => Self::parse(value),
+        }
+    }
+
+    fn check_guards<'a>(
+        ctx: &'a Context<'a>,
+        value: &'a Value,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        match value {
+            Value::Null => Box::pin(async { Ok(()) }),
+            _ => T::check_guards(ctx, value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{GQLInputValue, GQLType, MaybeUndefined, Value};
+
+    #[test]
+    fn test_maybe_undefined_type() {
+        assert_eq!(MaybeUndefined::<i32>::type_name(), "Int");
+        assert_eq!(MaybeUndefined::<i32>::qualified_type_name(), "Int");
+        assert_eq!(
+            MaybeUndefined::<i32>::parse_optional(None),
+            Ok(MaybeUndefined::Undefined)
+        );
+        assert_eq!(
+            MaybeUndefined::<i32>::parse_optional(Some(&Value::Null)),
+            Ok(MaybeUndefined::Null)
+        );
+        assert_eq!(
+            MaybeUndefined::<i32>::parse_optional(Some(&Value::Int(10.into()))),
+            Ok(MaybeUndefined::Value(10))
+        );
+    }
+}
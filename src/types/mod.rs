@@ -1,9 +1,15 @@
 mod empty_mutation;
 mod r#enum;
 mod list;
+mod maybe_undefined;
 mod optional;
 mod query_root;
+mod service;
 
 pub use empty_mutation::GQLEmptyMutation;
+pub(crate) use list::{resolve_list, ListItems};
+pub use maybe_undefined::MaybeUndefined;
 pub use query_root::QueryRoot;
+pub(crate) use query_root::{add_introspection_fields, resolve_introspection_field};
 pub use r#enum::{GQLEnum, GQLEnumItem};
+pub(crate) use service::Service;
@@ -1,5 +1,10 @@
-use crate::{registry, ContextSelectionSet, GQLInputValue, GQLOutputValue, GQLType, Result, Value};
+use crate::{
+    registry, Context, ContextSelectionSet, GQLInputValue, GQLOutputValue, GQLType,
+    InputValueResult, Result, Value,
+};
 use std::borrow::Cow;
+use std::future::Future;
+use std::pin::Pin;
 
 impl<T: GQLType> GQLType for Option<T> {
     fn type_name() -> Cow<'static, str> {
@@ -17,10 +22,27 @@ impl<T: GQLType> GQLType for Option<T> {
 }
 
 impl<T: GQLInputValue> GQLInputValue for Option<T> {
-    fn parse(value: &Value) -> Option<Self> {
+    fn parse(value: &Value) -> InputValueResult<Self> {
         match value {
-            Value::Null => Some(None),
-            _ => Some(Some(T::parse(value)?)),
+            Value::Null => Ok(None),
+            _ => Ok(Some(T::parse(value)?)),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        match self {
+            Some(value) => value.to_value(),
+            None => Value::Null,
+        }
+    }
+
+    fn check_guards<'a>(
+        ctx: &'a Context<'a>,
+        value: &'a Value,
+    ) -> Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>> {
+        match value {
+            Value::Null => Box::pin(async { Ok(()) }),
+            _ => T::check_guards(ctx, value),
         }
     }
 }
@@ -1,60 +1,193 @@
 use crate::model::{__Schema, __Type};
 use crate::registry::Type;
+use crate::scalars::Any;
+use crate::types::Service;
 use crate::{
     registry, Context, ContextSelectionSet, ErrorWithPosition, GQLObject, GQLOutputValue, GQLType,
     QueryError, Result, Value,
 };
 use graphql_parser::query::Field;
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 pub struct QueryRoot<T> {
     pub inner: T,
+    pub federation: bool,
 }
 
-impl<T: GQLType> GQLType for QueryRoot<T> {
-    fn type_name() -> Cow<'static, str> {
-        T::type_name()
-    }
+impl<T: GQLType> QueryRoot<T> {
+    /// Register the `_Service` and `_Entity` types of the federation, and add the `_service` and `_entities`
+    /// fields to the query type.
+    ///
+    /// The `_Entity` union and the `_entities` field are only added if there is at least one entity.
+    pub fn create_federation_types(registry: &mut registry::Registry) {
+        let service_type = Service::create_type_info(registry);
+        let representations_type = <Vec<Any>>::create_type_info(registry);
+        let entity_types = registry
+            .types
+            .values()
+            .filter_map(|ty| match ty {
+                Type::Object {
+                    name,
+                    keys: Some(keys),
+                    ..
+                } if !keys.is_empty() => Some(name.to_string()),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        let has_entities = !entity_types.is_empty();
+
+        if has_entities {
+            registry.types.insert(
+                "_Entity".to_string(),
+                Type::Union {
+                    name: "_Entity".into(),
+                    description: None,
+                    possible_types: entity_types,
+                },
+            );
+        }
 
-    fn create_type_info(registry: &mut registry::Registry) -> String {
-        let schema_type = __Schema::create_type_info(registry);
-        let root = T::create_type_info(registry);
         if let Some(Type::Object { fields, .. }) = registry.types.get_mut(T::type_name().as_ref()) {
             fields.insert(
-                "__schema",
+                "_service".into(),
                 registry::Field {
-                    name: "__schema",
-                    description: Some("Access the current type schema of this server."),
+                    name: "_service".into(),
+                    description: None,
                     args: Default::default(),
-                    ty: schema_type,
+                    ty: service_type,
                     deprecation: None,
+                    external: false,
+                    requires: None,
+                    provides: None,
+                    cache_control: Default::default(),
                 },
             );
 
-            fields.insert(
-                "__type",
-                registry::Field {
-                    name: "__type",
-                    description: Some("Request the type information of a single type."),
-                    args: {
-                        let mut args = HashMap::new();
-                        args.insert(
-                            "name",
-                            registry::InputValue {
-                                name: "name",
-                                description: None,
-                                ty: "String!".to_string(),
-                                default_value: None,
-                            },
-                        );
-                        args
+            if has_entities {
+                fields.insert(
+                    "_entities".into(),
+                    registry::Field {
+                        name: "_entities".into(),
+                        description: None,
+                        args: {
+                            let mut args = HashMap::new();
+                            args.insert(
+                                "representations".into(),
+                                registry::InputValue {
+                                    name: "representations".into(),
+                                    description: None,
+                                    ty: representations_type,
+                                    default_value: None,
+                                    validator: None,
+                                },
+                            );
+                            args
+                        },
+                        ty: "[_Entity]!".to_string(),
+                        deprecation: None,
+                        external: false,
+                        requires: None,
+                        provides: None,
+                        cache_control: Default::default(),
                     },
-                    ty: "__Type".to_string(),
-                    deprecation: None,
-                },
-            );
+                );
+            }
         }
+    }
+}
+
+/// Add the `__schema` and `__type` fields to the query type.
+pub(crate) fn add_introspection_fields(registry: &mut registry::Registry, query_type: &str) {
+    let schema_type = __Schema::create_type_info(registry);
+    if let Some(Type::Object { fields, .. }) = registry.types.get_mut(query_type) {
+        fields.insert(
+            "__schema".into(),
+            registry::Field {
+                name: "__schema".into(),
+                description: Some("Access the current type schema of this server.".into()),
+                args: Default::default(),
+                ty: schema_type,
+                deprecation: None,
+                external: false,
+                requires: None,
+                provides: None,
+                cache_control: Default::default(),
+            },
+        );
+
+        fields.insert(
+            "__type".into(),
+            registry::Field {
+                name: "__type".into(),
+                description: Some("Request the type information of a single type.".into()),
+                args: {
+                    let mut args = HashMap::new();
+                    args.insert(
+                        "name".into(),
+                        registry::InputValue {
+                            name: "name".into(),
+                            description: None,
+                            ty: "String!".to_string(),
+                            default_value: None,
+                            validator: None,
+                        },
+                    );
+                    args
+                },
+                ty: "__Type".to_string(),
+                deprecation: None,
+                external: false,
+                requires: None,
+                provides: None,
+                cache_control: Default::default(),
+            },
+        );
+    }
+}
+
+/// Resolve the `__schema` and `__type` fields of the query type, returns `None` for the other fields.
+pub(crate) async fn resolve_introspection_field(
+    ctx: &Context<'_>,
+    field: &Field,
+) -> Result<Option<serde_json::Value>> {
+    if field.name.as_str() == "__schema" {
+        let ctx_obj = ctx.with_item(&field.selection_set);
+        GQLOutputValue::resolve(
+            &__Schema {
+                registry: &ctx.registry,
+            },
+            &ctx_obj,
+        )
+        .await
+        .map(Some)
+        .map_err(|err| err.with_position(field.position).into())
+    } else if field.name.as_str() == "__type" {
+        let type_name: String = ctx.param_value("name", None::<fn() -> Value>).await?;
+        let ctx_obj = ctx.with_item(&field.selection_set);
+        GQLOutputValue::resolve(
+            &ctx.registry
+                .types
+                .get(&type_name)
+                .map(|ty| __Type::new_simple(ctx.registry, ty)),
+            &ctx_obj,
+        )
+        .await
+        .map(Some)
+        .map_err(|err| err.with_position(field.position).into())
+    } else {
+        Ok(None)
+    }
+}
+
+impl<T: GQLType> GQLType for QueryRoot<T> {
+    fn type_name() -> Cow<'static, str> {
+        T::type_name()
+    }
+
+    fn create_type_info(registry: &mut registry::Registry) -> String {
+        let root = T::create_type_info(registry);
+        add_introspection_fields(registry, &T::type_name());
         root
     }
 }
@@ -62,28 +195,44 @@ impl<T: GQLType> GQLType for QueryRoot<T> {
 #[async_trait::async_trait]
 impl<T: GQLObject + Send + Sync> GQLObject for QueryRoot<T> {
     async fn resolve_field(&self, ctx: &Context<'_>, field: &Field) -> Result<serde_json::Value> {
-        if field.name.as_str() == "__schema" {
+        if let Some(value) = resolve_introspection_field(ctx, field).await? {
+            return Ok(value);
+        }
+
+        if self.federation && field.name.as_str() == "_service" {
             let ctx_obj = ctx.with_item(&field.selection_set);
             return GQLOutputValue::resolve(
-                &__Schema {
-                    registry: &ctx.registry,
+                &Service {
+                    sdl: ctx.registry.export_sdl(true),
                 },
                 &ctx_obj,
             )
             .await
             .map_err(|err| err.with_position(field.position).into());
-        } else if field.name.as_str() == "__type" {
-            let type_name: String = ctx.param_value("name", || Value::Null)?;
-            let ctx_obj = ctx.with_item(&field.selection_set);
-            return GQLOutputValue::resolve(
-                &ctx.registry
-                    .types
-                    .get(&type_name)
-                    .map(|ty| __Type::new_simple(ctx.registry, ty)),
-                &ctx_obj,
-            )
-            .await
-            .map_err(|err| err.with_position(field.position).into());
+        } else if self.federation && field.name.as_str() == "_entities" {
+            let representations: Vec<Any> = ctx
+                .param_value("representations", None::<fn() -> Value>)
+                .await?;
+            let mut entities = Vec::new();
+            for (idx, representation) in representations.iter().enumerate() {
+                let type_name = match &representation.0 {
+                    Value::Object(params) => params.get("__typename"),
+                    _ => None,
+                };
+                let type_name = match type_name {
+                    Some(Value::String(type_name)) => type_name,
+                    _ => anyhow::bail!(QueryError::TypeNameNotExists.with_position(field.position)),
+                };
+                let ctx_idx = ctx.with_index(idx);
+                match self.inner.find_entity(&ctx_idx, &representation.0).await? {
+                    Some(entity) => entities.push(entity),
+                    None => anyhow::bail!(QueryError::EntityNotFound {
+                        type_name: type_name.clone(),
+                    }
+                    .with_position(field.position)),
+                }
+            }
+            return Ok(entities.into());
         }
 
         return self.inner.resolve_field(ctx, field).await;
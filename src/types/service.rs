@@ -0,0 +1,14 @@
+use async_graphql_derive::Object;
+
+/// The `_Service` type of the federation.
+pub(crate) struct Service {
+    pub sdl: String,
+}
+
+#[Object(internal, name = "_Service")]
+impl Service {
+    #[field]
+    async fn sdl(&self) -> &str {
+        &self.sdl
+    }
+}
@@ -1,20 +1,27 @@
 use crate::error::RuleError;
 use crate::registry::{Registry, Type};
+use crate::Variables;
 use graphql_parser::query::{Definition, Document};
 use graphql_parser::Pos;
 use std::collections::HashSet;
 
 pub struct ValidatorContext<'a> {
     pub registry: &'a Registry,
+    pub variables: Option<&'a Variables>,
     pub errors: Vec<RuleError>,
     type_stack: Vec<&'a Type>,
     fragments_names: HashSet<&'a str>,
 }
 
 impl<'a> ValidatorContext<'a> {
-    pub fn new(registry: &'a Registry, doc: &'a Document) -> Self {
+    pub fn new(
+        registry: &'a Registry,
+        doc: &'a Document,
+        variables: Option<&'a Variables>,
+    ) -> Self {
         Self {
             registry,
+            variables,
             errors: Default::default(),
             type_stack: Default::default(),
             fragments_names: doc
@@ -31,6 +38,7 @@ impl<'a> ValidatorContext<'a> {
     pub fn report_error<T: Into<String>>(&mut self, locations: Vec<Pos>, msg: T) {
         self.errors.push(RuleError {
             locations,
+            source: 0,
             message: msg.into(),
         })
     }
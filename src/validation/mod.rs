@@ -2,16 +2,77 @@ use crate::error::RuleErrors;
 use crate::registry::Registry;
 use crate::validation::context::ValidatorContext;
 use crate::validation::visitor::{visit, VisitorNil};
-use crate::Result;
+use crate::{QueryParseError, Result, Variables};
+use graphql_parser::parse_query;
 use graphql_parser::query::Document;
 
 mod context;
 mod rules;
 mod utils;
+mod variables;
 mod visitor;
 
-pub fn check_rules(registry: &Registry, doc: &Document) -> Result<()> {
-    let mut ctx = ValidatorContext::new(registry, doc);
+pub use utils::check_default_values;
+pub use variables::coerce_variables;
+
+/// Validate a client document made of several sources, the locations of the errors are relative to the source
+/// where they are, and its index is recorded in the errors.
+pub fn check_sources(
+    registry: &Registry,
+    sources: &[&str],
+    allow_introspection: bool,
+) -> Result<()> {
+    // The sources are joined by line breaks, so every source starts on a line of its own.
+    let mut first_lines = Vec::with_capacity(sources.len());
+    let mut line = 1;
+    for source in sources {
+        first_lines.push(line);
+        line += source.matches('\n').count() + 1;
+    }
+
+    let document = parse_query(&sources.join("\n")).map_err(|err| {
+        // The sources are parsed one by one to find the invalid one.
+        let err = sources
+            .iter()
+            .enumerate()
+            .find_map(|(idx, source)| {
+                parse_query(source)
+                    .err()
+                    .map(|err| format!("Source {}: {}", idx, err))
+            })
+            .unwrap_or_else(|| err.to_string());
+        QueryParseError(err)
+    })?;
+
+    check_rules(registry, &document, None, allow_introspection).map_err(|err| {
+        match err.downcast::<RuleErrors>() {
+            Ok(mut rule_errors) => {
+                for error in &mut rule_errors.errors {
+                    for (idx, pos) in error.locations.iter_mut().enumerate() {
+                        let source = first_lines
+                            .iter()
+                            .rposition(|first_line| *first_line <= pos.line)
+                            .unwrap_or_default();
+                        pos.line -= first_lines[source] - 1;
+                        if idx == 0 {
+                            error.source = source;
+                        }
+                    }
+                }
+                rule_errors.into()
+            }
+            Err(err) => err,
+        }
+    })
+}
+
+pub fn check_rules(
+    registry: &Registry,
+    doc: &Document,
+    variables: Option<&Variables>,
+    allow_introspection: bool,
+) -> Result<()> {
+    let mut ctx = ValidatorContext::new(registry, doc, variables);
     let mut visitor = VisitorNil
         .with(rules::ArgumentsOfCorrectType::default())
         .with(rules::DefaultValuesOfCorrectType)
@@ -33,7 +94,10 @@ pub fn check_rules(registry: &Registry, doc: &Document) -> Result<()> {
         .with(rules::VariableInAllowedPosition::default())
         .with(rules::ScalarLeafs)
         .with(rules::PossibleFragmentSpreads::default())
-        .with(rules::ProvidedNonNullArguments);
+        .with(rules::ProvidedNonNullArguments)
+        .with(rules::NoIntrospection {
+            disabled: !allow_introspection,
+        });
 
     visit(&mut visitor, &mut ctx, doc);
     if !ctx.errors.is_empty() {
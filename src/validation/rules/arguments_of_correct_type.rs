@@ -1,18 +1,40 @@
 use crate::registry::InputValue;
 use crate::validation::context::ValidatorContext;
-use crate::validation::utils::is_valid_input_value;
+use crate::validation::utils::{check_input_validators, check_input_value, resolve_variables};
 use crate::validation::visitor::Visitor;
-use graphql_parser::query::Field;
+use crate::InputValueError;
+use graphql_parser::query::{Field, OperationDefinition, VariableDefinition};
 use graphql_parser::schema::{Directive, Value};
 use graphql_parser::Pos;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 #[derive(Default)]
 pub struct ArgumentsOfCorrectType<'a> {
-    current_args: Option<&'a HashMap<&'static str, InputValue>>,
+    current_args: Option<&'a HashMap<Cow<'static, str>, InputValue>>,
+    var_defaults: HashMap<&'a str, &'a Value>,
 }
 
 impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
+    fn enter_operation_definition(
+        &mut self,
+        _ctx: &mut ValidatorContext<'a>,
+        _operation_definition: &'a OperationDefinition,
+    ) {
+        self.var_defaults.clear();
+    }
+
+    fn enter_variable_definition(
+        &mut self,
+        _ctx: &mut ValidatorContext<'a>,
+        variable_definition: &'a VariableDefinition,
+    ) {
+        if let Some(value) = &variable_definition.default_value {
+            self.var_defaults
+                .insert(variable_definition.name.as_str(), value);
+        }
+    }
+
     fn enter_directive(&mut self, ctx: &mut ValidatorContext<'a>, directive: &'a Directive) {
         self.current_args = ctx
             .registry
@@ -36,12 +58,35 @@ impl<'a> Visitor<'a> for ArgumentsOfCorrectType<'a> {
             .current_args
             .and_then(|args| args.get(name).map(|input| input))
         {
-            if !is_valid_input_value(ctx.registry, &arg.ty, value) {
+            if let Some(err) = check_input_value(ctx.registry, &arg.ty, value) {
+                ctx.report_error(
+                    vec![pos],
+                    format!(
+                        "Invalid value for argument \"{}\", {}",
+                        err.path_to_string(&arg.name),
+                        err.message
+                    ),
+                );
+                return;
+            }
+
+            let value = resolve_variables(value, ctx.variables, &self.var_defaults);
+            if let Value::Null = value {
+                return;
+            }
+            if let Some(err) = arg
+                .validator
+                .as_ref()
+                .and_then(|validator| validator.is_valid(&value))
+                .map(InputValueError::custom)
+                .or_else(|| check_input_validators(ctx.registry, &arg.ty, &value))
+            {
                 ctx.report_error(
                     vec![pos],
                     format!(
-                        "Invalid value for argument \"{}\", expected type \"{}\"",
-                        arg.name, arg.ty,
+                        "Invalid value for argument \"{}\", {}",
+                        err.path_to_string(&arg.name),
+                        err.message
                     ),
                 );
             }
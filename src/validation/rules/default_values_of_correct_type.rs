@@ -1,5 +1,5 @@
 use crate::validation::context::ValidatorContext;
-use crate::validation::utils::is_valid_input_value;
+use crate::validation::utils::check_input_value;
 use crate::validation::visitor::Visitor;
 use graphql_parser::query::{Type, VariableDefinition};
 
@@ -18,7 +18,7 @@ impl<'a> Visitor<'a> for DefaultValuesOfCorrectType {
                     variable_definition.name, variable_definition.var_type,
                 ));
             } else {
-                if !is_valid_input_value(
+                if let Some(err) = check_input_value(
                     ctx.registry,
                     &variable_definition.var_type.to_string(),
                     value,
@@ -26,8 +26,9 @@ impl<'a> Visitor<'a> for DefaultValuesOfCorrectType {
                     ctx.report_error(
                         vec![variable_definition.position],
                         format!(
-                            "Invalid default value for argument \"{}\", expected type \"{}\"",
-                            variable_definition.name, variable_definition.var_type
+                            "Invalid default value for argument \"{}\", {}",
+                            err.path_to_string(&variable_definition.name),
+                            err.message
                         ),
                     )
                 }
@@ -1,4 +1,3 @@
-use crate::registry::Type;
 use crate::validation::context::ValidatorContext;
 use crate::validation::visitor::Visitor;
 use graphql_parser::query::Field;
@@ -14,10 +13,9 @@ impl<'a> Visitor<'a> for FieldsOnCorrectType {
             .field_by_name(&field.name)
             .is_none()
         {
-            if let Some(Type::Union { .. }) = ctx.parent_type() {
-                if field.name == "__typename" {
-                    return;
-                }
+            // `__typename` can be queried on any composite type, including unions and interfaces.
+            if field.name == "__typename" {
+                return;
             }
 
             ctx.report_error(
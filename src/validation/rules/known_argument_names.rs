@@ -4,6 +4,7 @@ use crate::validation::visitor::Visitor;
 use crate::Value;
 use graphql_parser::query::{Directive, Field};
 use graphql_parser::Pos;
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 enum ArgsType<'a> {
@@ -16,7 +17,7 @@ enum ArgsType<'a> {
 
 #[derive(Default)]
 pub struct KnownArgumentNames<'a> {
-    current_args: Option<(&'a HashMap<&'static str, InputValue>, ArgsType<'a>)>,
+    current_args: Option<(&'a HashMap<Cow<'static, str>, InputValue>, ArgsType<'a>)>,
 }
 
 impl<'a> Visitor<'a> for KnownArgumentNames<'a> {
@@ -7,6 +7,7 @@ mod known_fragment_names;
 mod known_type_names;
 mod lone_anonymous_operation;
 mod no_fragment_cycles;
+mod no_introspection;
 mod no_undefined_variables;
 mod no_unused_fragments;
 mod no_unused_variables;
@@ -29,6 +30,7 @@ pub use known_fragment_names::KnownFragmentNames;
 pub use known_type_names::KnownTypeNames;
 pub use lone_anonymous_operation::LoneAnonymousOperation;
 pub use no_fragment_cycles::NoFragmentCycles;
+pub use no_introspection::NoIntrospection;
 pub use no_undefined_variables::NoUndefinedVariables;
 pub use no_unused_fragments::NoUnusedFragments;
 pub use no_unused_variables::NoUnusedVariables;
@@ -34,6 +34,7 @@ impl<'a> CycleDetector<'a> {
 
                 self.errors.push(RuleError {
                     locations: vec![err_pos],
+                    source: 0,
                     message: format!("Cannot spread fragment \"{}\"", name),
                 });
             } else if !self.visited.contains(name) {
@@ -0,0 +1,21 @@
+use crate::validation::context::ValidatorContext;
+use crate::validation::visitor::Visitor;
+use graphql_parser::query::Field;
+
+pub struct NoIntrospection {
+    pub disabled: bool,
+}
+
+impl<'a> Visitor<'a> for NoIntrospection {
+    fn enter_field(&mut self, ctx: &mut ValidatorContext<'a>, field: &'a Field) {
+        if self.disabled && (field.name == "__schema" || field.name == "__type") {
+            ctx.report_error(
+                vec![field.position],
+                format!(
+                    "Cannot query field \"{}\": introspection is disabled",
+                    field.name
+                ),
+            );
+        }
+    }
+}
@@ -28,7 +28,9 @@ impl<'a> Visitor<'a> for PossibleFragmentSpreads<'a> {
             .fragment_types
             .get(fragment_spread.fragment_name.as_str())
         {
-            if ctx.current_type().name() != *fragment_type {
+            if ctx.current_type().name() != *fragment_type
+                && !ctx.current_type().is_possible_type(fragment_type)
+            {
                 ctx.report_error(
                         vec![fragment_spread.position],
                         format!(
@@ -1,47 +1,79 @@
-use crate::registry::{Registry, Type, TypeName};
-use crate::Value;
+use crate::registry::{InputValue, Registry, Type, TypeName};
+use crate::{InputValueError, Value, Variables};
+use std::collections::HashMap;
 
-pub fn is_valid_input_value(registry: &Registry, type_name: &str, value: &Value) -> bool {
+/// Check whether the value is of the type, return the reason and the path of the invalid part if not.
+pub fn check_input_value(
+    registry: &Registry,
+    type_name: &str,
+    value: &Value,
+) -> Option<InputValueError> {
     if let Value::Variable(_) = value {
-        return true;
+        return None;
     }
 
     match TypeName::create(type_name) {
-        TypeName::NonNull(type_name) => match value {
-            Value::Null => false,
-            _ => is_valid_input_value(registry, type_name, value),
+        TypeName::NonNull(inner_type_name) => match value {
+            Value::Null => Some(InputValueError::expected_type(type_name, value)),
+            _ => check_input_value(registry, inner_type_name, value),
         },
-        TypeName::List(type_name) => match value {
-            Value::List(elems) => elems
-                .iter()
-                .all(|elem| is_valid_input_value(registry, type_name, elem)),
-            _ => false,
+        TypeName::List(elem_type_name) => match value {
+            Value::List(elems) => elems.iter().enumerate().find_map(|(idx, elem)| {
+                check_input_value(registry, elem_type_name, elem).map(|err| err.with_index(idx))
+            }),
+            Value::Null => None,
+            _ => Some(InputValueError::expected_type(type_name, value)),
         },
         TypeName::Named(type_name) => {
             if let Value::Null = value {
-                return true;
+                return None;
             }
 
             if let Some(ty) = registry.types.get(type_name) {
                 match ty {
-                    Type::Scalar { is_valid, .. } => is_valid(value),
+                    Type::Scalar {
+                        is_valid, parse, ..
+                    } => {
+                        if is_valid(value) {
+                            None
+                        } else {
+                            Some(parse(value).err().unwrap_or_else(|| {
+                                InputValueError::expected_type(type_name, value)
+                            }))
+                        }
+                    }
                     Type::Enum { enum_values, .. } => match value {
-                        Value::Enum(name) => enum_values.contains_key(name.as_str()),
-                        _ => false,
+                        Value::Enum(name) => {
+                            if enum_values.contains_key(name.as_str()) {
+                                None
+                            } else {
+                                Some(InputValueError::custom(format!(
+                                    "enumeration type \"{}\" does not contain the value \"{}\"",
+                                    type_name, name
+                                )))
+                            }
+                        }
+                        _ => Some(InputValueError::expected_type(type_name, value)),
                     },
                     Type::InputObject { input_fields, .. } => match value {
-                        Value::Object(values) => {
-                            for field in input_fields {
-                                let value = values.get(field.name).unwrap_or(&Value::Null);
-                                if !is_valid_input_value(registry, &field.ty, value) {
-                                    return field.default_value.is_some();
+                        Value::Object(values) => input_fields.iter().find_map(|field| {
+                            match values.get(field.name.as_ref()) {
+                                Some(value) => check_input_value(registry, &field.ty, value),
+                                None if field.default_value.is_none()
+                                    && TypeName::create(&field.ty).is_non_null() =>
+                                {
+                                    Some(InputValueError::custom(format!(
+                                        "expected type \"{}\", but it is not provided",
+                                        field.ty
+                                    )))
                                 }
+                                None => None,
                             }
-                            true
-                        }
-                        _ => false,
+                            .map(|err| err.with_field(&field.name))
+                        }),
+                        _ => Some(InputValueError::expected_type(type_name, value)),
                     },
-                    _ => false,
+                    _ => Some(InputValueError::expected_type(type_name, value)),
                 }
             } else {
                 unreachable!()
@@ -49,3 +81,139 @@ pub fn is_valid_input_value(registry: &Registry, type_name: &str, value: &Value)
         }
     }
 }
+
+/// Check the validators of the input object fields in `value`, return the reason of the first failure.
+///
+/// The value must be of the correct type, and it must not contain any variables.
+pub fn check_input_validators(
+    registry: &Registry,
+    type_name: &str,
+    value: &Value,
+) -> Option<InputValueError> {
+    match TypeName::create(type_name) {
+        TypeName::NonNull(type_name) => check_input_validators(registry, type_name, value),
+        TypeName::List(type_name) => match value {
+            Value::List(elems) => elems.iter().enumerate().find_map(|(idx, elem)| {
+                check_input_validators(registry, type_name, elem).map(|err| err.with_index(idx))
+            }),
+            _ => None,
+        },
+        TypeName::Named(type_name) => match (registry.types.get(type_name), value) {
+            (Some(Type::InputObject { input_fields, .. }), Value::Object(values)) => {
+                input_fields.iter().find_map(|field| {
+                    let value = values.get(field.name.as_ref()).unwrap_or(&Value::Null);
+                    if let Value::Null = value {
+                        return None;
+                    }
+                    field
+                        .validator
+                        .as_ref()
+                        .and_then(|validator| validator.is_valid(value))
+                        .map(InputValueError::custom)
+                        .or_else(|| check_input_validators(registry, &field.ty, value))
+                        .map(|err| err.with_field(&field.name))
+                })
+            }
+            _ => None,
+        },
+    }
+}
+
+/// Replace the variables in `value` with their values, the missing variables are replaced with their default
+/// values or `null`, and the input object fields that use the missing variables without default values are omitted.
+pub fn resolve_variables(
+    value: &Value,
+    variables: Option<&Variables>,
+    defaults: &HashMap<&str, &Value>,
+) -> Value {
+    match value {
+        Value::Variable(name) => variable_value(name, variables, defaults).unwrap_or(Value::Null),
+        Value::List(elems) => Value::List(
+            elems
+                .iter()
+                .map(|elem| resolve_variables(elem, variables, defaults))
+                .collect(),
+        ),
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .filter_map(|(name, value)| match value {
+                    Value::Variable(var_name) => variable_value(var_name, variables, defaults)
+                        .map(|value| (name.clone(), value)),
+                    _ => Some((name.clone(), resolve_variables(value, variables, defaults))),
+                })
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+fn variable_value(
+    name: &str,
+    variables: Option<&Variables>,
+    defaults: &HashMap<&str, &Value>,
+) -> Option<Value> {
+    variables
+        .and_then(|variables| variables.get(name))
+        .or_else(|| defaults.get(name).copied())
+        .cloned()
+}
+
+/// Check the default values of the arguments and input object fields against their types and validators,
+/// return the description of the first invalid one.
+pub fn check_default_values(registry: &Registry) -> Option<String> {
+    let check = |root: String, input_value: &InputValue| {
+        let value = input_value.default_value.as_ref()?;
+        check_input_value(registry, &input_value.ty, value)
+            .or_else(|| {
+                input_value
+                    .validator
+                    .as_ref()
+                    .and_then(|validator| validator.is_valid(value))
+                    .map(InputValueError::custom)
+            })
+            .or_else(|| check_input_validators(registry, &input_value.ty, value))
+            .map(|err| {
+                format!(
+                    "Invalid default value for \"{}\", {}",
+                    err.path_to_string(&root),
+                    err.message
+                )
+            })
+    };
+
+    let mut type_names = registry.types.keys().collect::<Vec<_>>();
+    type_names.sort();
+    for type_name in type_names {
+        match &registry.types[type_name] {
+            Type::InputObject { input_fields, .. } => {
+                for field in input_fields {
+                    if let Some(err) = check(format!("{}.{}", type_name, field.name), field) {
+                        return Some(err);
+                    }
+                }
+            }
+            ty => {
+                if let Some(fields) = ty.fields() {
+                    for field in fields.values() {
+                        for arg in field.args.values() {
+                            let root = format!("{}.{}({})", type_name, field.name, arg.name);
+                            if let Some(err) = check(root, arg) {
+                                return Some(err);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    for directive in registry.directives.values() {
+        for arg in directive.args.values() {
+            if let Some(err) = check(format!("@{}({})", directive.name, arg.name), arg) {
+                return Some(err);
+            }
+        }
+    }
+
+    None
+}
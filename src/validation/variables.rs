@@ -0,0 +1,152 @@
+use crate::error::{RuleError, RuleErrors};
+use crate::registry::{Registry, Type, TypeName};
+use crate::{InputValueError, Result, Value, Variables};
+use graphql_parser::query::VariableDefinition;
+use std::collections::BTreeMap;
+
+/// Coerce the provided variables against their declared types, all errors are reported at once.
+///
+/// The returned variables only contain the provided variables, the enum values provided as strings are converted
+/// to `Value::Enum`.
+pub fn coerce_variables(
+    registry: &Registry,
+    variable_definitions: &[VariableDefinition],
+    variables: Option<&Variables>,
+) -> Result<Variables> {
+    let mut coerced = Variables::default();
+    let mut errors = Vec::new();
+
+    for def in variable_definitions {
+        let var_type = def.var_type.to_string();
+        match variables.and_then(|variables| variables.get(&def.name)) {
+            Some(value) => match coerce_value(registry, &var_type, value.clone()) {
+                Ok(value) => {
+                    coerced.insert(def.name.clone(), value);
+                }
+                Err(err) => errors.push(RuleError {
+                    locations: vec![def.position],
+                    source: 0,
+                    message: format!(
+                        "Invalid value for variable \"{}\", {}",
+                        err.path_to_string(&format!("${}", def.name)),
+                        err.message
+                    ),
+                }),
+            },
+            None => {
+                if def.default_value.is_none() && TypeName::create(&var_type).is_non_null() {
+                    errors.push(RuleError {
+                        locations: vec![def.position],
+                        source: 0,
+                        message: format!(
+                            "Variable \"${}\" of required type \"{}\" was not provided.",
+                            def.name, var_type
+                        ),
+                    });
+                }
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(RuleErrors { errors }.into());
+    }
+    Ok(coerced)
+}
+
+fn coerce_value(
+    registry: &Registry,
+    type_name: &str,
+    value: Value,
+) -> std::result::Result<Value, InputValueError> {
+    match TypeName::create(type_name) {
+        TypeName::NonNull(inner_type_name) => match value {
+            Value::Null => Err(InputValueError::expected_type(type_name, &value)),
+            _ => coerce_value(registry, inner_type_name, value),
+        },
+        TypeName::List(elem_type_name) => match value {
+            Value::List(elems) => elems
+                .into_iter()
+                .enumerate()
+                .map(|(idx, elem)| {
+                    coerce_value(registry, elem_type_name, elem).map_err(|err| err.with_index(idx))
+                })
+                .collect::<std::result::Result<_, _>>()
+                .map(Value::List),
+            Value::Null => Ok(Value::Null),
+            // A single value is coerced to a list of one item.
+            value => Ok(Value::List(vec![coerce_value(
+                registry,
+                elem_type_name,
+                value,
+            )?])),
+        },
+        TypeName::Named(type_name) => {
+            if let Value::Null = value {
+                return Ok(value);
+            }
+
+            match registry.types.get(type_name) {
+                Some(Type::Scalar {
+                    is_valid, parse, ..
+                }) => {
+                    if is_valid(&value) {
+                        Ok(value)
+                    } else {
+                        // The reason is taken from the scalar, falling back to the type mismatch.
+                        Err(parse(&value)
+                            .err()
+                            .unwrap_or_else(|| InputValueError::expected_type(type_name, &value)))
+                    }
+                }
+                Some(Type::Enum { enum_values, .. }) => match value {
+                    Value::Enum(name) | Value::String(name) => {
+                        if enum_values.contains_key(name.as_str()) {
+                            Ok(Value::Enum(name))
+                        } else {
+                            Err(InputValueError::custom(format!(
+                                "enumeration type \"{}\" does not contain the value \"{}\"",
+                                type_name, name
+                            )))
+                        }
+                    }
+                    _ => Err(InputValueError::expected_type(type_name, &value)),
+                },
+                Some(Type::InputObject { input_fields, .. }) => match value {
+                    Value::Object(mut values) => {
+                        let mut coerced = BTreeMap::new();
+                        for field in input_fields {
+                            match values.remove(field.name.as_ref()) {
+                                Some(value) => {
+                                    let value = coerce_value(registry, &field.ty, value)
+                                        .map_err(|err| err.with_field(&field.name))?;
+                                    coerced.insert(field.name.to_string(), value);
+                                }
+                                None => {
+                                    if field.default_value.is_none()
+                                        && TypeName::create(&field.ty).is_non_null()
+                                    {
+                                        return Err(InputValueError::custom(format!(
+                                            "expected type \"{}\", but it is not provided",
+                                            field.ty
+                                        ))
+                                        .with_field(&field.name));
+                                    }
+                                }
+                            }
+                        }
+                        if let Some(name) = values.keys().next() {
+                            return Err(InputValueError::custom(format!(
+                                "unknown field \"{}\" of type \"{}\"",
+                                name, type_name
+                            )));
+                        }
+                        Ok(Value::Object(coerced))
+                    }
+                    _ => Err(InputValueError::expected_type(type_name, &value)),
+                },
+                _ => Err(InputValueError::expected_type(type_name, &value)),
+            }
+        }
+    }
+}
@@ -376,6 +376,11 @@ fn visit_selection<'a, V: Visitor<'a>>(
                         visit_field(v, ctx, field);
                     },
                 );
+            } else if field.name == "__typename" {
+                // `__typename` can be queried on any composite type, including unions.
+                ctx.with_type(ctx.registry.types.get("String").unwrap(), |ctx| {
+                    visit_field(v, ctx, field);
+                });
             } else {
                 ctx.report_error(
                     vec![field.position],
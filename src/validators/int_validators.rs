@@ -0,0 +1,92 @@
+use crate::validators::{check_scalar, InputValueValidator};
+use crate::Value;
+
+/// Integer range validator, the range is inclusive.
+pub struct IntRange {
+    /// Minimum value, including this value.
+    pub min: i64,
+
+    /// Maximum value, including this value.
+    pub max: i64,
+}
+
+impl InputValueValidator for IntRange {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        check_scalar(value, &|value| match value {
+            Value::Int(n) => {
+                let n = n.as_i64()?;
+                if n < self.min || n > self.max {
+                    Some(format!(
+                        "the value is {}, must be between {} and {}",
+                        n, self.min, self.max
+                    ))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Integer less than validator
+pub struct IntLessThan {
+    /// Less than this value.
+    pub value: i64,
+}
+
+impl InputValueValidator for IntLessThan {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        check_scalar(value, &|value| match value {
+            Value::Int(n) => {
+                let n = n.as_i64()?;
+                if n >= self.value {
+                    Some(format!(
+                        "the value is {}, must be less than {}",
+                        n, self.value
+                    ))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Integer greater than validator
+pub struct IntGreaterThan {
+    /// Greater than this value.
+    pub value: i64,
+}
+
+impl InputValueValidator for IntGreaterThan {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        check_scalar(value, &|value| match value {
+            Value::Int(n) => {
+                let n = n.as_i64()?;
+                if n <= self.value {
+                    Some(format!(
+                        "the value is {}, must be greater than {}",
+                        n, self.value
+                    ))
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        })
+    }
+}
+
+/// Integer nonzero validator
+pub struct IntNonZero;
+
+impl InputValueValidator for IntNonZero {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        check_scalar(value, &|value| match value {
+            Value::Int(n) if n.as_i64() == Some(0) => Some("the value must be nonzero".to_string()),
+            _ => None,
+        })
+    }
+}
@@ -0,0 +1,40 @@
+use crate::validators::InputValueValidator;
+use crate::Value;
+
+/// List minimum length validator
+pub struct ListMinLength {
+    /// Must be greater than or equal to this value.
+    pub length: usize,
+}
+
+impl InputValueValidator for ListMinLength {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        match value {
+            Value::List(items) if items.len() < self.length => Some(format!(
+                "the value length is {}, must be greater than or equal to {}",
+                items.len(),
+                self.length
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// List maximum length validator
+pub struct ListMaxLength {
+    /// Must be less than or equal to this value.
+    pub length: usize,
+}
+
+impl InputValueValidator for ListMaxLength {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        match value {
+            Value::List(items) if items.len() > self.length => Some(format!(
+                "the value length is {}, must be less than or equal to {}",
+                items.len(),
+                self.length
+            )),
+            _ => None,
+        }
+    }
+}
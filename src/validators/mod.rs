@@ -0,0 +1,116 @@
+//! Input value validators
+
+mod int_validators;
+mod list_validators;
+mod string_validators;
+
+use crate::Value;
+
+pub use int_validators::{IntGreaterThan, IntLessThan, IntNonZero, IntRange};
+pub use list_validators::{ListMaxLength, ListMinLength};
+#[cfg(feature = "regex")]
+pub use string_validators::StringPattern;
+pub use string_validators::{Email, StringMaxLength, StringMinLength};
+
+/// Input value validator
+///
+/// Validators are specified with the `validator` attribute of the field arguments and of the input object fields,
+/// they are checked when the query is validated, and the variables used by the value are substituted before checking.
+/// `null` values are never passed to a validator.
+///
+/// The validators of this module check scalar values, if the argument is a list they check each item of it, so
+/// `#[arg(validator(Email))] emails: Vec<String>` requires every item to be an email.
+///
+/// # Examples
+///
+/// ```rust
+/// use async_graphql::*;
+/// use async_graphql::validators::{Email, IntRange, InputValueValidator};
+///
+/// struct EvenNumber;
+///
+/// impl InputValueValidator for EvenNumber {
+///     fn is_valid(&self, value: &Value) -> Option<String> {
+///         match value {
+///             Value::Int(n) if n.as_i64().unwrap() % 2 != 0 => {
+///                 Some(format!("the value is {}, but it must be even", n.as_i64().unwrap()))
+///             }
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// struct QueryRoot;
+///
+/// #[Object]
+/// impl QueryRoot {
+///     #[field]
+///     async fn value(
+///         &self,
+///         #[arg(validator(IntRange(min = "0", max = "10"), EvenNumber))] n: i32,
+///         #[arg(validator(Email))] email: Option<String>,
+///     ) -> i32 {
+///         n
+///     }
+/// }
+///
+/// #[async_std::main]
+/// async fn main() {
+///     let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+///     assert!(schema.query("{ value(n: 4) }").execute().await.is_ok());
+///     assert!(schema.query("{ value(n: 5) }").execute().await.is_err());
+///     assert!(schema.query("{ value(n: 12) }").execute().await.is_err());
+///     assert!(schema.query("{ value(n: 4, email: \"abc\") }").execute().await.is_err());
+/// }
+/// ```
+pub trait InputValueValidator: Send + Sync {
+    /// Check the value, return the reason if it is invalid.
+    fn is_valid(&self, value: &Value) -> Option<String>;
+}
+
+/// An extension trait for `InputValueValidator`
+pub trait ValidatorExt: InputValueValidator + Sized {
+    /// Merge the two validators, both must pass.
+    fn and<R: InputValueValidator>(self, other: R) -> And<Self, R> {
+        And(self, other)
+    }
+
+    /// Merge the two validators, either one must pass.
+    fn or<R: InputValueValidator>(self, other: R) -> Or<Self, R> {
+        Or(self, other)
+    }
+}
+
+impl<T: InputValueValidator> ValidatorExt for T {}
+
+/// Validator for `ValidatorExt::and`
+pub struct And<A: InputValueValidator, B: InputValueValidator>(A, B);
+
+impl<A: InputValueValidator, B: InputValueValidator> InputValueValidator for And<A, B> {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        self.0.is_valid(value).or_else(|| self.1.is_valid(value))
+    }
+}
+
+/// Validator for `ValidatorExt::or`
+pub struct Or<A: InputValueValidator, B: InputValueValidator>(A, B);
+
+impl<A: InputValueValidator, B: InputValueValidator> InputValueValidator for Or<A, B> {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        let err = self.0.is_valid(value)?;
+        self.1
+            .is_valid(value)
+            .map(|err2| format!("{} or {}", err, err2))
+    }
+}
+
+/// Check a scalar value, or each item of it if it is a list, the error tells the index of the invalid item.
+fn check_scalar(value: &Value, check: &dyn Fn(&Value) -> Option<String>) -> Option<String> {
+    match value {
+        Value::List(items) => items.iter().enumerate().find_map(|(idx, item)| {
+            check_scalar(item, check)
+                .map(|err| format!("the item at index {} is invalid, {}", idx, err))
+        }),
+        _ => check(value),
+    }
+}
@@ -0,0 +1,131 @@
+use crate::validators::{check_scalar, InputValueValidator};
+use crate::Value;
+
+/// String minimum length validator, the length is counted in characters.
+pub struct StringMinLength {
+    /// Must be greater than or equal to this value.
+    pub length: usize,
+}
+
+impl InputValueValidator for StringMinLength {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        check_scalar(value, &|value| match value {
+            Value::String(s) if s.chars().count() < self.length => Some(format!(
+                "the value length is {}, must be greater than or equal to {}",
+                s.chars().count(),
+                self.length
+            )),
+            _ => None,
+        })
+    }
+}
+
+/// String maximum length validator, the length is counted in characters.
+pub struct StringMaxLength {
+    /// Must be less than or equal to this value.
+    pub length: usize,
+}
+
+impl InputValueValidator for StringMaxLength {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        check_scalar(value, &|value| match value {
+            Value::String(s) if s.chars().count() > self.length => Some(format!(
+                "the value length is {}, must be less than or equal to {}",
+                s.chars().count(),
+                self.length
+            )),
+            _ => None,
+        })
+    }
+}
+
+/// Email validator
+pub struct Email;
+
+impl InputValueValidator for Email {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        check_scalar(value, &|value| match value {
+            Value::String(s) if !is_email(s) => Some("invalid email format".to_string()),
+            _ => None,
+        })
+    }
+}
+
+/// String pattern validator, the value must match the regular expression, it requires the `regex` feature.
+///
+/// The pattern is given as a literal, `#[arg(validator(StringPattern("^[a-z]+$")))]`.
+#[cfg(feature = "regex")]
+pub struct StringPattern {
+    regex: regex::Regex,
+}
+
+#[cfg(feature = "regex")]
+impl StringPattern {
+    /// Create a validator with the regular expression `pattern`.
+    ///
+    /// Panics if the pattern is invalid, it is created when the schema is built.
+    pub fn new(pattern: &str) -> Self {
+        match regex::Regex::new(pattern) {
+            Ok(regex) => Self { regex },
+            Err(err) => panic!("Invalid pattern \"{}\": {}", pattern, err),
+        }
+    }
+}
+
+#[cfg(feature = "regex")]
+impl InputValueValidator for StringPattern {
+    fn is_valid(&self, value: &Value) -> Option<String> {
+        check_scalar(value, &|value| match value {
+            Value::String(s) if !self.regex.is_match(s) => Some(format!(
+                "the value does not match the pattern \"{}\"",
+                self.regex.as_str()
+            )),
+            _ => None,
+        })
+    }
+}
+
+fn is_email(s: &str) -> bool {
+    let (local, domain) = match s.rfind('@') {
+        Some(idx) => (&s[..idx], &s[idx + 1..]),
+        None => return false,
+    };
+
+    let local_valid = !local.is_empty()
+        && local.len() <= 64
+        && !local.starts_with('.')
+        && !local.ends_with('.')
+        && !local.contains("..")
+        && local
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "!#$%&'*+-/=?^_`{|}~.".contains(c));
+
+    let domain_valid = domain.len() <= 255
+        && domain.contains('.')
+        && domain.split('.').all(|label| {
+            !label.is_empty()
+                && label.len() <= 63
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+        });
+
+    local_valid && domain_valid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email() {
+        assert!(is_email("sunli@example.com"));
+        assert!(is_email("a.b+c@mail.example.co"));
+        assert!(!is_email("sunli"));
+        assert!(!is_email("sunli@"));
+        assert!(!is_email("@example.com"));
+        assert!(!is_email("sunli@example"));
+        assert!(!is_email("sun..li@example.com"));
+        assert!(!is_email("sunli@-example.com"));
+    }
+}
@@ -0,0 +1,179 @@
+use async_graphql::http::GQLRequest;
+use async_graphql::*;
+
+struct User;
+
+#[Object(cache_control(max_age = 60))]
+impl User {
+    #[field]
+    async fn name(&self) -> &str {
+        "user"
+    }
+
+    #[field(cache_control(max_age = 10, private))]
+    async fn email(&self) -> &str {
+        "user@example.com"
+    }
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    #[field(cache_control(max_age = 30))]
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    #[field(cache_control(max_age = 120))]
+    async fn user(&self) -> User {
+        User
+    }
+
+    #[field]
+    async fn version(&self) -> i32 {
+        1
+    }
+
+    #[field(cache_control(max_age = 0))]
+    async fn random(&self) -> i32 {
+        4
+    }
+}
+
+struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    #[field(cache_control(max_age = 30))]
+    async fn action(&self) -> bool {
+        true
+    }
+}
+
+async fn cache_control(schema: &Schema<QueryRoot, MutationRoot>, query: &str) -> CacheControl {
+    schema
+        .query(query)
+        .execute_response()
+        .await
+        .unwrap()
+        .cache_control
+}
+
+#[async_std::test]
+pub async fn test_cache_control() {
+    let schema = Schema::new(QueryRoot, MutationRoot);
+
+    assert_eq!(
+        cache_control(&schema, "{ version }").await,
+        CacheControl {
+            public: true,
+            max_age: 0,
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "{ value }").await,
+        CacheControl {
+            public: true,
+            max_age: 30,
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "{ __typename value }").await,
+        CacheControl {
+            public: true,
+            max_age: 30,
+        }
+    );
+
+    // A field without hints makes the response uncacheable.
+    assert_eq!(
+        cache_control(&schema, "{ value version }").await,
+        CacheControl {
+            public: true,
+            max_age: 0,
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "{ value random }").await,
+        CacheControl {
+            public: true,
+            max_age: 0,
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "{ user { name } }").await,
+        CacheControl {
+            public: true,
+            max_age: 60,
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "{ value user { name } }").await,
+        CacheControl {
+            public: true,
+            max_age: 30,
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "{ value user { name email } }").await,
+        CacheControl {
+            public: false,
+            max_age: 10,
+        }
+    );
+
+    // The hints of the fields that are not resolved are ignored.
+    assert_eq!(
+        cache_control(&schema, "{ value user { name email @skip(if: true) } }").await,
+        CacheControl {
+            public: true,
+            max_age: 30,
+        }
+    );
+    assert_eq!(
+        cache_control(&schema, "query A { value } query B { user { email } }").await,
+        CacheControl {
+            public: true,
+            max_age: 30,
+        }
+    );
+
+    // The result of a mutation is never cached.
+    assert_eq!(
+        cache_control(&schema, "mutation { action }").await,
+        CacheControl::default()
+    );
+}
+
+#[async_std::test]
+pub async fn test_cache_control_header() {
+    let schema = Schema::new(QueryRoot, MutationRoot);
+    let execute = |query: &str| {
+        GQLRequest {
+            query: query.to_string(),
+            operation_name: None,
+            variables: None,
+        }
+        .execute(&schema)
+    };
+
+    assert_eq!(execute("{ version }").await.cache_control_header(), None);
+    assert_eq!(
+        execute("{ value }").await.cache_control_header(),
+        Some("max-age=30".to_string())
+    );
+    assert_eq!(
+        execute("{ user { email } }").await.cache_control_header(),
+        Some("max-age=10, private".to_string())
+    );
+    assert_eq!(
+        execute("{ value random }").await.cache_control_header(),
+        None
+    );
+    assert_eq!(execute("{ unknown }").await.cache_control_header(), None);
+    assert_eq!(
+        execute("mutation { action }").await.cache_control_header(),
+        None
+    );
+}
@@ -0,0 +1,144 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_default_value_arg() {
+    #[Enum]
+    enum MyEnum {
+        A,
+        B,
+    }
+
+    #[InputObject]
+    struct MyInput {
+        #[field(default)]
+        a: i32,
+        #[field(default_with = "vec![MyEnum::B]")]
+        b: Vec<MyEnum>,
+        #[field(default = "\"abc\"")]
+        c: String,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value1(&self, #[arg(default)] input: i32) -> i32 {
+            input
+        }
+
+        #[field]
+        async fn value2(&self, #[arg(default_with = "(1..=3).collect()")] input: Vec<i32>) -> i32 {
+            input.iter().sum()
+        }
+
+        #[field]
+        async fn value3(&self, #[arg(default_with = "MyEnum::B")] input: MyEnum) -> MyEnum {
+            input
+        }
+
+        #[field]
+        async fn value4(&self, #[arg(default = "{a: 5}")] input: MyInput) -> String {
+            format!("{} {:?} {}", input.a, input.b, input.c)
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    assert_eq!(
+        schema
+            .query(r#"{ value1 value2 value3 value4 other: value4(input: {}) }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "value1": 0,
+            "value2": 6,
+            "value3": "B",
+            "value4": "5 [B] abc",
+            "other": "0 [B] abc",
+        })
+    );
+
+    assert_eq!(
+        schema
+            .query(
+                r#"{
+                    __type(name: "Root") { fields { name args { name defaultValue } } }
+                    input: __type(name: "MyInput") { inputFields { name defaultValue } }
+                }"#
+            )
+            .execute()
+            .await
+            .unwrap()["input"],
+        serde_json::json!({
+            "inputFields": [
+                { "name": "a", "defaultValue": "0" },
+                { "name": "b", "defaultValue": "[B]" },
+                { "name": "c", "defaultValue": "\"abc\"" },
+            ]
+        })
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "Invalid default value for \"Root.value(input)\", expected type \"Int\", found \"abc\""
+)]
+pub fn test_invalid_default_value() {
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value(&self, #[arg(default = "\"abc\"")] input: i32) -> i32 {
+            input
+        }
+    }
+
+    Schema::new(Root, GQLEmptyMutation);
+}
+
+#[test]
+#[should_panic(
+    expected = "Invalid default value for \"MyInput.values[1]\", expected type \"Int!\", found null"
+)]
+pub fn test_invalid_input_object_default_value() {
+    #[InputObject]
+    struct MyInput {
+        #[field(default = "[1, null]")]
+        values: Vec<i32>,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value(&self, input: MyInput) -> i32 {
+            input.values.iter().sum()
+        }
+    }
+
+    Schema::new(Root, GQLEmptyMutation);
+}
+
+#[test]
+pub fn test_try_new_invalid_default_value() {
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value(&self, #[arg(default = "\"abc\"")] input: i32) -> i32 {
+            input
+        }
+    }
+
+    assert_eq!(
+        Schema::try_new(Root, GQLEmptyMutation)
+            .err()
+            .unwrap()
+            .to_string(),
+        "Invalid default value for \"Root.value(input)\", expected type \"Int\", found \"abc\""
+    );
+}
@@ -0,0 +1,258 @@
+use async_graphql::diff::*;
+use async_graphql::*;
+
+fn changes(diff: &SchemaDiff) -> Vec<(ChangeLevel, ChangeKind, &str)> {
+    diff.changes
+        .iter()
+        .map(|change| (change.level, change.kind, change.path.as_str()))
+        .collect()
+}
+
+#[test]
+pub fn test_diff_sdl() {
+    let old = r#"
+        type Query {
+            user(id: ID!): User
+            users(limit: Int = 10): [User!]!
+            search(text: String!): [SearchResult]
+        }
+
+        type User {
+            name: String!
+            email: String
+            role: Role
+            age: Int
+        }
+
+        type Post {
+            title: String
+        }
+
+        union SearchResult = User | Post
+
+        enum Role {
+            ADMIN
+            USER
+            GUEST
+        }
+
+        input UserFilter {
+            name: String
+            role: Role
+        }
+    "#;
+    let new = r#"
+        type Query {
+            user(id: ID!, deleted: Boolean!): User
+            users(limit: Int = 20, offset: Int): [User!]!
+            search(text: String): [SearchResult]
+        }
+
+        type User {
+            name: String
+            email: String!
+            role: Role
+            age: Int @deprecated
+        }
+
+        type Comment {
+            text: String
+        }
+
+        union SearchResult = User | Comment
+
+        enum Role {
+            ADMIN
+            USER
+            EDITOR
+        }
+
+        input UserFilter {
+            name: String
+            role: Role!
+            active: Boolean!
+        }
+    "#;
+
+    let diff = diff_sdl(old, new).unwrap();
+    assert!(diff.is_breaking());
+    assert_eq!(
+        changes(&diff),
+        vec![
+            (ChangeLevel::Safe, ChangeKind::TypeAdded, "Comment"),
+            (ChangeLevel::Breaking, ChangeKind::TypeRemoved, "Post"),
+            (
+                ChangeLevel::Safe,
+                ChangeKind::ArgumentTypeChanged,
+                "Query.search.text"
+            ),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::ArgumentAdded,
+                "Query.user.deleted"
+            ),
+            (
+                ChangeLevel::Dangerous,
+                ChangeKind::ArgumentDefaultValueChanged,
+                "Query.users.limit"
+            ),
+            (
+                ChangeLevel::Safe,
+                ChangeKind::ArgumentAdded,
+                "Query.users.offset"
+            ),
+            (
+                ChangeLevel::Dangerous,
+                ChangeKind::EnumValueAdded,
+                "Role.EDITOR"
+            ),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::EnumValueRemoved,
+                "Role.GUEST"
+            ),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::UnionMemberRemoved,
+                "SearchResult"
+            ),
+            (
+                ChangeLevel::Dangerous,
+                ChangeKind::UnionMemberAdded,
+                "SearchResult"
+            ),
+            (ChangeLevel::Safe, ChangeKind::FieldDeprecated, "User.age"),
+            (
+                ChangeLevel::Safe,
+                ChangeKind::FieldTypeChanged,
+                "User.email"
+            ),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::FieldTypeChanged,
+                "User.name"
+            ),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::InputFieldAdded,
+                "UserFilter.active"
+            ),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::InputFieldTypeChanged,
+                "UserFilter.role"
+            ),
+        ]
+    );
+
+    let report = serde_json::to_value(&diff).unwrap();
+    assert_eq!(
+        report["changes"][1],
+        serde_json::json!({
+            "level": "breaking",
+            "kind": "TYPE_REMOVED",
+            "path": "Post",
+            "message": "Type \"Post\" was removed.",
+        })
+    );
+
+    assert!(diff_sdl(old, old).unwrap().is_empty());
+    assert!(diff_sdl(old, "type Query {").is_err());
+}
+
+mod v1 {
+    use async_graphql::*;
+
+    #[Enum]
+    pub enum Status {
+        Active,
+        Archived,
+    }
+
+    pub struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        #[field]
+        async fn value(&self, #[arg(default = "1")] n: i32) -> i32 {
+            n
+        }
+
+        #[field]
+        async fn status(&self) -> Status {
+            Status::Active
+        }
+
+        #[field]
+        async fn name(&self) -> String {
+            "abc".to_string()
+        }
+    }
+}
+
+mod v2 {
+    use async_graphql::*;
+
+    #[Enum]
+    pub enum Status {
+        Active,
+    }
+
+    pub struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        #[field]
+        async fn value(&self, n: i32) -> i32 {
+            n
+        }
+
+        #[field]
+        async fn status(&self) -> Option<Status> {
+            None
+        }
+
+        #[field]
+        async fn count(&self) -> i32 {
+            0
+        }
+    }
+}
+
+#[test]
+pub fn test_diff_registry() {
+    let old = Schema::new(v1::QueryRoot, GQLEmptyMutation);
+    let new = Schema::new(v2::QueryRoot, GQLEmptyMutation);
+
+    let diff = diff_registry(old.registry(), new.registry());
+    assert_eq!(
+        changes(&diff),
+        vec![
+            (ChangeLevel::Safe, ChangeKind::FieldAdded, "QueryRoot.count"),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::FieldRemoved,
+                "QueryRoot.name"
+            ),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::FieldTypeChanged,
+                "QueryRoot.status"
+            ),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::ArgumentDefaultValueChanged,
+                "QueryRoot.value.n"
+            ),
+            (
+                ChangeLevel::Breaking,
+                ChangeKind::EnumValueRemoved,
+                "Status.ARCHIVED"
+            ),
+        ]
+    );
+    assert_eq!(diff.changes_of_level(ChangeLevel::Breaking).count(), 4);
+
+    assert_eq!(diff_sdl(&old.sdl(), &new.sdl()).unwrap(), diff);
+    assert!(diff_registry(old.registry(), old.registry()).is_empty());
+}
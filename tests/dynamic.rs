@@ -0,0 +1,441 @@
+use async_graphql::dynamic::*;
+use async_graphql::{Enum, QueryError, Value, Variables};
+use futures::{FutureExt, StreamExt};
+use std::time::Duration;
+
+struct Table {
+    name: String,
+    columns: Vec<Column>,
+}
+
+struct Column {
+    name: String,
+    kind: ColumnKind,
+}
+
+#[Enum]
+enum ColumnKind {
+    Int,
+    Text,
+}
+
+struct Version;
+
+#[async_graphql::Object]
+impl Version {
+    #[field]
+    async fn major(&self) -> i32 {
+        1
+    }
+}
+
+fn tables() -> Vec<Table> {
+    vec![
+        Table {
+            name: "users".to_string(),
+            columns: vec![
+                Column {
+                    name: "id".to_string(),
+                    kind: ColumnKind::Int,
+                },
+                Column {
+                    name: "name".to_string(),
+                    kind: ColumnKind::Text,
+                },
+            ],
+        },
+        Table {
+            name: "posts".to_string(),
+            columns: vec![Column {
+                name: "title".to_string(),
+                kind: ColumnKind::Text,
+            }],
+        },
+    ]
+}
+
+fn build_schema() -> Schema {
+    let named = Interface::new("Named").field(InterfaceField::new("name", "String!"));
+
+    let column = Object::new("Column")
+        .implement("Named")
+        .field(Field::new("name", "String!", |ctx| {
+            async move {
+                let column = ctx.parent_value.downcast_ref::<Column>().unwrap();
+                Ok(FieldValue::value(column.name.clone()))
+            }
+            .boxed()
+        }))
+        .field(Field::new("kind", "ColumnKind!", |ctx| {
+            async move {
+                let column = ctx.parent_value.downcast_ref::<Column>().unwrap();
+                Ok(FieldValue::output(column.kind))
+            }
+            .boxed()
+        }));
+
+    let table = Object::new("Table")
+        .implement("Named")
+        .field(Field::new("name", "String!", |ctx| {
+            async move {
+                let table = ctx.parent_value.downcast_ref::<Table>().unwrap();
+                Ok(FieldValue::value(table.name.clone()))
+            }
+            .boxed()
+        }))
+        .field(
+            Field::new("columns", "[Column!]!", |ctx| {
+                async move {
+                    let table = ctx.parent_value.downcast_ref::<Table>().unwrap();
+                    let kind = match ctx.arg("kind").await? {
+                        Value::Enum(kind) => Some(kind),
+                        _ => None,
+                    };
+                    Ok(FieldValue::list(
+                        table
+                            .columns
+                            .iter()
+                            .filter(|column| match &kind {
+                                Some(kind) => {
+                                    kind == if let ColumnKind::Int = column.kind {
+                                        "INT"
+                                    } else {
+                                        "TEXT"
+                                    }
+                                }
+                                None => true,
+                            })
+                            .map(|column| {
+                                FieldValue::owned_any(Column {
+                                    name: column.name.clone(),
+                                    kind: column.kind,
+                                })
+                            }),
+                    ))
+                }
+                .boxed()
+            })
+            .argument(InputValue::new("kind", "ColumnKind")),
+        );
+
+    let query = Object::new("Query")
+        .field(Field::new("tables", "[Table!]!", |ctx| {
+            async move {
+                let tables = ctx.data::<Vec<String>>();
+                Ok(FieldValue::list(tables.iter().map(|name| {
+                    let table = tables_by_name(name);
+                    FieldValue::owned_any(table)
+                })))
+            }
+            .boxed()
+        }))
+        .field(
+            Field::new("table", "Table", |ctx| {
+                async move {
+                    let name = ctx
+                        .param_value::<String, _>("name", None::<fn() -> Value>)
+                        .await?;
+                    Ok(tables()
+                        .into_iter()
+                        .find(|table| table.name == name)
+                        .map(FieldValue::owned_any)
+                        .unwrap_or(FieldValue::NULL))
+                }
+                .boxed()
+            })
+            .argument(InputValue::new("name", "String!")),
+        )
+        .field(Field::new("named", "[Named!]!", |_| {
+            async move {
+                Ok(FieldValue::list(vec![
+                    FieldValue::owned_any(tables_by_name("posts")).with_type("Table"),
+                    FieldValue::owned_any(Column {
+                        name: "id".to_string(),
+                        kind: ColumnKind::Int,
+                    })
+                    .with_type("Column"),
+                ]))
+            }
+            .boxed()
+        }))
+        .field(
+            Field::new("limit", "Int!", |ctx| {
+                async move {
+                    match ctx.arg("value").await? {
+                        Value::Int(value) => Ok(FieldValue::value(value.as_i64())),
+                        _ => Ok(FieldValue::NULL),
+                    }
+                }
+                .boxed()
+            })
+            .argument(InputValue::new("value", "Int").default_value(Value::Int(10.into()))),
+        )
+        .field(Field::new("version", "Version!", |_| {
+            async move { Ok(FieldValue::output(Version)) }.boxed()
+        }))
+        .field(Field::new("broken", "String!", |_| {
+            async move { Ok(FieldValue::NULL) }.boxed()
+        }));
+
+    Schema::build("Query", None)
+        .register(named)
+        .register(column)
+        .register(table)
+        .register(query)
+        .register_type::<ColumnKind>()
+        .register_type::<Version>()
+        .data(vec!["users".to_string(), "posts".to_string()])
+        .finish()
+        .unwrap()
+}
+
+fn tables_by_name(name: &str) -> Table {
+    tables()
+        .into_iter()
+        .find(|table| table.name == name)
+        .unwrap()
+}
+
+#[async_std::test]
+pub async fn test_dynamic_query() {
+    let schema = build_schema();
+    assert_eq!(
+        schema
+            .query("{ tables { name columns { name kind } } }")
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "tables": [
+                {
+                    "name": "users",
+                    "columns": [
+                        { "name": "id", "kind": "INT" },
+                        { "name": "name", "kind": "TEXT" },
+                    ],
+                },
+                {
+                    "name": "posts",
+                    "columns": [{ "name": "title", "kind": "TEXT" }],
+                },
+            ]
+        })
+    );
+
+    let mut variables = Variables::default();
+    variables.insert("kind".to_string(), Value::Enum("TEXT".to_string()));
+    assert_eq!(
+        schema
+            .query(
+                r#"query($kind: ColumnKind) {
+                    table(name: "users") { columns(kind: $kind) { name } }
+                    missing: table(name: "comments") { name }
+                    limit
+                    otherLimit: limit(value: 5)
+                    version { major }
+                }"#
+            )
+            .variables(&variables)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "table": { "columns": [{ "name": "name" }] },
+            "missing": null,
+            "limit": 10,
+            "otherLimit": 5,
+            "version": { "major": 1 },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_interface() {
+    let schema = build_schema();
+    assert_eq!(
+        schema
+            .query(
+                r#"{
+                    named {
+                        __typename
+                        name
+                        ... on Table { columns { name } }
+                        ...ColumnKind
+                    }
+                }
+
+                fragment ColumnKind on Column { kind }"#
+            )
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "named": [
+                { "__typename": "Table", "name": "posts", "columns": [{ "name": "title" }] },
+                { "__typename": "Column", "name": "id", "kind": "INT" },
+            ]
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_validation() {
+    let schema = build_schema();
+    assert!(schema
+        .query("{ tables { unknown } }")
+        .execute()
+        .await
+        .is_err());
+    assert!(schema.query("{ table { name } }").execute().await.is_err());
+    assert!(schema
+        .query(r#"{ table(name: "users") { columns(kind: OTHER) { name } } }"#)
+        .execute()
+        .await
+        .is_err());
+
+    let err = schema.query("{ broken }").execute().await.unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid value for type \"String!\", it cannot be null."
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_introspection() {
+    let schema = build_schema();
+    assert_eq!(
+        schema
+            .query(r#"{ __type(name: "Named") { kind possibleTypes { name } } }"#)
+            .execute()
+            .await
+            .unwrap()["__type"]["kind"],
+        serde_json::json!("INTERFACE")
+    );
+    assert!(schema.sdl().contains("type Table implements Named {"));
+}
+
+#[async_std::test]
+pub async fn test_dynamic_incremental() {
+    let schema = build_schema();
+    let response = schema
+        .query(
+            r#"{
+                tables @stream(initialCount: 1) { name }
+                ... @defer(label: "limit") { limit }
+            }"#,
+        )
+        .execute_incremental()
+        .await
+        .unwrap();
+    assert_eq!(
+        response.data,
+        serde_json::json!({ "tables": [{ "name": "users" }] })
+    );
+    assert!(response.has_next);
+    let patches = response.patches.collect::<Vec<_>>().await;
+    assert_eq!(
+        patches
+            .iter()
+            .map(|patch| (
+                patch.label.as_deref(),
+                &patch.path,
+                patch.data.as_ref(),
+                patch.has_next
+            ))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                None,
+                &serde_json::json!(["tables", 1]),
+                Some(&serde_json::json!({ "name": "posts" })),
+                true
+            ),
+            (
+                Some("limit"),
+                &serde_json::json!([]),
+                Some(&serde_json::json!({ "limit": 10 })),
+                false
+            ),
+        ]
+    );
+}
+
+#[async_std::test]
+pub async fn test_dynamic_options() {
+    let schema = Schema::build("Query", None)
+        .register(Object::new("Query").field(Field::new("slow", "Int!", |_| {
+            async move {
+                async_std::task::sleep(Duration::from_secs(2)).await;
+                Ok(FieldValue::value(1))
+            }
+            .boxed()
+        })))
+        .timeout(Duration::from_millis(50))
+        .disable_introspection()
+        .finish()
+        .unwrap();
+
+    let err = schema.query("{ slow }").execute().await.unwrap_err();
+    assert!(matches!(
+        err.downcast_ref::<QueryError>(),
+        Some(QueryError::Timeout)
+    ));
+    assert!(schema
+        .query(r#"{ __type(name: "Query") { name } }"#)
+        .execute()
+        .await
+        .is_err());
+    assert!(schema
+        .validate(&[r#"{ __type(name: "Query") { name } }"#])
+        .is_err());
+}
+
+fn resolver(_: ResolverContext<'_>) -> FieldFuture<'_> {
+    async move { Ok(FieldValue::NULL) }.boxed()
+}
+
+#[test]
+pub fn test_dynamic_schema_errors() {
+    let err = Schema::build("Query", None)
+        .register(Object::new("Query").field(Field::new("value", "Unknown", resolver)))
+        .finish()
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "The type \"Unknown\" of the field \"Query.value\" is not defined."
+    );
+
+    let err = Schema::build("Query", None)
+        .register(Object::new("Other"))
+        .finish()
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "The root type \"Query\" is not a dynamic object."
+    );
+
+    let err = Schema::build("Query", None)
+        .register(Interface::new("Named").field(InterfaceField::new("name", "String!")))
+        .register(
+            Object::new("Query")
+                .implement("Named")
+                .field(Field::new("name", "String", resolver)),
+        )
+        .finish()
+        .err()
+        .unwrap();
+    assert_eq!(
+        err.to_string(),
+        "The object \"Query\" does not have the field \"name: String!\" of the interface \"Named\"."
+    );
+
+    let err = Schema::build("Query", None)
+        .register(Object::new("Query").field(Field::new("value", "Int", resolver)))
+        .register(Object::new("Query"))
+        .finish()
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "The type \"Query\" is already defined.");
+}
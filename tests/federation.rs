@@ -0,0 +1,201 @@
+use async_graphql::*;
+
+struct User {
+    id: ID,
+}
+
+#[Object(extends)]
+impl User {
+    #[field(external)]
+    async fn id(&self) -> &ID {
+        &self.id
+    }
+
+    #[field]
+    async fn reviews(&self) -> Vec<Review> {
+        if self.id.as_str() == "1234" {
+            vec![Review {
+                body: "A highly effective form of birth control.".to_string(),
+                upc: "top-1".to_string(),
+            }]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+struct Review {
+    body: String,
+    upc: String,
+}
+
+#[Object]
+impl Review {
+    #[field]
+    async fn body(&self) -> &str {
+        &self.body
+    }
+
+    #[field(provides = "name")]
+    async fn product(&self) -> Product {
+        Product {
+            upc: self.upc.clone(),
+        }
+    }
+}
+
+struct Product {
+    upc: String,
+}
+
+#[Object(extends)]
+impl Product {
+    #[field(external)]
+    async fn upc(&self) -> &str {
+        &self.upc
+    }
+
+    #[field(requires = "upc")]
+    async fn in_stock(&self) -> bool {
+        self.upc == "top-1"
+    }
+}
+
+struct QueryRoot;
+
+#[Object(name = "Query", extends)]
+impl QueryRoot {
+    #[field]
+    async fn version(&self) -> i32 {
+        1
+    }
+
+    #[entity]
+    async fn find_user_by_id(&self, id: ID) -> User {
+        User { id }
+    }
+
+    #[entity]
+    async fn find_product_by_upc(&self, upc: String) -> Result<Product> {
+        if upc.starts_with("top-") {
+            Ok(Product { upc })
+        } else {
+            Err(anyhow::anyhow!("Product not found"))
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_federation_entities() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation).enable_federation();
+    let query = r#"{
+        _entities(representations: [
+            {__typename: "User", id: "1234"},
+            {__typename: "Product", upc: "top-2"}
+        ]) {
+            __typename
+            ... on User {
+                id
+                reviews { body product { upc } }
+            }
+            ... on Product {
+                upc
+                inStock
+            }
+        }
+    }"#;
+    assert_eq!(
+        schema.query(query).execute().await.unwrap(),
+        serde_json::json!({
+            "_entities": [
+                {
+                    "__typename": "User",
+                    "id": "1234",
+                    "reviews": [{
+                        "body": "A highly effective form of birth control.",
+                        "product": { "upc": "top-1" },
+                    }],
+                },
+                {
+                    "__typename": "Product",
+                    "upc": "top-2",
+                    "inStock": false,
+                },
+            ]
+        })
+    );
+
+    let query = r#"{ _entities(representations: [{__typename: "Product", upc: "bottom-1"}]) { __typename } }"#;
+    assert!(schema.query(query).execute().await.is_err());
+
+    let query =
+        r#"{ _entities(representations: [{__typename: "Review", body: "abc"}]) { __typename } }"#;
+    assert!(schema.query(query).execute().await.is_err());
+
+    let query = r#"{ _entities(representations: [{upc: "top-1"}]) { __typename } }"#;
+    assert!(schema.query(query).execute().await.is_err());
+}
+
+#[async_std::test]
+pub async fn test_federation_sdl() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation).enable_federation();
+    let sdl = schema
+        .query("{ _service { sdl } }")
+        .execute()
+        .await
+        .unwrap()["_service"]["sdl"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(
+        sdl,
+        r#"extend type Product @key(fields: "upc") {
+	inStock: Boolean! @requires(fields: "upc")
+	upc: String! @external
+}
+extend type Query {
+	version: Int!
+}
+type Review {
+	body: String!
+	product: Product! @provides(fields: "name")
+}
+extend type User @key(fields: "id") {
+	id: ID! @external
+	reviews: [Review!]!
+}
+"#
+    );
+}
+
+#[async_std::test]
+pub async fn test_federation_disabled() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+    assert!(schema
+        .query("{ _service { sdl } }")
+        .execute()
+        .await
+        .is_err());
+    assert_eq!(
+        schema.sdl(),
+        r#"type Product {
+	inStock: Boolean!
+	upc: String!
+}
+type Query {
+	version: Int!
+}
+type Review {
+	body: String!
+	product: Product!
+}
+type User {
+	id: ID!
+	reviews: [Review!]!
+}
+schema {
+	query: Query
+}
+"#
+    );
+}
@@ -0,0 +1,187 @@
+use async_graphql::*;
+
+#[derive(Clone)]
+struct User {
+    name: String,
+}
+
+#[Object]
+impl User {
+    #[field]
+    async fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+#[derive(Clone)]
+struct Post {
+    title: String,
+}
+
+#[Object]
+impl Post {
+    #[field]
+    async fn title(&self) -> &str {
+        &self.title
+    }
+}
+
+struct Page<T> {
+    items: Vec<T>,
+}
+
+#[Object]
+impl<T: GQLOutputValue + Send + Sync> Page<T> {
+    #[field]
+    async fn items(&self) -> &Vec<T> {
+        &self.items
+    }
+
+    #[field]
+    async fn count(&self) -> i32 {
+        self.items.len() as i32
+    }
+}
+
+struct Edge<T> {
+    node: T,
+}
+
+#[Object(
+    concretes(name = "UserEdge", params(User)),
+    concretes(name = "PostEdge", params(Post))
+)]
+impl<T: GQLOutputValue + Clone + Send + Sync> Edge<T> {
+    #[field]
+    async fn node(&self) -> T {
+        self.node.clone()
+    }
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    #[field]
+    async fn users(&self) -> Page<User> {
+        Page {
+            items: vec![User {
+                name: "jack".to_string(),
+            }],
+        }
+    }
+
+    #[field]
+    async fn posts(&self) -> Page<Post> {
+        Page {
+            items: vec![
+                Post {
+                    title: "a".to_string(),
+                },
+                Post {
+                    title: "b".to_string(),
+                },
+            ],
+        }
+    }
+
+    #[field]
+    async fn user_lists(&self) -> Page<Vec<User>> {
+        Page {
+            items: vec![vec![User {
+                name: "tom".to_string(),
+            }]],
+        }
+    }
+
+    #[field]
+    async fn optional_users(&self) -> Page<Option<User>> {
+        Page { items: vec![None] }
+    }
+
+    #[field]
+    async fn user_edge(&self) -> Edge<User> {
+        Edge {
+            node: User {
+                name: "rose".to_string(),
+            },
+        }
+    }
+
+    #[field]
+    async fn post_edge(&self) -> Edge<Post> {
+        Edge {
+            node: Post {
+                title: "c".to_string(),
+            },
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_generic_object() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+    let query = r#"{
+        users { __typename count items { name } }
+        posts { __typename count items { title } }
+        userEdge { __typename node { name } }
+        postEdge { __typename node { title } }
+    }"#;
+    assert_eq!(
+        schema.query(query).execute().await.unwrap(),
+        serde_json::json!({
+            "users": { "__typename": "UserPage", "count": 1, "items": [{ "name": "jack" }] },
+            "posts": { "__typename": "PostPage", "count": 2, "items": [{ "title": "a" }, { "title": "b" }] },
+            "userEdge": { "__typename": "UserEdge", "node": { "name": "rose" } },
+            "postEdge": { "__typename": "PostEdge", "node": { "title": "c" } },
+        })
+    );
+
+    let query = r#"{
+        userPage: __type(name: "UserPage") { name }
+        postEdge: __type(name: "PostEdge") { fields { name type { ofType { name } } } }
+        page: __type(name: "Page") { name }
+        edge: __type(name: "Edge") { name }
+    }"#;
+    assert_eq!(
+        schema.query(query).execute().await.unwrap(),
+        serde_json::json!({
+            "userPage": { "name": "UserPage" },
+            "postEdge": { "fields": [{ "name": "node", "type": { "ofType": { "name": "Post" } } }] },
+            "page": null,
+            "edge": null,
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_generic_object_wrapped_params() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+    let query = r#"{
+        users { __typename }
+        userLists { __typename count items { name } }
+        optionalUsers { __typename count items { name } }
+    }"#;
+    assert_eq!(
+        schema.query(query).execute().await.unwrap(),
+        serde_json::json!({
+            "users": { "__typename": "UserPage" },
+            "userLists": { "__typename": "UserListPage", "count": 1, "items": [[{ "name": "tom" }]] },
+            "optionalUsers": { "__typename": "UserNullablePage", "count": 1, "items": [null] },
+        })
+    );
+
+    let query = r#"{
+        userPage: __type(name: "UserPage") { name }
+        userListPage: __type(name: "UserListPage") { name }
+        userNullablePage: __type(name: "UserNullablePage") { name }
+    }"#;
+    assert_eq!(
+        schema.query(query).execute().await.unwrap(),
+        serde_json::json!({
+            "userPage": { "name": "UserPage" },
+            "userListPage": { "name": "UserListPage" },
+            "userNullablePage": { "name": "UserNullablePage" },
+        })
+    );
+}
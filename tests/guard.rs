@@ -0,0 +1,185 @@
+use async_graphql::guard::Guard;
+use async_graphql::*;
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+enum Role {
+    Admin,
+    Guest,
+}
+
+struct RoleGuard {
+    role: Role,
+}
+
+#[async_trait::async_trait]
+impl Guard for RoleGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        if ctx.data_opt::<Role>() == Some(&self.role) {
+            Ok(())
+        } else {
+            Err(FieldError::new("Forbidden").into())
+        }
+    }
+}
+
+struct Username(String);
+
+struct UserGuard {
+    username: &'static str,
+}
+
+#[async_trait::async_trait]
+impl Guard for UserGuard {
+    async fn check(&self, ctx: &Context<'_>) -> Result<()> {
+        if ctx.data_opt::<Username>().map(|name| name.0.as_str()) == Some(self.username) {
+            Ok(())
+        } else {
+            Err(FieldError::new("Forbidden").into())
+        }
+    }
+}
+
+#[async_std::test]
+pub async fn test_guard() {
+    #[InputObject]
+    struct MyInput {
+        #[field(guard(RoleGuard(role = "Role::Admin")))]
+        secret: Option<i32>,
+        value: i32,
+    }
+
+    struct MyObj;
+
+    #[Object]
+    impl MyObj {
+        #[field(guard(RoleGuard(role = "Role::Admin")))]
+        async fn value(&self) -> i32 {
+            1
+        }
+    }
+
+    #[Interface(field(name = "value", type = "i32"))]
+    struct MyInterface(MyObj);
+
+    struct QueryRoot;
+
+    #[Object]
+    impl QueryRoot {
+        #[field(guard(RoleGuard(role = "Role::Admin")))]
+        async fn value(&self) -> i32 {
+            1
+        }
+
+        #[field(guard(RoleGuard(role = "Role::Admin"), UserGuard(username = r#""test""#)))]
+        async fn value_and(&self) -> i32 {
+            2
+        }
+
+        #[field(guard(or(RoleGuard(role = "Role::Admin"), UserGuard(username = r#""test""#))))]
+        async fn value_or(&self) -> i32 {
+            3
+        }
+
+        #[field]
+        async fn input(&self, input: Vec<MyInput>) -> i32 {
+            input
+                .iter()
+                .map(|item| item.value + item.secret.unwrap_or_default())
+                .sum()
+        }
+
+        #[field]
+        async fn interface(&self) -> MyInterface {
+            MyObj.into()
+        }
+    }
+
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+
+    let err = schema
+        .query("{ value }")
+        .data(Role::Guest)
+        .execute()
+        .await
+        .unwrap_err();
+    let err = err.downcast_ref::<PositionError>().unwrap();
+    assert_eq!(err.to_string(), "Forbidden");
+    assert_eq!(err.position.line, 1);
+    assert_eq!(err.position.column, 3);
+
+    assert_eq!(
+        schema
+            .query("{ value }")
+            .data(Role::Admin)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "value": 1 })
+    );
+
+    assert!(schema
+        .query("{ valueAnd }")
+        .data(Role::Admin)
+        .execute()
+        .await
+        .is_err());
+    assert_eq!(
+        schema
+            .query("{ valueAnd }")
+            .data(Role::Admin)
+            .data(Username("test".to_string()))
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "valueAnd": 2 })
+    );
+
+    assert!(schema
+        .query("{ valueOr }")
+        .data(Role::Guest)
+        .execute()
+        .await
+        .is_err());
+    assert_eq!(
+        schema
+            .query("{ valueOr }")
+            .data(Role::Guest)
+            .data(Username("test".to_string()))
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "valueOr": 3 })
+    );
+
+    assert_eq!(
+        schema
+            .query("{ input(input: [{value: 1}, {value: 2}]) }")
+            .data(Role::Guest)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "input": 3 })
+    );
+    assert!(schema
+        .query("{ input(input: [{value: 1}, {value: 2, secret: 1}]) }")
+        .data(Role::Guest)
+        .execute()
+        .await
+        .is_err());
+    assert_eq!(
+        schema
+            .query("{ input(input: [{value: 1}, {value: 2, secret: 1}]) }")
+            .data(Role::Admin)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "input": 4 })
+    );
+
+    assert!(schema
+        .query("{ interface { value } }")
+        .data(Role::Guest)
+        .execute()
+        .await
+        .is_err());
+}
@@ -0,0 +1,421 @@
+use async_graphql::http::multipart_stream;
+use async_graphql::*;
+use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+struct Review {
+    id: i32,
+}
+
+#[Object]
+impl Review {
+    #[field]
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    #[field]
+    async fn body(&self) -> String {
+        format!("review{}", self.id)
+    }
+}
+
+struct Product;
+
+#[Object]
+impl Product {
+    #[field]
+    async fn name(&self) -> &str {
+        "product"
+    }
+
+    #[field]
+    async fn price(&self) -> i32 {
+        10
+    }
+
+    #[field]
+    async fn reviews(&self) -> Vec<Review> {
+        (1..=3).map(|id| Review { id }).collect()
+    }
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    #[field]
+    async fn product(&self) -> Product {
+        Product
+    }
+
+    #[field]
+    async fn numbers(&self) -> Vec<i32> {
+        vec![1, 2, 3, 4]
+    }
+}
+
+struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    #[field]
+    async fn create_product(&self) -> Product {
+        Product
+    }
+}
+
+/// The fields of a patch as a json value, the patches of these tests have no errors.
+fn patch_to_json(patch: IncrementalPatch) -> serde_json::Value {
+    assert!(patch.error.is_none());
+    serde_json::json!({
+        "label": patch.label,
+        "path": patch.path,
+        "data": patch.data,
+        "hasNext": patch.has_next,
+    })
+}
+
+async fn execute(
+    schema: &Schema<QueryRoot, MutationRoot>,
+    query: &str,
+) -> (serde_json::Value, bool, Vec<serde_json::Value>) {
+    let response = schema.query(query).execute_incremental().await.unwrap();
+    let patches = response
+        .patches
+        .map(patch_to_json)
+        .collect::<Vec<_>>()
+        .await;
+    (response.data, response.has_next, patches)
+}
+
+fn patch(
+    label: Option<&str>,
+    path: serde_json::Value,
+    data: serde_json::Value,
+    has_next: bool,
+) -> serde_json::Value {
+    serde_json::json!({
+        "label": label,
+        "path": path,
+        "data": data,
+        "hasNext": has_next,
+    })
+}
+
+#[async_std::test]
+pub async fn test_defer() {
+    let schema = Schema::new(QueryRoot, MutationRoot);
+    let query = r#"{
+        product {
+            name
+            ... @defer(label: "price") { price }
+            ...ProductReviews @defer
+        }
+    }
+
+    fragment ProductReviews on Product {
+        reviews { id }
+    }"#;
+    let (data, has_next, patches) = execute(&schema, query).await;
+    assert_eq!(
+        data,
+        serde_json::json!({ "product": { "name": "product" } })
+    );
+    assert!(has_next);
+    assert_eq!(
+        patches,
+        vec![
+            patch(
+                Some("price"),
+                serde_json::json!(["product"]),
+                serde_json::json!({ "price": 10 }),
+                true
+            ),
+            patch(
+                None,
+                serde_json::json!(["product"]),
+                serde_json::json!({ "reviews": [{ "id": 1 }, { "id": 2 }, { "id": 3 }] }),
+                false
+            ),
+        ]
+    );
+
+    // The directives are ignored by `execute`.
+    assert_eq!(
+        schema.query(query).execute().await.unwrap(),
+        serde_json::json!({
+            "product": {
+                "name": "product",
+                "price": 10,
+                "reviews": [{ "id": 1 }, { "id": 2 }, { "id": 3 }],
+            }
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_defer_in_list() {
+    let schema = Schema::new(QueryRoot, MutationRoot);
+    let query = r#"{
+        product {
+            reviews {
+                id
+                ... @defer { body }
+            }
+        }
+    }"#;
+    let (data, has_next, patches) = execute(&schema, query).await;
+    assert_eq!(
+        data,
+        serde_json::json!({ "product": { "reviews": [{ "id": 1 }, { "id": 2 }, { "id": 3 }] } })
+    );
+    assert!(has_next);
+    assert_eq!(
+        patches,
+        (0..3)
+            .map(|idx| patch(
+                None,
+                serde_json::json!(["product", "reviews", idx]),
+                serde_json::json!({ "body": format!("review{}", idx + 1) }),
+                idx < 2
+            ))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[async_std::test]
+pub async fn test_stream() {
+    let schema = Schema::new(QueryRoot, MutationRoot);
+    let query = r#"{
+        numbers @stream(initialCount: 2, label: "numbers")
+        product { reviews @stream { id } }
+    }"#;
+    let (data, has_next, patches) = execute(&schema, query).await;
+    assert_eq!(
+        data,
+        serde_json::json!({ "numbers": [1, 2], "product": { "reviews": [] } })
+    );
+    assert!(has_next);
+    assert_eq!(
+        patches,
+        vec![
+            patch(
+                Some("numbers"),
+                serde_json::json!(["numbers", 2]),
+                serde_json::json!(3),
+                true
+            ),
+            patch(
+                Some("numbers"),
+                serde_json::json!(["numbers", 3]),
+                serde_json::json!(4),
+                true
+            ),
+            patch(
+                None,
+                serde_json::json!(["product", "reviews", 0]),
+                serde_json::json!({ "id": 1 }),
+                true
+            ),
+            patch(
+                None,
+                serde_json::json!(["product", "reviews", 1]),
+                serde_json::json!({ "id": 2 }),
+                true
+            ),
+            patch(
+                None,
+                serde_json::json!(["product", "reviews", 2]),
+                serde_json::json!({ "id": 3 }),
+                false
+            ),
+        ]
+    );
+}
+
+#[async_std::test]
+pub async fn test_incremental_without_patches() {
+    let schema = Schema::new(QueryRoot, MutationRoot);
+
+    let (data, has_next, patches) = execute(&schema, "{ numbers }").await;
+    assert_eq!(data, serde_json::json!({ "numbers": [1, 2, 3, 4] }));
+    assert!(!has_next);
+    assert!(patches.is_empty());
+
+    let (data, has_next, patches) = execute(
+        &schema,
+        "{ numbers @stream(initialCount: 10) ... @defer(if: false) { product { price } } }",
+    )
+    .await;
+    assert_eq!(
+        data,
+        serde_json::json!({ "numbers": [1, 2, 3, 4], "product": { "price": 10 } })
+    );
+    assert!(!has_next);
+    assert!(patches.is_empty());
+
+    let (data, has_next, patches) = execute(
+        &schema,
+        "mutation { createProduct { name ... @defer { price } } }",
+    )
+    .await;
+    assert_eq!(
+        data,
+        serde_json::json!({ "createProduct": { "name": "product", "price": 10 } })
+    );
+    assert!(!has_next);
+    assert!(patches.is_empty());
+}
+
+#[derive(Default)]
+struct Calls {
+    product: AtomicUsize,
+    reviews: AtomicUsize,
+    body: AtomicUsize,
+}
+
+struct CountedReview {
+    id: i32,
+}
+
+#[Object]
+impl CountedReview {
+    #[field]
+    async fn id(&self) -> i32 {
+        self.id
+    }
+
+    #[field]
+    async fn body(&self, ctx: &Context<'_>) -> String {
+        ctx.data::<Arc<Calls>>().body.fetch_add(1, Ordering::SeqCst);
+        format!("review{}", self.id)
+    }
+}
+
+struct CountedProduct;
+
+#[Object]
+impl CountedProduct {
+    #[field]
+    async fn name(&self) -> &str {
+        "product"
+    }
+
+    #[field]
+    async fn reviews(&self, ctx: &Context<'_>) -> Vec<CountedReview> {
+        ctx.data::<Arc<Calls>>()
+            .reviews
+            .fetch_add(1, Ordering::SeqCst);
+        (1..=3).map(|id| CountedReview { id }).collect()
+    }
+}
+
+struct CountedQueryRoot;
+
+#[Object]
+impl CountedQueryRoot {
+    #[field]
+    async fn product(&self, ctx: &Context<'_>) -> CountedProduct {
+        ctx.data::<Arc<Calls>>()
+            .product
+            .fetch_add(1, Ordering::SeqCst);
+        CountedProduct
+    }
+}
+
+#[async_std::test]
+pub async fn test_incremental_resolver_calls() {
+    // The deferred fragments and the streamed items are resolved with the values of the initial payload, so every
+    // resolver is called once, as in a non-incremental execution.
+    let query = r#"{
+        product {
+            name
+            ... @defer { reviews @stream(initialCount: 1) { id ... @defer { body } } }
+            ... @defer { reviews { body } }
+        }
+    }"#;
+    let calls = Arc::new(Calls::default());
+    let schema = Schema::new(CountedQueryRoot, GQLEmptyMutation).data(calls.clone());
+    let response = schema.query(query).execute_incremental().await.unwrap();
+    let patches = response.patches.collect::<Vec<_>>().await;
+    assert_eq!(
+        response.data,
+        serde_json::json!({ "product": { "name": "product" } })
+    );
+    assert_eq!(
+        patches
+            .iter()
+            .map(|patch| patch.path.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            serde_json::json!(["product"]),
+            serde_json::json!(["product", "reviews", 0]),
+            serde_json::json!(["product", "reviews", 1]),
+            serde_json::json!(["product", "reviews", 1]),
+            serde_json::json!(["product", "reviews", 2]),
+            serde_json::json!(["product", "reviews", 2]),
+            serde_json::json!(["product"]),
+        ]
+    );
+    assert_eq!(
+        patches[0].data,
+        Some(serde_json::json!({ "reviews": [{ "id": 1 }] }))
+    );
+    assert_eq!(patches[2].data, Some(serde_json::json!({ "id": 2 })));
+    assert!(patches[..6].iter().all(|patch| patch.has_next));
+    assert!(!patches[6].has_next);
+
+    let counts = |calls: &Calls| {
+        (
+            calls.product.load(Ordering::SeqCst),
+            calls.reviews.load(Ordering::SeqCst),
+            calls.body.load(Ordering::SeqCst),
+        )
+    };
+    assert_eq!(counts(&calls), (1, 2, 6));
+
+    let calls = Arc::new(Calls::default());
+    let schema = Schema::new(CountedQueryRoot, GQLEmptyMutation).data(calls.clone());
+    schema.query(query).execute().await.unwrap();
+    assert_eq!(counts(&calls), (1, 2, 6));
+}
+
+#[async_std::test]
+pub async fn test_multipart_stream() {
+    let schema = Schema::new(QueryRoot, MutationRoot);
+    let response = schema
+        .query(r#"{ product { name ... @defer(label: "price") { price } } }"#)
+        .execute_incremental()
+        .await;
+    let body = multipart_stream(response)
+        .collect::<Vec<_>>()
+        .await
+        .concat();
+    // The key order of the JSON bodies depends on the `preserve_order` feature of `serde_json`, so the parts are
+    // compared as values.
+    assert!(body.starts_with("\r\n---"));
+    assert!(body.ends_with("\r\n-----\r\n"));
+    let parts = body["\r\n---".len()..body.len() - "\r\n-----\r\n".len()]
+        .split("\r\n---")
+        .map(|part| {
+            let json = part
+                .strip_prefix("\r\nContent-Type: application/json; charset=utf-8\r\n\r\n")
+                .unwrap();
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        parts,
+        vec![
+            serde_json::json!({ "data": { "product": { "name": "product" } }, "hasNext": true }),
+            serde_json::json!({
+                "data": { "price": 10 },
+                "hasNext": false,
+                "label": "price",
+                "path": ["product"],
+            }),
+        ]
+    );
+}
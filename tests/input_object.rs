@@ -93,3 +93,131 @@ pub async fn test_input_object_default_value() {
         })
     );
 }
+
+#[async_std::test]
+pub async fn test_input_value_errors() {
+    #[Enum]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[InputObject]
+    struct Item {
+        name: String,
+        color: Color,
+        count: i8,
+    }
+
+    #[InputObject]
+    struct MyInput {
+        items: Vec<Item>,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn count(&self, input: MyInput) -> i32 {
+            input
+                .items
+                .iter()
+                .filter(|item| item.color == Color::Red)
+                .map(|item| item.name.len() as i32 + item.count as i32)
+                .sum()
+        }
+    }
+
+    fn item(name: Value, color: &str, count: i32) -> Value {
+        let mut obj = std::collections::BTreeMap::new();
+        obj.insert("name".to_string(), name);
+        obj.insert("color".to_string(), Value::Enum(color.to_string()));
+        obj.insert("count".to_string(), Value::Int(count.into()));
+        Value::Object(obj)
+    }
+
+    async fn execute(
+        schema: &Schema<Root, GQLEmptyMutation>,
+        items: Vec<Value>,
+    ) -> Result<serde_json::Value> {
+        let mut input = std::collections::BTreeMap::new();
+        input.insert("items".to_string(), Value::List(items));
+        let mut variables = Variables::default();
+        variables.insert("input".to_string(), Value::Object(input));
+        schema
+            .query("query($input: MyInput!) { count(input: $input) }")
+            .variables(&variables)
+            .execute()
+            .await
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+
+    assert_eq!(
+        execute(
+            &schema,
+            vec![
+                item(Value::String("abc".to_string()), "RED", 1),
+                item(Value::String("abc".to_string()), "GREEN", 1)
+            ]
+        )
+        .await
+        .unwrap(),
+        serde_json::json!({ "count": 4 })
+    );
+
+    assert_eq!(
+        execute(
+            &schema,
+            vec![
+                item(Value::String("abc".to_string()), "RED", 1),
+                item(Value::Int(10.into()), "RED", 1)
+            ]
+        )
+        .await
+        .unwrap_err()
+        .to_string(),
+        "Invalid value for variable \"$input.items[1].name\", expected type \"String\", found 10\n"
+    );
+
+    assert_eq!(
+        execute(
+            &schema,
+            vec![item(Value::String("abc".to_string()), "BLUE", 1)]
+        )
+        .await
+        .unwrap_err()
+        .to_string(),
+        "Invalid value for variable \"$input.items[0].color\", enumeration type \"Color\" does not contain the value \"BLUE\"\n"
+    );
+
+    assert_eq!(
+        execute(
+            &schema,
+            vec![item(Value::String("abc".to_string()), "RED", 1000)]
+        )
+        .await
+        .unwrap_err()
+        .to_string(),
+        "Invalid value for argument \"input.items[0].count\", the value 1000 is out of range for \"i8\""
+    );
+
+    assert_eq!(
+        execute(&schema, vec![item(Value::Null, "RED", 1)])
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for variable \"$input.items[0].name\", expected type \"String!\", found null\n"
+    );
+
+    assert_eq!(
+        schema
+            .query(r#"{ count(input: { items: [{ name: "abc", color: RED, count: 1 }, { color: RED, count: 1 }] }) }"#)
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"input.items[1].name\", expected type \"String!\", but it is not provided\n"
+    );
+}
@@ -0,0 +1,63 @@
+use async_graphql::*;
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    #[field]
+    async fn value(&self) -> i32 {
+        10
+    }
+}
+
+#[async_std::test]
+pub async fn test_disable_introspection() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation).disable_introspection();
+    assert_eq!(
+        schema
+            .query("{ __schema { queryType { name } } }")
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Cannot query field \"__schema\": introspection is disabled\n"
+    );
+    assert!(schema
+        .query("{ __type(name: \"QueryRoot\") { name } }")
+        .execute()
+        .await
+        .is_err());
+    assert_eq!(
+        schema
+            .query("{ value __typename }")
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "value": 10, "__typename": "QueryRoot" })
+    );
+}
+
+#[async_std::test]
+pub async fn test_introspection_predicate() {
+    struct Token(&'static str);
+
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation)
+        .introspection_predicate(|data| data.get::<Token>().map(|token| token.0) == Some("staff"));
+    let query = "{ __schema { queryType { name } } }";
+    assert!(schema.query(query).execute().await.is_err());
+    assert!(schema
+        .query(query)
+        .data(Token("user"))
+        .execute()
+        .await
+        .is_err());
+    assert_eq!(
+        schema
+            .query(query)
+            .data(Token("staff"))
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "__schema": { "queryType": { "name": "QueryRoot" } } })
+    );
+}
@@ -0,0 +1,153 @@
+use async_graphql::*;
+
+struct Detail;
+
+#[Object]
+impl Detail {
+    #[field]
+    async fn c(&self) -> i32 {
+        1
+    }
+
+    #[field]
+    async fn d(&self, n: i32) -> i32 {
+        n
+    }
+}
+
+struct MyObj {
+    fields: Vec<String>,
+    exists: Vec<bool>,
+}
+
+#[Object]
+impl MyObj {
+    #[field]
+    async fn fields(&self) -> &Vec<String> {
+        &self.fields
+    }
+
+    #[field]
+    async fn exists(&self) -> &Vec<bool> {
+        &self.exists
+    }
+
+    #[field]
+    async fn a(&self) -> i32 {
+        1
+    }
+
+    #[field]
+    async fn b(&self, n: i32) -> i32 {
+        n
+    }
+
+    #[field]
+    async fn detail(&self) -> Detail {
+        Detail
+    }
+}
+
+struct Root;
+
+#[Object]
+impl Root {
+    #[field]
+    async fn obj(&self, ctx: &Context<'_>) -> Result<MyObj> {
+        fn describe(fields: Vec<SelectionField<'_>>, res: &mut Vec<String>) -> Result<()> {
+            for field in fields {
+                let args = field
+                    .arguments()?
+                    .into_iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect::<Vec<_>>();
+                res.push(format!(
+                    "{}:{}({})",
+                    field.alias().unwrap_or_else(|| field.name()),
+                    field.name(),
+                    args.join(",")
+                ));
+                describe(field.selection_fields(), res)?;
+            }
+            Ok(())
+        }
+
+        let mut fields = Vec::new();
+        describe(ctx.look_ahead().selection_fields(), &mut fields)?;
+
+        let look_ahead = ctx.look_ahead();
+        let exists = vec![
+            look_ahead.field("a").exists(),
+            look_ahead.field("b").exists(),
+            look_ahead.field("detail").exists(),
+            look_ahead.field("detail").field("c").exists(),
+            look_ahead.field("detail").field("d").exists(),
+            look_ahead.field("unknown").exists(),
+        ];
+        Ok(MyObj { fields, exists })
+    }
+}
+
+#[async_std::test]
+pub async fn test_look_ahead_fields() {
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    let resp = http::GQLRequest {
+        query: r#"query($n: Int!, $skip: Boolean!) {
+            obj {
+                fields
+                a @skip(if: $skip)
+                x: b(n: $n)
+                ... { detail { d(n: 3) } }
+                ...F @include(if: false)
+            }
+        }
+
+        fragment F on MyObj {
+            b(n: 1)
+        }"#
+        .to_string(),
+        operation_name: None,
+        variables: Some(serde_json::json!({ "n": 7, "skip": true })),
+    }
+    .execute(&schema)
+    .await;
+    assert_eq!(
+        serde_json::to_value(resp).unwrap()["data"]["obj"]["fields"],
+        serde_json::json!(["fields:fields()", "x:b(n=7)", "detail:detail()", "d:d(n=3)"])
+    );
+}
+
+#[async_std::test]
+pub async fn test_look_ahead_exists() {
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    let res = schema
+        .query(
+            r#"{
+                a: obj { exists a }
+                b: obj { exists ...F }
+                c: obj { exists ... { detail { c } } detail { d(n: 1) } }
+                d: obj { exists ... @skip(if: true) { a } b(n: 1) @include(if: false) }
+            }
+
+            fragment F on MyObj {
+                b(n: 1)
+                detail { ... { c } }
+            }"#,
+        )
+        .execute()
+        .await
+        .unwrap();
+    let exists = ["a", "b", "c", "d"]
+        .iter()
+        .map(|name| res[name]["exists"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        exists,
+        vec![
+            serde_json::json!([true, false, false, false, false, false]),
+            serde_json::json!([false, true, true, true, false, false]),
+            serde_json::json!([false, false, true, true, true, false]),
+            serde_json::json!([false, false, false, false, false, false]),
+        ]
+    );
+}
@@ -0,0 +1,73 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_maybe_undefined_type() {
+    #[InputObject]
+    struct MyInput {
+        value: MaybeUndefined<i32>,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value1(&self, input: MaybeUndefined<i32>) -> i32 {
+            if input.is_null() {
+                1
+            } else if input.is_undefined() {
+                2
+            } else {
+                input.take().unwrap()
+            }
+        }
+
+        #[field]
+        async fn value2(&self, input: MyInput) -> i32 {
+            if input.value.is_null() {
+                1
+            } else if input.value.is_undefined() {
+                2
+            } else {
+                input.value.take().unwrap()
+            }
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    let query = r#"
+        query($v: Int) {
+            v1:value1(input: 99)
+            v2:value1(input: null)
+            v3:value1
+            v4:value1(input: $v)
+            v5:value2(input: { value: 99 })
+            v6:value2(input: { value: null })
+            v7:value2(input: {})
+        }
+        "#;
+    assert_eq!(
+        schema.query(query).execute().await.unwrap(),
+        serde_json::json!({
+            "v1": 99,
+            "v2": 1,
+            "v3": 2,
+            "v4": 2,
+            "v5": 99,
+            "v6": 1,
+            "v7": 2,
+        })
+    );
+
+    let mut variables = Variables::default();
+    variables.insert("v".to_string(), Value::Null);
+    assert_eq!(
+        schema
+            .query("query($v: Int) { value1(input: $v) }")
+            .variables(&variables)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "value1": 1 })
+    );
+}
@@ -0,0 +1,141 @@
+use async_graphql::*;
+
+struct UserQuery {
+    name: String,
+}
+
+#[Object]
+impl UserQuery {
+    #[field]
+    async fn user_name(&self) -> &str {
+        &self.name
+    }
+
+    #[field]
+    async fn users(&self, count: i32) -> Vec<String> {
+        (0..count).map(|n| format!("user{}", n)).collect()
+    }
+}
+
+struct OrderQuery;
+
+#[Object]
+impl OrderQuery {
+    #[field]
+    async fn order_count(&self) -> i32 {
+        10
+    }
+}
+
+struct UserMutation;
+
+#[Object]
+impl UserMutation {
+    #[field]
+    async fn create_user(&self, name: String) -> String {
+        name
+    }
+}
+
+struct OrderMutation;
+
+#[Object]
+impl OrderMutation {
+    #[field]
+    async fn create_order(&self) -> i32 {
+        1
+    }
+}
+
+#[MergedObject(name = "Query", desc = "The merged query root")]
+struct QueryRoot(UserQuery, OrderQuery);
+
+#[MergedObject]
+struct MutationRoot {
+    user: UserMutation,
+    order: OrderMutation,
+}
+
+#[async_std::test]
+pub async fn test_merged_object() {
+    let schema = Schema::new(
+        QueryRoot(
+            UserQuery {
+                name: "abc".to_string(),
+            },
+            OrderQuery,
+        ),
+        MutationRoot {
+            user: UserMutation,
+            order: OrderMutation,
+        },
+    );
+
+    assert_eq!(
+        schema
+            .query("{ __typename userName users(count: 2) orderCount }")
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "__typename": "Query",
+            "userName": "abc",
+            "users": ["user0", "user1"],
+            "orderCount": 10,
+        })
+    );
+
+    assert_eq!(
+        schema
+            .query(r#"mutation { createUser(name: "def") createOrder }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "createUser": "def",
+            "createOrder": 1,
+        })
+    );
+
+    assert!(schema.query("{ unknown }").execute().await.is_err());
+
+    let mut fields = schema
+        .query(r#"{ __type(name: "Query") { description fields { name } } }"#)
+        .execute()
+        .await
+        .unwrap()["__type"]["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|field| field["name"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    fields.sort();
+    assert_eq!(fields, vec!["orderCount", "userName", "users"]);
+}
+
+#[test]
+pub fn test_merged_object_duplicate_field() {
+    struct OtherOrderQuery;
+
+    #[Object]
+    impl OtherOrderQuery {
+        #[field]
+        async fn order_count(&self) -> i32 {
+            20
+        }
+    }
+
+    #[MergedObject]
+    struct DuplicateQuery(OrderQuery, OtherOrderQuery);
+
+    assert_eq!(
+        Schema::try_new(
+            DuplicateQuery(OrderQuery, OtherOrderQuery),
+            GQLEmptyMutation,
+        )
+        .err()
+        .unwrap()
+        .to_string(),
+        "Field \"orderCount\" of the merged object \"DuplicateQuery\" is defined by both \"OrderQuery\" and \"OtherOrderQuery\""
+    );
+}
@@ -0,0 +1,90 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_path_node() {
+    struct Item;
+
+    #[Object]
+    impl Item {
+        #[field]
+        async fn path(&self, ctx: &Context<'_>) -> String {
+            ctx.path_node().unwrap().to_string()
+        }
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn items(&self) -> Vec<Item> {
+            vec![Item, Item]
+        }
+
+        #[field]
+        async fn item(&self) -> Item {
+            Item
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    let query = r#"{
+        items { path }
+        item { path alias: path }
+        other: item { path }
+    }"#;
+    assert_eq!(
+        schema.query(query).execute().await.unwrap(),
+        serde_json::json!({
+            "items": [
+                { "path": "items.0.path" },
+                { "path": "items.1.path" },
+            ],
+            "item": { "path": "item.path", "alias": "item.alias" },
+            "other": { "path": "other.path" },
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_error_path() {
+    struct Item(i32);
+
+    #[Object]
+    impl Item {
+        #[field]
+        async fn value(&self) -> Result<i32> {
+            if self.0 == 2 {
+                Err(FieldError::new("bad item").into())
+            } else {
+                Ok(self.0)
+            }
+        }
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn items(&self) -> Vec<Item> {
+            vec![Item(0), Item(1), Item(2)]
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    let request: http::GQLRequest = serde_json::from_value(serde_json::json!({
+        "query": "{\n  list: items { value }\n}",
+    }))
+    .unwrap();
+    assert_eq!(
+        serde_json::to_value(request.execute(&schema).await).unwrap(),
+        serde_json::json!({
+            "errors": [{
+                "message": "bad item",
+                "locations": [{ "line": 2, "column": 17 }],
+                "path": ["list", 2, "value"],
+            }]
+        })
+    );
+}
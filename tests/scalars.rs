@@ -53,3 +53,510 @@ test_scalars!(test_u64_scalar, u64, 10);
 test_scalars!(test_bool_scalar, bool, true);
 test_scalars!(test_f32_scalar, f32, 10.5);
 test_scalars!(test_f64_scalar, f32, 10.5);
+
+macro_rules! test_string_scalars {
+    ($test_name:ident, $ty:ty, $value:expr) => {
+        #[async_std::test]
+        pub async fn $test_name() {
+            #[InputObject]
+            struct MyInput {
+                value: $ty,
+            }
+
+            struct Root {
+                value: $ty,
+            }
+
+            #[Object]
+            impl Root {
+                #[field]
+                async fn value(&self) -> &$ty {
+                    &self.value
+                }
+
+                #[field]
+                async fn test_arg(&self, input: $ty) -> $ty {
+                    input
+                }
+
+                #[field]
+                async fn test_input(&self, input: MyInput) -> $ty {
+                    input.value
+                }
+            }
+
+            let value: $ty = GQLScalar::parse(&Value::String($value.to_string())).unwrap();
+            let schema = Schema::new(Root { value }, GQLEmptyMutation);
+            let query = format!(
+                "{{ value testArg(input: {0:?}) testInput(input: {{value: {0:?}}}) }}",
+                $value
+            );
+            assert_eq!(
+                schema.query(&query).execute().await.unwrap(),
+                serde_json::json!({ "value": $value, "testArg": $value, "testInput": $value })
+            );
+
+            let query = format!("{{ testArg(input: {:?}) }}", "!invalid!");
+            assert!(schema.query(&query).execute().await.is_err());
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+test_string_scalars!(
+    test_datetime_utc_scalar,
+    chrono::DateTime<chrono::Utc>,
+    "2020-03-01T12:30:00+00:00"
+);
+#[cfg(feature = "chrono")]
+test_string_scalars!(
+    test_datetime_fixed_offset_scalar,
+    chrono::DateTime<chrono::FixedOffset>,
+    "2020-03-01T12:30:00+08:00"
+);
+#[cfg(feature = "chrono")]
+test_string_scalars!(test_naive_date_scalar, chrono::NaiveDate, "2020-03-01");
+#[cfg(feature = "chrono")]
+test_string_scalars!(test_naive_time_scalar, chrono::NaiveTime, "12:30:00.250");
+#[cfg(feature = "chrono")]
+test_string_scalars!(
+    test_naive_datetime_scalar,
+    chrono::NaiveDateTime,
+    "2020-03-01T12:30:00"
+);
+#[cfg(feature = "chrono")]
+test_string_scalars!(test_duration_scalar, chrono::Duration, "P1DT9000.500S");
+#[cfg(feature = "uuid")]
+test_string_scalars!(
+    test_uuid_scalar,
+    uuid::Uuid,
+    "936da01f-9abd-4d9d-80c7-02af85c822a8"
+);
+#[cfg(feature = "url")]
+test_string_scalars!(test_url_scalar, url::Url, "https://example.com/a?b=c");
+#[cfg(feature = "rust_decimal")]
+test_string_scalars!(test_decimal_scalar, rust_decimal::Decimal, "-123.4500");
+#[cfg(feature = "bson")]
+test_string_scalars!(
+    test_object_id_scalar,
+    bson::oid::ObjectId,
+    "5e5c8b3fd0a8a9ff00b39a8a"
+);
+#[cfg(feature = "base64")]
+test_string_scalars!(test_base64_scalar, Base64, "aGVsbG8gd29ybGQ=");
+
+#[cfg(feature = "chrono")]
+#[async_std::test]
+pub async fn test_datetime_scalars_in_one_schema() {
+    use chrono::{DateTime, FixedOffset, Utc};
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn utc(&self, input: DateTime<Utc>) -> DateTime<Utc> {
+            input
+        }
+
+        #[field]
+        async fn fixed_offset(&self, input: DateTime<FixedOffset>) -> DateTime<FixedOffset> {
+            input
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    assert_eq!(
+        schema
+            .query(r#"{ utc(input: "2020-03-01T12:30:00+08:00") fixedOffset(input: "2020-03-01T12:30:00+08:00") }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "utc": "2020-03-01T04:30:00+00:00",
+            "fixedOffset": "2020-03-01T12:30:00+08:00",
+        })
+    );
+
+    // The variables are checked against the same `DateTime` scalar whichever type registered it.
+    let resp = http::GQLRequest {
+        query: "query($a: DateTime!, $b: DateTime!) { utc(input: $a) fixedOffset(input: $b) }"
+            .to_string(),
+        operation_name: None,
+        variables: Some(serde_json::json!({
+            "a": "2020-03-01T12:30:00Z",
+            "b": "2020-03-01T12:30:00-05:00",
+        })),
+    }
+    .execute(&schema)
+    .await;
+    assert_eq!(
+        serde_json::to_value(resp).unwrap(),
+        serde_json::json!({
+            "data": {
+                "utc": "2020-03-01T12:30:00+00:00",
+                "fixedOffset": "2020-03-01T12:30:00-05:00",
+            }
+        })
+    );
+
+    assert!(schema
+        .query(r#"{ utc(input: "2020-03-01 12:30:00") }"#)
+        .execute()
+        .await
+        .is_err());
+    assert_eq!(schema.sdl().matches("scalar DateTime").count(), 1);
+}
+
+#[async_std::test]
+pub async fn test_int_range() {
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value_i32(&self, n: i32) -> i32 {
+            n
+        }
+
+        #[field]
+        async fn value_u8(&self, n: u8) -> u8 {
+            n
+        }
+
+        #[field]
+        async fn value_i64(&self, n: i32) -> i64 {
+            n as i64 * 1000
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+
+    assert!(schema
+        .query("{ valueI32(n: 5000000000) }")
+        .execute()
+        .await
+        .is_err());
+    assert_eq!(
+        schema
+            .query("{ valueU8(n: 300) }")
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"n\", the value 300 is out of range for \"u8\""
+    );
+    assert_eq!(
+        schema
+            .query("{ valueI64(n: 1000) }")
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "valueI64": 1000000 })
+    );
+    assert_eq!(
+        schema
+            .query("{ valueI64(n: 5000000) }")
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Int cannot represent non 32-bit signed integer value: 5000000000"
+    );
+
+    let resp = http::GQLRequest {
+        query: "query($n: Int!) { valueI32(n: $n) }".to_string(),
+        operation_name: None,
+        variables: Some(serde_json::json!({ "n": 5_000_000_000i64 })),
+    }
+    .execute(&schema)
+    .await;
+    assert_eq!(
+        serde_json::to_value(resp).unwrap()["errors"][0]["message"],
+        "Invalid value for variable \"$n\", Int cannot represent non 32-bit signed integer value: 5000000000"
+    );
+
+    // The variables are coerced to `Int`, the range of the Rust type is checked by the argument.
+    let resp = http::GQLRequest {
+        query: "query($a: Int!, $b: Int!) { valueI32(n: $a) valueU8(n: $b) }".to_string(),
+        operation_name: None,
+        variables: Some(serde_json::json!({ "a": 300, "b": 300 })),
+    }
+    .execute(&schema)
+    .await;
+    assert_eq!(
+        serde_json::to_value(resp).unwrap()["errors"][0]["message"],
+        "Invalid value for argument \"n\", the value 300 is out of range for \"u8\""
+    );
+}
+
+#[async_std::test]
+pub async fn test_int64_scalars() {
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn int64(&self, n: Int64) -> Int64 {
+            n
+        }
+
+        #[field]
+        async fn big_int(&self, n: BigInt) -> BigInt {
+            n
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+
+    assert_eq!(
+        schema
+            .query(r#"{ a:int64(n: 5000000000) b:int64(n: "-5000000000") c:bigInt(n: 5000000000) d:bigInt(n: "-5000000000") }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "a": 5_000_000_000i64,
+            "b": -5_000_000_000i64,
+            "c": "5000000000",
+            "d": "-5000000000",
+        })
+    );
+
+    let resp = http::GQLRequest {
+        query: "query($a: Int64!, $b: BigInt!) { int64(n: $a) bigInt(n: $b) }".to_string(),
+        operation_name: None,
+        variables: Some(
+            serde_json::json!({ "a": 9_007_199_254_740_993i64, "b": "9007199254740993" }),
+        ),
+    }
+    .execute(&schema)
+    .await;
+    assert_eq!(
+        serde_json::to_value(resp).unwrap(),
+        serde_json::json!({
+            "data": {
+                "int64": 9_007_199_254_740_993i64,
+                "bigInt": "9007199254740993",
+            }
+        })
+    );
+
+    assert!(schema
+        .query(r#"{ int64(n: "abc") }"#)
+        .execute()
+        .await
+        .is_err());
+}
+
+#[async_std::test]
+pub async fn test_json_scalar() {
+    #[derive(serde_derive::Serialize, serde_derive::Deserialize)]
+    struct Metadata {
+        name: String,
+        tags: Vec<String>,
+        kind: String,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn metadata(&self, input: Json<Metadata>) -> Json<Metadata> {
+            input
+        }
+
+        #[field]
+        async fn tags(&self, input: Json<Metadata>) -> Json<Vec<String>> {
+            Json(input.0.tags)
+        }
+
+        #[field]
+        async fn untyped(&self, input: serde_json::Value) -> serde_json::Value {
+            input
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+
+    assert_eq!(
+        schema
+            .query(
+                r#"{
+                    metadata(input: { name: "abc", tags: ["a", "b"], kind: SIMPLE })
+                    tags(input: { name: "abc", tags: ["c"], kind: "SIMPLE" })
+                    untyped(input: { a: [1, 2.5, null, true], b: { c: ENUM } })
+                }"#
+            )
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "metadata": { "name": "abc", "tags": ["a", "b"], "kind": "SIMPLE" },
+            "tags": ["c"],
+            "untyped": { "a": [1, 2.5, null, true], "b": { "c": "ENUM" } },
+        })
+    );
+
+    let resp = http::GQLRequest {
+        query: "query($a: JSON!, $b: JSON!) { metadata(input: $a) untyped(input: $b) }".to_string(),
+        operation_name: None,
+        variables: Some(serde_json::json!({
+            "a": { "name": "abc", "tags": [], "kind": "X" },
+            "b": [{ "x": 1 }, "y"],
+        })),
+    }
+    .execute(&schema)
+    .await;
+    assert_eq!(
+        serde_json::to_value(resp).unwrap(),
+        serde_json::json!({
+            "data": {
+                "metadata": { "name": "abc", "tags": [], "kind": "X" },
+                "untyped": [{ "x": 1 }, "y"],
+            }
+        })
+    );
+
+    assert!(schema
+        .query(r#"{ metadata(input: { name: "abc" }) }"#)
+        .execute()
+        .await
+        .is_err());
+}
+
+#[async_std::test]
+pub async fn test_scalar_attribute() {
+    /// A value wrapped by a generic scalar, the name and description come from the attribute.
+    struct Tagged<T>(T);
+
+    #[Scalar(name = "TaggedInt", desc = "A tagged integer")]
+    impl<T: GQLScalar> GQLScalar for Tagged<T> {
+        fn parse(value: &Value) -> InputValueResult<Self> {
+            T::parse(value).map(Tagged)
+        }
+
+        fn to_json(&self) -> Result<serde_json::Value> {
+            Ok(serde_json::json!({ "tag": "int", "value": self.0.to_json()? }))
+        }
+    }
+
+    #[Scalar(specified_by_url = "https://tools.ietf.org/html/rfc5322")]
+    #[derive(Clone)]
+    struct Email(String);
+
+    #[Scalar(name = "Percent")]
+    struct Wrapper<T>(T);
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn tagged(&self, n: Tagged<i32>) -> Tagged<i32> {
+            n
+        }
+
+        #[field]
+        async fn email(&self, email: Email) -> Email {
+            email
+        }
+
+        #[field]
+        async fn percent(&self, n: Wrapper<f64>) -> Wrapper<f64> {
+            n
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    assert_eq!(
+        schema
+            .query(r#"{ tagged(n: 10) email(email: "a@example.com") percent(n: 0.5) }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "tagged": { "tag": "int", "value": 10 },
+            "email": "a@example.com",
+            "percent": 0.5,
+        })
+    );
+
+    assert!(schema
+        .query(r#"{ email(email: 10) }"#)
+        .execute()
+        .await
+        .is_err());
+
+    assert_eq!(
+        schema
+            .query(
+                r#"{
+                    tagged: __type(name: "TaggedInt") { kind description specifiedByURL }
+                    email: __type(name: "Email") { kind description specifiedByURL }
+                    percent: __type(name: "Percent") { kind }
+                }"#
+            )
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "tagged": { "kind": "SCALAR", "description": "A tagged integer", "specifiedByURL": null },
+            "email": {
+                "kind": "SCALAR",
+                "description": null,
+                "specifiedByURL": "https://tools.ietf.org/html/rfc5322",
+            },
+            "percent": { "kind": "SCALAR" },
+        })
+    );
+}
+
+#[async_std::test]
+#[allow(deprecated)]
+pub async fn test_impl_scalar_macro() {
+    struct MyInt(i32);
+
+    impl GQLScalar for MyInt {
+        fn type_name() -> &'static str {
+            "MyInt"
+        }
+
+        fn parse(value: &Value) -> InputValueResult<Self> {
+            match value {
+                Value::Int(n) => Ok(MyInt(n.as_i64().unwrap() as i32)),
+                _ => Err(InputValueError::expected_type("MyInt", value)),
+            }
+        }
+
+        fn to_json(&self) -> Result<serde_json::Value> {
+            Ok(self.0.into())
+        }
+    }
+
+    impl_scalar!(MyInt);
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn double(&self, n: MyInt) -> MyInt {
+            MyInt(n.0 * 2)
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    assert_eq!(
+        schema
+            .query(r#"{ double(n: 21) __type(name: "MyInt") { kind } }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "double": 42,
+            "__type": { "kind": "SCALAR" },
+        })
+    );
+}
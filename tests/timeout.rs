@@ -0,0 +1,114 @@
+use async_graphql::http::multipart_stream;
+use async_graphql::*;
+use futures::StreamExt;
+use std::time::{Duration, Instant};
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    #[field]
+    async fn fast(&self) -> i32 {
+        1
+    }
+
+    #[field]
+    async fn slow(&self) -> i32 {
+        async_std::task::sleep(Duration::from_secs(2)).await;
+        2
+    }
+}
+
+fn is_timeout(err: &Error) -> bool {
+    matches!(err.downcast_ref::<QueryError>(), Some(QueryError::Timeout))
+}
+
+#[async_std::test]
+pub async fn test_schema_timeout() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation).timeout(Duration::from_millis(50));
+    assert_eq!(
+        schema.query("{ fast }").execute().await.unwrap(),
+        serde_json::json!({ "fast": 1 })
+    );
+    let err = schema.query("{ fast slow }").execute().await.unwrap_err();
+    assert!(is_timeout(&err));
+}
+
+#[async_std::test]
+pub async fn test_query_timeout() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation).timeout(Duration::from_secs(10));
+    let err = schema
+        .query("{ slow }")
+        .timeout(Duration::from_millis(50))
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(is_timeout(&err));
+
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+    let err = schema
+        .query("{ slow }")
+        .timeout(Duration::from_millis(50))
+        .execute()
+        .await
+        .unwrap_err();
+    assert!(is_timeout(&err));
+}
+
+#[async_std::test]
+pub async fn test_incremental_initial_payload() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+    let start = Instant::now();
+    let response = schema
+        .query("{ fast ... @defer { slow } }")
+        .execute_incremental()
+        .await
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(response.data, serde_json::json!({ "fast": 1 }));
+    assert!(response.has_next);
+    let patches = response.patches.collect::<Vec<_>>().await;
+    assert!(start.elapsed() >= Duration::from_secs(2));
+    assert_eq!(patches.len(), 1);
+    assert_eq!(patches[0].data, Some(serde_json::json!({ "slow": 2 })));
+    assert!(!patches[0].has_next);
+}
+
+#[async_std::test]
+pub async fn test_incremental_timeout() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+    let response = schema
+        .query("{ fast ... @defer { slow } }")
+        .timeout(Duration::from_millis(50))
+        .execute_incremental()
+        .await
+        .unwrap();
+    assert_eq!(response.data, serde_json::json!({ "fast": 1 }));
+    let patches = response.patches.collect::<Vec<_>>().await;
+    assert_eq!(patches.len(), 1);
+    assert_eq!(patches[0].path, serde_json::json!([]));
+    assert!(patches[0].data.is_none());
+    assert!(is_timeout(patches[0].error.as_ref().unwrap()));
+    assert!(!patches[0].has_next);
+}
+
+#[async_std::test]
+pub async fn test_incremental_timeout_part() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+    let response = schema
+        .query(r#"{ fast ... @defer(label: "slow") { slow } }"#)
+        .timeout(Duration::from_millis(50))
+        .execute_incremental()
+        .await;
+    let parts = multipart_stream(response).collect::<Vec<_>>().await;
+    let json = parts[1]
+        .strip_prefix("\r\n---\r\nContent-Type: application/json; charset=utf-8\r\n\r\n")
+        .unwrap();
+    let mut part = serde_json::from_str::<serde_json::Value>(json).unwrap();
+    let errors = part.as_object_mut().unwrap().remove("errors").unwrap();
+    assert_eq!(errors.as_array().unwrap().len(), 1);
+    assert_eq!(
+        part,
+        serde_json::json!({ "label": "slow", "path": [], "data": null, "hasNext": false })
+    );
+}
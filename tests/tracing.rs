@@ -0,0 +1,159 @@
+#![cfg(feature = "tracing")]
+
+use async_graphql::*;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// The fields of a span or an event, formatted with `Debug`.
+#[derive(Default)]
+struct Fields(Vec<(String, String)>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .push((field.name().to_string(), format!("{:?}", value)));
+    }
+}
+
+impl Fields {
+    fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(field_name, _)| field_name == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// An event with the fields of the span it is recorded in.
+struct CapturedEvent {
+    level: Level,
+    fields: Fields,
+    span: Option<Arc<Fields>>,
+}
+
+/// A subscriber that captures the events.
+#[derive(Default)]
+struct CaptureSubscriber {
+    next_id: AtomicU64,
+    spans: Mutex<Vec<(Id, Arc<Fields>)>>,
+    stack: Mutex<Vec<Id>>,
+    events: Arc<Mutex<Vec<CapturedEvent>>>,
+}
+
+impl Subscriber for CaptureSubscriber {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1);
+        let mut fields = Fields::default();
+        fields
+            .0
+            .push(("name".to_string(), span.metadata().name().to_string()));
+        span.record(&mut fields);
+        self.spans
+            .lock()
+            .unwrap()
+            .push((id.clone(), Arc::new(fields)));
+        id
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let span = self.stack.lock().unwrap().last().and_then(|current| {
+            self.spans
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|(id, _)| id == current)
+                .map(|(_, fields)| fields.clone())
+        });
+        self.events.lock().unwrap().push(CapturedEvent {
+            level: *event.metadata().level(),
+            fields,
+            span,
+        });
+    }
+
+    fn enter(&self, span: &Id) {
+        self.stack.lock().unwrap().push(span.clone());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.stack.lock().unwrap().pop();
+    }
+}
+
+struct User;
+
+#[Object]
+impl User {
+    #[field]
+    async fn name(&self) -> Result<String> {
+        Err(anyhow::anyhow!("no name"))
+    }
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    #[field]
+    async fn value(&self) -> i32 {
+        10
+    }
+
+    #[field]
+    async fn user(&self) -> User {
+        User
+    }
+}
+
+#[test]
+pub fn test_field_error_event() {
+    let subscriber = CaptureSubscriber::default();
+    let events = subscriber.events.clone();
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+
+    tracing::subscriber::with_default(subscriber, || {
+        async_std::task::block_on(async {
+            assert!(schema
+                .query("{ value user { name } }")
+                .execute()
+                .await
+                .is_err());
+        })
+    });
+
+    let events = events.lock().unwrap();
+    let field_errors = events
+        .iter()
+        .filter(|event| event.fields.get("message") == Some("field failed"))
+        .collect::<Vec<_>>();
+
+    // The error is recorded once, in the span of the field that failed.
+    assert_eq!(field_errors.len(), 1);
+    let event = field_errors[0];
+    assert_eq!(event.level, Level::ERROR);
+    assert_eq!(event.fields.get("error"), Some("no name"));
+    let span = event.span.as_ref().unwrap();
+    assert_eq!(span.get("name"), Some("field"));
+    assert_eq!(span.get("parent_type"), Some("User"));
+    assert_eq!(span.get("field_name"), Some("name"));
+    assert_eq!(span.get("path"), Some("user.name"));
+
+    // The operation error is still recorded.
+    assert!(events
+        .iter()
+        .any(|event| event.fields.get("message") == Some("operation failed")));
+}
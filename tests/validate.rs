@@ -0,0 +1,121 @@
+use async_graphql::*;
+
+struct User;
+
+#[Object]
+impl User {
+    #[field]
+    async fn id(&self) -> i32 {
+        1
+    }
+
+    #[field]
+    async fn name(&self) -> &str {
+        "jack"
+    }
+}
+
+struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    #[field]
+    async fn user(&self, id: i32) -> User {
+        let _ = id;
+        User
+    }
+
+    #[field]
+    async fn users(&self) -> Vec<User> {
+        vec![User]
+    }
+}
+
+/// The source, the first location and the message of the errors.
+fn errors(err: Error) -> Vec<(usize, (usize, usize), String)> {
+    err.downcast_ref::<RuleErrors>()
+        .unwrap()
+        .errors
+        .iter()
+        .map(|error| {
+            let pos = error.locations[0];
+            (error.source, (pos.line, pos.column), error.message.clone())
+        })
+        .collect()
+}
+
+#[test]
+pub fn test_validate_document() {
+    let schema = Schema::new(QueryRoot, GQLEmptyMutation);
+
+    let operations = r#"
+        query GetUser($id: Int!) {
+            user(id: $id) { ...UserFields }
+        }
+
+        query GetUsers {
+            users { ...UserFields }
+        }
+    "#;
+    let fragments = r#"
+        fragment UserFields on User {
+            id
+            name
+        }
+    "#;
+    schema.validate(&[operations, fragments]).unwrap();
+
+    // The fragment is defined in another source.
+    let err = schema.validate(&[operations]).unwrap_err();
+    assert_eq!(
+        errors(err),
+        vec![
+            (0, (3, 32), "Unknown fragment: \"UserFields\"".to_string()),
+            (0, (7, 24), "Unknown fragment: \"UserFields\"".to_string()),
+        ]
+    );
+
+    // The locations are relative to the source where the error is.
+    let err = schema
+        .validate(&[fragments, operations, "fragment Unused on User { id }"])
+        .unwrap_err();
+    assert_eq!(
+        errors(err),
+        vec![(2, (1, 1), "Fragment \"Unused\" is never used".to_string())]
+    );
+    let err = schema
+        .validate(&[operations, "\n\n  fragment UserFields on User { id age }"])
+        .unwrap_err();
+    assert_eq!(
+        errors(err),
+        vec![(
+            1,
+            (3, 36),
+            "Cannot query field \"age\" on type \"User\".".to_string()
+        )]
+    );
+
+    // All the errors of all the operations are returned.
+    let err = schema
+        .validate(&[
+            "query A { user { age } } query B($n: Int) { users { name } }",
+            fragments,
+        ])
+        .unwrap_err();
+    let err = err.downcast::<RuleErrors>().unwrap();
+    assert_eq!(err.errors.len(), 4);
+
+    assert!(schema
+        .validate(&["{ __type(name: \"User\") { name } }"])
+        .is_ok());
+    assert!(Schema::new(QueryRoot, GQLEmptyMutation)
+        .disable_introspection()
+        .validate(&["{ __type(name: \"User\") { name } }"])
+        .is_err());
+
+    let err = schema
+        .validate(&[operations, fragments, "{ users {"])
+        .unwrap_err();
+    assert!(err.downcast_ref::<QueryParseError>().is_some());
+    assert!(err.to_string().starts_with("Source 2: "));
+}
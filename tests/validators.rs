@@ -0,0 +1,338 @@
+use async_graphql::validators::{
+    Email, InputValueValidator, IntRange, ListMaxLength, StringMinLength,
+};
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_argument_validators() {
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn int_range(&self, #[arg(validator(IntRange(min = 1, max = 10)))] n: i32) -> i32 {
+            n
+        }
+
+        #[field]
+        async fn email(&self, #[arg(validator(Email))] email: Option<String>) -> bool {
+            email.is_some()
+        }
+
+        #[field]
+        async fn list(
+            &self,
+            #[arg(validator(ListMaxLength(length = "2")))] values: Vec<i32>,
+        ) -> i32 {
+            values.iter().sum()
+        }
+
+        #[field]
+        async fn name(
+            &self,
+            #[arg(validator(or(StringMinLength(length = 3), Email)))] name: String,
+        ) -> String {
+            name
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+
+    assert_eq!(
+        schema.query("{ intRange(n: 5) }").execute().await.unwrap(),
+        serde_json::json!({ "intRange": 5 })
+    );
+    assert_eq!(
+        schema
+            .query("{ intRange(n: 11) }")
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"n\", the value is 11, must be between 1 and 10\n"
+    );
+
+    assert_eq!(
+        schema.query("{ email }").execute().await.unwrap(),
+        serde_json::json!({ "email": false })
+    );
+    assert_eq!(
+        schema
+            .query(r#"{ email(email: "sunli@example.com") }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "email": true })
+    );
+    assert_eq!(
+        schema
+            .query(r#"{ email(email: "sunli") }"#)
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"email\", invalid email format\n"
+    );
+
+    assert_eq!(
+        schema
+            .query("{ list(values: [1, 2]) }")
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "list": 3 })
+    );
+    assert!(schema
+        .query("{ list(values: [1, 2, 3]) }")
+        .execute()
+        .await
+        .is_err());
+
+    assert!(schema
+        .query(r#"{ name(name: "abc") }"#)
+        .execute()
+        .await
+        .is_ok());
+    assert!(schema
+        .query(r#"{ name(name: "a@b.cn") }"#)
+        .execute()
+        .await
+        .is_ok());
+    assert_eq!(
+        schema
+            .query(r#"{ name(name: "ab") }"#)
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"name\", the value length is 2, must be greater than or equal to 3 or invalid email format\n"
+    );
+}
+
+#[async_std::test]
+pub async fn test_variable_validators() {
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value(
+            &self,
+            #[arg(validator(IntRange(min = 1, max = 10)))] n: Option<i32>,
+        ) -> Option<i32> {
+            n
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    let query = "query($n: Int = 20) { value(n: $n) }";
+
+    let mut variables = Variables::default();
+    variables.insert("n".to_string(), Value::Int(5.into()));
+    assert_eq!(
+        schema
+            .query(query)
+            .variables(&variables)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "value": 5 })
+    );
+
+    let mut variables = Variables::default();
+    variables.insert("n".to_string(), Value::Int(50.into()));
+    assert!(schema
+        .query(query)
+        .variables(&variables)
+        .execute()
+        .await
+        .is_err());
+
+    assert!(schema.query(query).execute().await.is_err());
+}
+
+#[async_std::test]
+pub async fn test_input_object_validators() {
+    #[InputObject]
+    struct MyInput {
+        #[field(validator(StringMinLength(length = 2)))]
+        name: String,
+
+        #[field(validator(IntRange(min = 0, max = 150)))]
+        age: Option<i32>,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn count(&self, input: Vec<MyInput>) -> i32 {
+            input
+                .iter()
+                .map(|item| item.name.len() as i32 + item.age.unwrap_or_default())
+                .sum()
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+
+    assert_eq!(
+        schema
+            .query(r#"{ count(input: [{ name: "ab" }, { name: "cd", age: 20 }]) }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "count": 24 })
+    );
+    assert_eq!(
+        schema
+            .query(r#"{ count(input: [{ name: "ab" }, { name: "cd", age: 200 }]) }"#)
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"input[1].age\", the value is 200, must be between 0 and 150\n"
+    );
+}
+
+#[async_std::test]
+pub async fn test_custom_validator() {
+    struct NotBlank;
+
+    impl InputValueValidator for NotBlank {
+        fn is_valid(&self, value: &Value) -> Option<String> {
+            match value {
+                Value::String(s) if s.trim().is_empty() => Some("the value is blank".to_string()),
+                _ => None,
+            }
+        }
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value(&self, #[arg(validator(NotBlank))] s: String) -> String {
+            s
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    assert!(schema.query(r#"{ value(s: "a") }"#).execute().await.is_ok());
+    assert_eq!(
+        schema
+            .query(r#"{ value(s: "  ") }"#)
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"s\", the value is blank\n"
+    );
+}
+
+#[async_std::test]
+pub async fn test_list_item_validators() {
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn emails(
+            &self,
+            #[arg(validator(ListMaxLength(length = 2), Email))] emails: Vec<String>,
+        ) -> i32 {
+            emails.len() as i32
+        }
+
+        #[field]
+        async fn sum(
+            &self,
+            #[arg(validator(IntRange(min = 1, max = 10)))] values: Vec<i32>,
+        ) -> i32 {
+            values.iter().sum()
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+
+    assert_eq!(
+        schema
+            .query(r#"{ emails(emails: ["a@example.com", "b@example.com"]) }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "emails": 2 })
+    );
+    assert_eq!(
+        schema
+            .query(r#"{ emails(emails: ["a@example.com", "b"]) }"#)
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"emails\", the item at index 1 is invalid, invalid email format\n"
+    );
+    assert!(schema
+        .query(r#"{ emails(emails: ["a@example.com", "b@example.com", "c@example.com"]) }"#)
+        .execute()
+        .await
+        .is_err());
+
+    assert_eq!(
+        schema
+            .query("{ sum(values: [1, 2]) }")
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "sum": 3 })
+    );
+    assert_eq!(
+        schema
+            .query("{ sum(values: [1, 20]) }")
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"values\", the item at index 1 is invalid, the value is 20, must be between 1 and 10\n"
+    );
+}
+
+#[cfg(feature = "regex")]
+#[async_std::test]
+pub async fn test_pattern_validator() {
+    use async_graphql::validators::StringPattern;
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn code(
+            &self,
+            #[arg(validator(StringPattern("^[A-Z]{3}$")))] code: String,
+        ) -> String {
+            code
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    assert_eq!(
+        schema
+            .query(r#"{ code(code: "ABC") }"#)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({ "code": "ABC" })
+    );
+    assert_eq!(
+        schema
+            .query(r#"{ code(code: "abc") }"#)
+            .execute()
+            .await
+            .unwrap_err()
+            .to_string(),
+        "Invalid value for argument \"code\", the value does not match the pattern \"^[A-Z]{3}$\"\n"
+    );
+}
@@ -0,0 +1,215 @@
+use async_graphql::*;
+
+#[async_std::test]
+pub async fn test_nested_variables() {
+    #[InputObject]
+    struct Filter {
+        name: Option<String>,
+        and: Option<Vec<Filter>>,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn names(&self, filter: Filter) -> Vec<String> {
+            fn collect(filter: &Filter, names: &mut Vec<String>) {
+                names.extend(filter.name.clone());
+                for filter in filter.and.iter().flatten() {
+                    collect(filter, names);
+                }
+            }
+
+            let mut names = Vec::new();
+            collect(&filter, &mut names);
+            names
+        }
+
+        #[field]
+        async fn values(&self, values: Vec<Vec<Option<i32>>>) -> Vec<Vec<Option<i32>>> {
+            values
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    let mut variables = Variables::default();
+    variables.insert("a".to_string(), Value::String("a".to_string()));
+    variables.insert("b".to_string(), Value::String("b".to_string()));
+    variables.insert("n".to_string(), Value::Int(10.into()));
+
+    let query = r#"
+        query($a: String, $b: String, $n: Int) {
+            names(filter: { name: $a, and: [{ name: "c", and: [{ name: $b }] }] })
+            values(values: [[1, $n], [$n]])
+        }
+        "#;
+    assert_eq!(
+        schema
+            .query(query)
+            .variables(&variables)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "names": ["a", "c", "b"],
+            "values": [[1, 10], [10]],
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_missing_optional_variables() {
+    #[InputObject]
+    struct MyInput {
+        value: MaybeUndefined<i32>,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value(&self, input: Vec<MyInput>) -> Vec<String> {
+            input
+                .into_iter()
+                .map(|input| match input.value {
+                    MaybeUndefined::Undefined => "undefined".to_string(),
+                    MaybeUndefined::Null => "null".to_string(),
+                    MaybeUndefined::Value(n) => n.to_string(),
+                })
+                .collect()
+        }
+
+        #[field]
+        async fn list(&self, values: Vec<Option<i32>>) -> Vec<Option<i32>> {
+            values
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    let mut variables = Variables::default();
+    variables.insert("b".to_string(), Value::Null);
+
+    let query = r#"
+        query($a: Int, $b: Int, $c: Int = 10) {
+            value(input: [{ value: $a }, { value: $b }, { value: $c }])
+            list(values: [$a, $b, $c])
+        }
+        "#;
+    assert_eq!(
+        schema
+            .query(query)
+            .variables(&variables)
+            .execute()
+            .await
+            .unwrap(),
+        serde_json::json!({
+            "value": ["undefined", "null", "10"],
+            "list": [null, null, 10],
+        })
+    );
+}
+
+#[async_std::test]
+pub async fn test_variable_coercion() {
+    #[Enum]
+    enum Color {
+        Red,
+        Green,
+    }
+
+    #[InputObject]
+    struct MyInput {
+        id: ID,
+        colors: Vec<Color>,
+    }
+
+    struct Root;
+
+    #[Object]
+    impl Root {
+        #[field]
+        async fn value(&self, input: Option<MyInput>) -> i32 {
+            input
+                .map(|input| input.id.len() as i32 + input.colors.len() as i32)
+                .unwrap_or(0)
+        }
+
+        #[field]
+        async fn count(&self, n: Option<i32>) -> i32 {
+            n.unwrap_or_default()
+        }
+    }
+
+    let schema = Schema::new(Root, GQLEmptyMutation);
+    let execute = |variables: serde_json::Value| {
+        let schema = &schema;
+        async move {
+            serde_json::to_value(
+                http::GQLRequest {
+                    query: r#"
+                    query($id: ID!, $input: MyInput, $n: Int) {
+                        value(input: $input)
+                        v2: value(input: { id: $id, colors: [] }) @include(if: false)
+                        count(n: $n) @include(if: false)
+                    }"#
+                    .to_string(),
+                    operation_name: None,
+                    variables: Some(variables),
+                }
+                .execute(schema)
+                .await,
+            )
+            .unwrap()
+        }
+    };
+
+    assert_eq!(
+        execute(serde_json::json!({
+            "id": 1,
+            "input": { "id": "abc", "colors": ["RED", "GREEN"] },
+        }))
+        .await,
+        serde_json::json!({ "data": { "value": 5 } })
+    );
+
+    assert_eq!(
+        execute(serde_json::json!({
+            "id": 1,
+            "input": { "id": "abc", "colors": "RED" },
+        }))
+        .await,
+        serde_json::json!({ "data": { "value": 4 } })
+    );
+
+    let resp = execute(serde_json::json!({
+        "input": { "id": "abc", "colors": ["RED", "BLUE"] },
+        "n": "abc",
+    }))
+    .await;
+    let messages = resp["errors"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|err| err["message"].as_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        vec![
+            "Variable \"$id\" of required type \"ID!\" was not provided.",
+            "Invalid value for variable \"$input.colors[1]\", enumeration type \"Color\" does not contain the value \"BLUE\"",
+            "Invalid value for variable \"$n\", expected type \"Int\", found \"abc\"",
+        ]
+    );
+
+    let resp = execute(serde_json::json!({
+        "id": 1,
+        "input": { "id": "abc", "colors": [], "other": 1 },
+    }))
+    .await;
+    assert_eq!(
+        resp["errors"][0]["message"],
+        "Invalid value for variable \"$input\", unknown field \"other\" of type \"MyInput\""
+    );
+}